@@ -0,0 +1,414 @@
+//! `#[derive(McSerialize, McDeserialize)]` for `mcproto-rs`, which re-exports both with its
+//! `derive` feature. The `#[mc(...)]` attributes are documented in the `derive` module of that crate.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{
+    parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Error, Expr, Fields,
+    GenericArgument, Generics, Ident, Lit, PathArguments, Result, Token, Type,
+};
+
+#[proc_macro_derive(McSerialize, attributes(mc))]
+pub fn derive_serialize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_serialize(&input).unwrap_or_else(|err| err.to_compile_error()).into()
+}
+
+#[proc_macro_derive(McDeserialize, attributes(mc))]
+pub fn derive_deserialize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_deserialize(&input).unwrap_or_else(|err| err.to_compile_error()).into()
+}
+
+fn expand_serialize(input: &DeriveInput) -> Result<TokenStream2> {
+    let name = &input.ident;
+    let generics = with_bound(&input.generics, quote!(::mcproto_rs::Serialize));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            // bound like in the enum arms, so #[mc(if = ...)] conditions can name earlier fields
+            let fields = FieldSpec::parse_all(&data.fields)?;
+            let pattern = fields_pattern(&data.fields, &fields);
+            let writes = fields.iter().map(|field| {
+                let local = &field.local;
+                field.write(quote!(#local))
+            });
+            quote! {
+                let Self #pattern = self;
+                #(#writes)*
+            }
+        }
+        Data::Enum(data) => {
+            let tag = EnumSpec::parse(&input.attrs)?.tag;
+            let mut arms = Vec::new();
+            for variant in &data.variants {
+                let id = variant_id(variant)?;
+                let var_name = &variant.ident;
+                let fields = FieldSpec::parse_all(&variant.fields)?;
+                let pattern = fields_pattern(&variant.fields, &fields);
+                let writes = fields.iter().map(|field| {
+                    let local = &field.local;
+                    field.write(quote!(#local))
+                });
+                arms.push(quote! {
+                    Self::#var_name #pattern => {
+                        ::mcproto_rs::Serializer::serialize_other(__to, &<#tag>::from(#id))?;
+                        #(#writes)*
+                    }
+                });
+            }
+            quote! {
+                match self {
+                    #(#arms),*
+                }
+            }
+        }
+        Data::Union(_) => return Err(Error::new(input.span(), "unions can't be derived")),
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::mcproto_rs::Serialize for #name #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn mc_serialize<__S: ::mcproto_rs::Serializer>(&self, __to: &mut __S) -> ::mcproto_rs::SerializeResult {
+                #body
+                Ok(())
+            }
+        }
+    })
+}
+
+fn expand_deserialize(input: &DeriveInput) -> Result<TokenStream2> {
+    let name = &input.ident;
+    let name_str = name.to_string();
+    let generics = with_bound(&input.generics, quote!(::mcproto_rs::Deserialize));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let fields = FieldSpec::parse_all(&data.fields)?;
+            let reads = fields.iter().map(|field| field.read(None));
+            let construct = construct(quote!(Self), &data.fields, &fields);
+            quote! {
+                let __rest = __data;
+                #(#reads)*
+                ::mcproto_rs::Deserialized::ok(#construct, __rest)
+            }
+        }
+        Data::Enum(data) => {
+            let tag = EnumSpec::parse(&input.attrs)?.tag;
+            let mut branches = Vec::new();
+            for variant in &data.variants {
+                let id = variant_id(variant)?;
+                let var_name = &variant.ident;
+                let var_str = var_name.to_string();
+                let fields = FieldSpec::parse_all(&variant.fields)?;
+                let reads = fields.iter().map(|field| field.read(Some(&var_str)));
+                let construct = construct(quote!(Self::#var_name), &variant.fields, &fields);
+                branches.push(quote! {
+                    if __id == <#tag>::from(#id) {
+                        #(#reads)*
+                        return ::mcproto_rs::Deserialized::ok(#construct, __rest);
+                    }
+                });
+            }
+            quote! {
+                let ::mcproto_rs::Deserialized { value: __id, data: __rest } =
+                    <#tag as ::mcproto_rs::Deserialize>::mc_deserialize(__data)?;
+                #(#branches)*
+                Err(::mcproto_rs::DeserializeErr::CannotUnderstandValue(
+                    ::mcproto_rs::derive::__private::format!("invalid {} id {:?}", #name_str, __id)
+                ))
+            }
+        }
+        Data::Union(_) => return Err(Error::new(input.span(), "unions can't be derived")),
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::mcproto_rs::Deserialize for #name #ty_generics #where_clause {
+            fn mc_deserialize(__data: &[u8]) -> ::mcproto_rs::DeserializeResult<'_, Self> {
+                #body
+            }
+        }
+    })
+}
+
+fn with_bound(generics: &Generics, bound: TokenStream2) -> Generics {
+    let mut generics = generics.clone();
+    let params: Vec<Ident> = generics.type_params().map(|param| param.ident.clone()).collect();
+    let where_clause = generics.make_where_clause();
+    for param in params {
+        where_clause.predicates.push(parse_quote!(#param: #bound));
+    }
+    generics
+}
+
+fn fields_pattern(fields: &Fields, specs: &[FieldSpec]) -> TokenStream2 {
+    let locals = specs.iter().map(|field| &field.local);
+    match fields {
+        Fields::Named(_) => quote!({ #(#locals),* }),
+        Fields::Unnamed(_) => quote!((#(#locals),*)),
+        Fields::Unit => quote!(),
+    }
+}
+
+fn construct(path: TokenStream2, fields: &Fields, specs: &[FieldSpec]) -> TokenStream2 {
+    let locals = specs.iter().map(|field| &field.local);
+    match fields {
+        Fields::Named(_) => quote!(#path { #(#locals),* }),
+        Fields::Unnamed(_) => quote!(#path(#(#locals),*)),
+        Fields::Unit => path,
+    }
+}
+
+fn variant_id(variant: &syn::Variant) -> Result<TokenStream2> {
+    let mut id = None;
+    for arg in mc_args(&variant.attrs)? {
+        match (arg.key.to_string().as_str(), arg.value) {
+            ("id", Some(lit)) => id = Some(quote!(#lit)),
+            (other, _) => return Err(Error::new(arg.key.span(), format!("unknown variant attribute `{}`", other))),
+        }
+    }
+
+    match (id, &variant.discriminant) {
+        (Some(id), _) => Ok(id),
+        (None, Some((_, expr))) => Ok(quote!(#expr)),
+        (None, None) => Err(Error::new(variant.span(), "variant needs an id, like #[mc(id = 0x01)] or `= 0x01`")),
+    }
+}
+
+// one `key` or `key = literal` of a #[mc(...)] attribute
+struct McArg {
+    key: Ident,
+    value: Option<Lit>,
+}
+
+impl Parse for McArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        // keys like `if` are keywords, which Ident::parse won't accept
+        let key = Ident::parse_any(input)?;
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(Self { key, value })
+    }
+}
+
+fn mc_args(attrs: &[Attribute]) -> Result<Vec<McArg>> {
+    let mut out = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("mc")) {
+        out.extend(attr.parse_args_with(Punctuated::<McArg, Token![,]>::parse_terminated)?);
+    }
+    Ok(out)
+}
+
+fn str_value<T: syn::parse::Parse>(arg: &McArg) -> Result<T> {
+    match &arg.value {
+        Some(Lit::Str(value)) => value.parse(),
+        _ => Err(Error::new(arg.key.span(), format!("expected `{} = \"...\"`", arg.key))),
+    }
+}
+
+struct EnumSpec {
+    tag: Type,
+}
+
+impl EnumSpec {
+    fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut tag = parse_quote!(::mcproto_rs::types::VarInt);
+        for arg in mc_args(attrs)? {
+            match arg.key.to_string().as_str() {
+                "tag" => tag = str_value(&arg)?,
+                other => return Err(Error::new(arg.key.span(), format!("unknown enum attribute `{}`", other))),
+            }
+        }
+        Ok(Self { tag })
+    }
+}
+
+enum Codec {
+    Plain,
+    VarInt,
+    VarLong,
+    Counted { counter: Box<Type>, elem: Box<Type> },
+    Rest,
+}
+
+struct FieldSpec {
+    local: Ident,
+    name: String,
+    value_ty: Type,
+    codec: Codec,
+    condition: Option<Expr>,
+}
+
+impl FieldSpec {
+    fn parse_all(fields: &Fields) -> Result<Vec<Self>> {
+        let mut out = Vec::new();
+        for (idx, field) in fields.iter().enumerate() {
+            let spec = Self::parse(idx, field)?;
+            if let Some(Self { codec: Codec::Rest, .. }) = out.last() {
+                return Err(Error::new(field.span(), "#[mc(rest)] has to be on the last field"));
+            }
+            out.push(spec);
+        }
+        Ok(out)
+    }
+
+    fn parse(idx: usize, field: &syn::Field) -> Result<Self> {
+        let (local, name) = match &field.ident {
+            Some(ident) => (ident.clone(), ident.to_string()),
+            None => (format_ident!("__field{}", idx), idx.to_string()),
+        };
+
+        let mut codec = None;
+        let mut condition = None;
+        for arg in mc_args(&field.attrs)? {
+            let parsed = match arg.key.to_string().as_str() {
+                "varint" => Codec::VarInt,
+                "varlong" => Codec::VarLong,
+                "rest" => Codec::Rest,
+                "count" => Codec::Counted { counter: Box::new(str_value(&arg)?), elem: Box::new(parse_quote!(())) },
+                "if" => {
+                    condition = Some(str_value(&arg)?);
+                    continue;
+                }
+                other => return Err(Error::new(arg.key.span(), format!("unknown field attribute `{}`", other))),
+            };
+            if codec.replace(parsed).is_some() {
+                return Err(Error::new(arg.key.span(), "a field can only have one of varint, varlong, count and rest"));
+            }
+        }
+
+        // #[mc(if = ...)] fields are Option<T>, and everything else describes the T
+        let value_ty = if condition.is_some() {
+            generic_arg(&field.ty, "Option")
+                .ok_or_else(|| Error::new(field.ty.span(), "#[mc(if = ...)] fields have to be an Option"))?
+        } else {
+            field.ty.clone()
+        };
+
+        let codec = match codec.unwrap_or(Codec::Plain) {
+            Codec::Counted { counter, .. } => {
+                let elem = generic_arg(&value_ty, "Vec")
+                    .ok_or_else(|| Error::new(field.ty.span(), "#[mc(count = ...)] fields have to be a Vec"))?;
+                Codec::Counted { counter, elem: Box::new(elem) }
+            }
+            other => other,
+        };
+
+        Ok(Self { local, name, value_ty, codec, condition })
+    }
+
+    // statement which writes the field to `__to`, given `field`, a reference to it. Conditions
+    // see the earlier fields as references too.
+    fn write(&self, field: TokenStream2) -> TokenStream2 {
+        let value = match &self.condition {
+            Some(_) => quote!(__value),
+            None => field.clone(),
+        };
+
+        let write = match &self.codec {
+            Codec::Plain => quote! {
+                ::mcproto_rs::Serializer::serialize_other(__to, #value)?;
+            },
+            Codec::VarInt => quote! {
+                ::mcproto_rs::Serializer::serialize_other(__to, &::mcproto_rs::types::VarInt(*#value))?;
+            },
+            Codec::VarLong => quote! {
+                ::mcproto_rs::Serializer::serialize_other(__to, &::mcproto_rs::types::VarLong(*#value))?;
+            },
+            Codec::Counted { counter, .. } => quote! {
+                ::mcproto_rs::derive::__private::serialize_counted::<#counter, _, _>((#value).as_slice(), __to)?;
+            },
+            Codec::Rest => quote! {
+                ::mcproto_rs::Serializer::serialize_bytes(__to, (#value).as_slice())?;
+            },
+        };
+
+        let name = &self.name;
+        match &self.condition {
+            Some(condition) => {
+                let condition_str = quote!(#condition).to_string();
+                quote! {
+                    match (#field, ::mcproto_rs::derive::Condition::holds(&(#condition))) {
+                        (Some(__value), true) => {
+                            #write
+                        }
+                        (None, false) => {}
+                        (Some(_), false) => return Err(::mcproto_rs::SerializeErr::CannotSerialize(
+                            ::mcproto_rs::derive::__private::format!("{} is set, but `{}` doesn't hold", #name, #condition_str)
+                        )),
+                        (None, true) => return Err(::mcproto_rs::SerializeErr::CannotSerialize(
+                            ::mcproto_rs::derive::__private::format!("{} is missing, but `{}` holds", #name, #condition_str)
+                        )),
+                    }
+                }
+            }
+            None => write,
+        }
+    }
+
+    // statement which reads the field into a local named after it, from `__rest`
+    fn read(&self, variant: Option<&str>) -> TokenStream2 {
+        let value_ty = &self.value_ty;
+        let read = match &self.codec {
+            Codec::Plain => quote!(<#value_ty as ::mcproto_rs::Deserialize>::mc_deserialize(__rest)),
+            Codec::VarInt => quote!(::mcproto_rs::derive::__private::deserialize_varint(__rest)),
+            Codec::VarLong => quote!(::mcproto_rs::derive::__private::deserialize_varlong(__rest)),
+            Codec::Counted { counter, elem } => quote! {
+                ::mcproto_rs::derive::__private::deserialize_counted::<#counter, #elem>(__rest)
+            },
+            Codec::Rest => quote!(::mcproto_rs::derive::__private::deserialize_rest(__rest)),
+        };
+
+        let read = match &self.condition {
+            Some(condition) => quote! {
+                if ::mcproto_rs::derive::Condition::holds(&(#condition)) {
+                    #read.map(|__read| __read.map(Some))
+                } else {
+                    ::mcproto_rs::Deserialized::ok(None, __rest)
+                }
+            },
+            None => read,
+        };
+
+        let local = &self.local;
+        let name = &self.name;
+        let in_variant = variant.map(|variant| quote!(.in_field(#variant, 0)));
+        quote! {
+            let ::mcproto_rs::Deserialized { value: #local, data: __rest } = #read
+                .map_err(|__err| __err.in_field(#name, __data.len() - __rest.len())#in_variant)?;
+        }
+    }
+}
+
+// T out of Option<T> or Vec<T>
+fn generic_arg(ty: &Type, wrapper: &str) -> Option<Type> {
+    let path = match ty {
+        Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return None,
+    };
+    let segment = path.segments.last()?;
+    if segment.ident != wrapper {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match args.args.first()? {
+            GenericArgument::Type(ty) => Some(ty.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
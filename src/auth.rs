@@ -0,0 +1,214 @@
+use crate::utils::hex;
+use alloc::{fmt, format, string::String, vec::Vec};
+use rsa::pkcs8::{DecodePublicKey, EncodePublicKey};
+use rsa::rand_core::OsRng;
+use rsa::{Pkcs1v15Encrypt, RsaPrivateKey, RsaPublicKey};
+use sha1::{Digest, Sha1};
+
+// the vanilla server generates a 1024 bit key on startup
+pub const SERVER_KEY_BITS: usize = 1024;
+
+pub enum AuthErr {
+    BadPublicKey(String),
+    KeyGenerationFailed(String),
+    EncryptFailed(String),
+    DecryptFailed(String),
+}
+
+impl fmt::Display for AuthErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use AuthErr::*;
+        match self {
+            BadPublicKey(message) => f.write_fmt(format_args!("bad public key: {}", message)),
+            KeyGenerationFailed(message) => {
+                f.write_fmt(format_args!("failed to generate key: {}", message))
+            }
+            EncryptFailed(message) => f.write_fmt(format_args!("rsa encrypt failed: {}", message)),
+            DecryptFailed(message) => f.write_fmt(format_args!("rsa decrypt failed: {}", message)),
+        }
+    }
+}
+
+impl fmt::Debug for AuthErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <dyn fmt::Display>::fmt(self, f)
+    }
+}
+
+impl std::error::Error for AuthErr {}
+
+pub type AuthResult<T> = Result<T, AuthErr>;
+
+/// Computes the hash sent to the session server by `hasJoined` / `join`.
+///
+/// This is SHA-1 over `server_id`, the shared secret and the DER public key from
+/// `LoginEncryptionRequest`, rendered like java's `new BigInteger(digest).toString(16)`.
+pub fn server_hash(server_id: &str, shared_secret: &[u8], public_key: &[u8]) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(server_id.as_bytes());
+    hasher.update(shared_secret);
+    hasher.update(public_key);
+    java_hex_digest(hasher.finalize().into())
+}
+
+// signed two's complement, in hex, without leading zeros
+fn java_hex_digest(mut digest: [u8; 20]) -> String {
+    let negative = (digest[0] & 0x80) != 0;
+    if negative {
+        let mut carry = true;
+        for byte in digest.iter_mut().rev() {
+            *byte = !*byte;
+            if carry {
+                let (sum, overflow) = byte.overflowing_add(1);
+                *byte = sum;
+                carry = overflow;
+            }
+        }
+    }
+
+    let digits = hex(&digest);
+    let digits = digits.trim_start_matches('0');
+    let digits = if digits.is_empty() { "0" } else { digits };
+    if negative {
+        format!("-{}", digits)
+    } else {
+        digits.into()
+    }
+}
+
+/// The server's RSA key pair, used to decrypt `LoginEncryptionResponse`.
+pub struct ServerPrivateKey {
+    key: RsaPrivateKey,
+    public_key_der: Vec<u8>,
+}
+
+impl ServerPrivateKey {
+    pub fn generate() -> AuthResult<Self> {
+        let key = RsaPrivateKey::new(&mut OsRng, SERVER_KEY_BITS)
+            .map_err(move |err| AuthErr::KeyGenerationFailed(format!("{}", err)))?;
+        Self::from_key(key)
+    }
+
+    pub fn from_key(key: RsaPrivateKey) -> AuthResult<Self> {
+        let public_key_der = key
+            .to_public_key()
+            .to_public_key_der()
+            .map_err(move |err| AuthErr::BadPublicKey(format!("{}", err)))?
+            .into_vec();
+
+        Ok(Self { key, public_key_der })
+    }
+
+    /// The X.509 DER encoded public key, as sent in `LoginEncryptionRequest::public_key`.
+    pub fn public_key_der(&self) -> &[u8] {
+        self.public_key_der.as_slice()
+    }
+
+    pub fn public_key(&self) -> ServerPublicKey {
+        ServerPublicKey {
+            key: self.key.to_public_key(),
+            der: self.public_key_der.clone(),
+        }
+    }
+
+    /// Decrypts the shared secret or verify token from `LoginEncryptionResponse`.
+    pub fn decrypt(&self, data: &[u8]) -> AuthResult<Vec<u8>> {
+        self.key
+            .decrypt(Pkcs1v15Encrypt, data)
+            .map_err(move |err| AuthErr::DecryptFailed(format!("{}", err)))
+    }
+}
+
+impl fmt::Debug for ServerPrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!("ServerPrivateKey(public={})", hex(self.public_key_der())))
+    }
+}
+
+/// The server's public key as received by a client in `LoginEncryptionRequest`.
+#[derive(Clone, PartialEq)]
+pub struct ServerPublicKey {
+    key: RsaPublicKey,
+    der: Vec<u8>,
+}
+
+impl ServerPublicKey {
+    pub fn from_der(der: &[u8]) -> AuthResult<Self> {
+        let key = RsaPublicKey::from_public_key_der(der)
+            .map_err(move |err| AuthErr::BadPublicKey(format!("{}", err)))?;
+
+        Ok(Self { key, der: der.to_vec() })
+    }
+
+    pub fn der(&self) -> &[u8] {
+        self.der.as_slice()
+    }
+
+    /// Encrypts the shared secret or verify token for `LoginEncryptionResponse`.
+    pub fn encrypt(&self, data: &[u8]) -> AuthResult<Vec<u8>> {
+        self.key
+            .encrypt(&mut OsRng, Pkcs1v15Encrypt, data)
+            .map_err(move |err| AuthErr::EncryptFailed(format!("{}", err)))
+    }
+}
+
+impl fmt::Debug for ServerPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!("ServerPublicKey({})", hex(self.der())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name_hash(name: &str) -> String {
+        server_hash(name, &[], &[])
+    }
+
+    // well known examples from wiki.vg
+    #[test]
+    fn test_server_hash_examples() {
+        assert_eq!(name_hash("Notch"), "4ed1f46bbe04bc756bcb17c0c7ce3e4632f06a48");
+        assert_eq!(name_hash("jeb_"), "-7c9d5b0044c130109a5d7b5fb5c317c02b4e28c1");
+        assert_eq!(name_hash("simon"), "88e16a1019277b15d58faf0541e11910eb756f6");
+    }
+
+    #[test]
+    fn test_server_hash_concatenates_parts() {
+        assert_eq!(server_hash("No", b"t", b"ch"), name_hash("Notch"));
+    }
+
+    #[test]
+    fn test_java_hex_digest_edges() {
+        assert_eq!(java_hex_digest([0u8; 20]), "0");
+        assert_eq!(java_hex_digest([0xFF; 20]), "-1");
+
+        let mut min = [0u8; 20];
+        min[0] = 0x80;
+        assert_eq!(java_hex_digest(min), "-8000000000000000000000000000000000000000");
+    }
+
+    #[test]
+    fn test_rsa_round_trip() {
+        let server = ServerPrivateKey::generate().expect("generates key");
+        let client = ServerPublicKey::from_der(server.public_key_der()).expect("valid der");
+        assert_eq!(client, server.public_key());
+
+        let secret = [7u8; 16];
+        let encrypted = client.encrypt(&secret).expect("encrypts");
+        assert_eq!(encrypted.len(), SERVER_KEY_BITS / 8);
+        assert_eq!(server.decrypt(encrypted.as_slice()).expect("decrypts"), secret);
+    }
+
+    #[test]
+    fn test_bad_public_key() {
+        assert!(matches!(ServerPublicKey::from_der(&[0x30, 0x03, 0x01]), Err(AuthErr::BadPublicKey(_))));
+    }
+
+    #[test]
+    fn test_bad_ciphertext() {
+        let server = ServerPrivateKey::generate().expect("generates key");
+        assert!(matches!(server.decrypt(&[1, 2, 3]), Err(AuthErr::DecryptFailed(_))));
+    }
+}
@@ -301,7 +301,7 @@ impl ByteOrder for LittleEndian {
     }
 
     fn read_ushort(data: &[u8]) -> DeserializeResult<'_, u16> {
-        Ok(take(4, data)?
+        Ok(take(2, data)?
             .map(move |bytes| (bytes[0] as u16) | ((bytes[1] as u16) << 8)))
     }
 
@@ -970,12 +970,40 @@ impl super::Serialize for Chat {
 
 impl super::Deserialize for Chat {
     fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
-        String::mc_deserialize(data)?.try_map(move |raw| {
-            serde_json::from_str(raw.as_str()).map_err(move |err|
-                super::DeserializeErr::FailedJsonDeserialize(format!(
-                    "failed to deserialize chat from JSON '{}' :: {:?}", raw, err
-                )))
-        })
+        let max_chars = crate::limits::current().max_chat_chars;
+        crate::types::deserialize_string_limited("chat", max_chars, data)?.try_map(move |raw| parse_json(raw.as_str()))
+    }
+}
+
+fn parse_json(raw: &str) -> Result<Chat, super::DeserializeErr> {
+    serde_json::from_str(raw).map_err(move |err|
+        super::DeserializeErr::FailedJsonDeserialize(format!(
+            "failed to deserialize chat from JSON '{}' :: {:?}", raw, err
+        )))
+}
+
+/// The JSON of a chat component, borrowed from the packet it was read from and not parsed yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChatRef<'a> {
+    pub json: &'a str,
+}
+
+impl ChatRef<'_> {
+    pub fn to_chat(&self) -> Result<Chat, super::DeserializeErr> {
+        parse_json(self.json)
+    }
+}
+
+impl super::Serialize for ChatRef<'_> {
+    fn mc_serialize<S: super::Serializer>(&self, to: &mut S) -> SerializeResult {
+        to.serialize_other(&self.json)
+    }
+}
+
+impl<'a> super::BorrowDeserialize<'a> for ChatRef<'a> {
+    fn mc_deserialize_borrowed(data: &'a [u8]) -> DeserializeResult<'a, Self> {
+        let max_chars = crate::limits::current().max_chat_chars;
+        Ok(crate::types::deserialize_str_limited("chat", max_chars, data)?.map(move |json| ChatRef { json }))
     }
 }
 
@@ -0,0 +1,469 @@
+use crate::connection::ConnectionState;
+use crate::frame::{read_frame, serialize_packet_payload, FrameResult, RawFrame};
+use crate::limits::DeserializeLimits;
+use crate::protocol::{HasCompressionThreshold, HasNextState, Packet, PacketDirection, State};
+use crate::types::BytesSerializer;
+use crate::Deserialized;
+use alloc::{fmt, vec::Vec};
+
+#[cfg(feature = "std")]
+use crate::frame::FrameErr;
+#[cfg(feature = "compression")]
+use crate::compression::Compression;
+#[cfg(feature = "encryption")]
+use crate::encryption::{CraftDecryptor, CraftEncryptor, EncryptionResult};
+
+/// Reads and writes framed packets of one protocol version, keeping track of the connection
+/// state (see `ConnectionState`), compression and encryption. This does no I/O itself; the `io` module wraps it for
+/// blocking streams, and with the `tokio-codec` feature it implements tokio's `Decoder` and
+/// `Encoder`.
+pub struct PacketCodec<P> {
+    connection: ConnectionState,
+    direction: PacketDirection,
+    limits: Option<DeserializeLimits>,
+    #[cfg(feature = "compression")]
+    compression: Compression,
+    #[cfg(feature = "encryption")]
+    encryptor: Option<CraftEncryptor>,
+    #[cfg(feature = "encryption")]
+    decryptor: Option<CraftDecryptor>,
+    #[cfg(feature = "encryption")]
+    decrypted: usize,
+    _packet: core::marker::PhantomData<fn() -> P>,
+}
+
+impl<P> PacketCodec<P> where P: Packet + HasCompressionThreshold + HasNextState {
+    /// Creates a codec which reads packets sent in `direction`, and writes packets going the
+    /// opposite way.
+    pub fn new(direction: PacketDirection) -> Self {
+        Self {
+            connection: ConnectionState::new(),
+            direction,
+            limits: None,
+            #[cfg(feature = "compression")]
+            compression: Compression::disabled(),
+            #[cfg(feature = "encryption")]
+            encryptor: None,
+            #[cfg(feature = "encryption")]
+            decryptor: None,
+            #[cfg(feature = "encryption")]
+            decrypted: 0,
+            _packet: core::marker::PhantomData,
+        }
+    }
+
+    pub fn client() -> Self {
+        Self::new(PacketDirection::ClientBound)
+    }
+
+    pub fn server() -> Self {
+        Self::new(PacketDirection::ServerBound)
+    }
+
+    pub fn state(&self) -> State {
+        self.connection.state()
+    }
+
+    /// Overrides the state, which otherwise follows `Handshake` and `LoginSuccess` packets.
+    pub fn set_state(&mut self, state: State) {
+        self.connection.set_state(state);
+    }
+
+    pub fn connection(&self) -> &ConnectionState {
+        &self.connection
+    }
+
+    pub fn direction(&self) -> PacketDirection {
+        self.direction
+    }
+
+    pub fn limits(&self) -> Option<&DeserializeLimits> {
+        self.limits.as_ref()
+    }
+
+    /// Decodes packets under `limits`, including their field limits, instead of the limits of
+    /// `crate::limits::current`.
+    pub fn set_limits(&mut self, limits: DeserializeLimits) {
+        self.limits = Some(limits);
+    }
+
+    #[cfg(feature = "compression")]
+    pub fn compression(&self) -> &Compression {
+        &self.compression
+    }
+
+    #[cfg(feature = "compression")]
+    pub fn set_compression_threshold(&mut self, threshold: i32) {
+        self.compression.set_threshold(threshold);
+    }
+
+    /// Encrypts everything written, and decrypts everything read, after this call.
+    ///
+    /// Any bytes which were already passed to `decode` but not consumed are treated as encrypted.
+    #[cfg(feature = "encryption")]
+    pub fn enable_encryption(&mut self, shared_secret: &[u8]) -> EncryptionResult<()> {
+        self.encryptor = Some(CraftEncryptor::new(shared_secret)?);
+        self.decryptor = Some(CraftDecryptor::new(shared_secret)?);
+        self.decrypted = 0;
+        Ok(())
+    }
+
+    #[cfg(feature = "encryption")]
+    pub fn is_encrypted(&self) -> bool {
+        self.decryptor.is_some()
+    }
+
+    /// Reads one packet from the front of `buf`, which holds every received byte that has not
+    /// been consumed yet.
+    ///
+    /// Returns the packet together with the number of bytes it used, which the caller must remove
+    /// from the front of `buf` before calling this again. Returns `Ok(None)` if more bytes are
+    /// needed. `buf` is mutable because received bytes are decrypted in place.
+    pub fn decode(&mut self, buf: &mut [u8]) -> FrameResult<Option<(usize, P)>> {
+        #[cfg(feature = "encryption")]
+        {
+            if let Some(decryptor) = self.decryptor.as_mut() {
+                if self.decrypted < buf.len() {
+                    decryptor.decrypt(&mut buf[self.decrypted..]);
+                    self.decrypted = buf.len();
+                }
+            }
+        }
+
+        let (consumed, packet) = match read_frame(buf)? {
+            Some(Deserialized { value: payload, data: rest }) => {
+                let consumed = buf.len() - rest.len();
+                #[cfg(feature = "compression")]
+                let payload = self.compression.decode_payload(payload)?;
+                let frame = RawFrame::parse(payload.as_ref(), self.state(), self.direction)?;
+                let packet = match self.limits.as_ref() {
+                    Some(limits) => frame.deserialize_with_limits::<P>(limits)?,
+                    None => frame.deserialize::<P>()?,
+                };
+                (consumed, packet)
+            }
+            None => return Ok(None),
+        };
+
+        #[cfg(feature = "encryption")]
+        {
+            self.decrypted = self.decrypted.saturating_sub(consumed);
+        }
+        self.observe(&packet)?;
+        Ok(Some((consumed, packet)))
+    }
+
+    /// Appends the frame for `packet` to `out`, compressed and encrypted as needed.
+    ///
+    /// Fails without writing anything if `packet` doesn't belong to the current state.
+    pub fn encode(&mut self, packet: &P, out: &mut Vec<u8>) -> FrameResult<()> {
+        self.connection.check(packet)?;
+        let payload = serialize_packet_payload(packet)?;
+        let mut frame = BytesSerializer::with_capacity(payload.len() + 5);
+        #[cfg(feature = "compression")]
+        self.compression.write_frame(payload.as_slice(), &mut frame)?;
+        #[cfg(not(feature = "compression"))]
+        crate::frame::write_frame(payload.as_slice(), &mut frame)?;
+
+        #[allow(unused_mut)]
+        let mut frame = frame.into_bytes();
+        #[cfg(feature = "encryption")]
+        {
+            if let Some(encryptor) = self.encryptor.as_mut() {
+                encryptor.encrypt(frame.as_mut_slice());
+            }
+        }
+
+        out.extend_from_slice(frame.as_slice());
+        self.observe(packet)
+    }
+
+    fn observe(&mut self, packet: &P) -> FrameResult<()> {
+        self.connection.observe(packet)?;
+        #[cfg(feature = "compression")]
+        self.compression.observe(packet);
+        Ok(())
+    }
+}
+
+impl<P> fmt::Debug for PacketCodec<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PacketCodec(state={:?}, direction={:?}", self.connection.state(), self.direction)?;
+        #[cfg(feature = "compression")]
+        write!(f, ", compression={:?}", self.compression.threshold())?;
+        #[cfg(feature = "encryption")]
+        write!(f, ", encrypted={}", self.decryptor.is_some())?;
+        f.write_str(")")
+    }
+}
+
+#[cfg(feature = "std")]
+pub enum CodecErr {
+    Io(std::io::Error),
+    Frame(FrameErr),
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for CodecErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use CodecErr::*;
+        match self {
+            Io(err) => f.write_fmt(format_args!("io error: {}", err)),
+            Frame(err) => f.write_fmt(format_args!("{}", err)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Debug for CodecErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <dyn fmt::Display>::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CodecErr {}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for CodecErr {
+    fn from(err: std::io::Error) -> Self {
+        CodecErr::Io(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<FrameErr> for CodecErr {
+    fn from(err: FrameErr) -> Self {
+        CodecErr::Frame(err)
+    }
+}
+
+#[cfg(feature = "tokio-codec")]
+impl<P> tokio_util::codec::Decoder for PacketCodec<P> where P: Packet + HasCompressionThreshold + HasNextState {
+    type Item = P;
+    type Error = CodecErr;
+
+    fn decode(&mut self, src: &mut bytes::BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match PacketCodec::decode(self, &mut src[..])? {
+            Some((consumed, packet)) => {
+                bytes::Buf::advance(src, consumed);
+                Ok(Some(packet))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(feature = "tokio-codec")]
+impl<P> tokio_util::codec::Encoder<P> for PacketCodec<P> where P: Packet + HasCompressionThreshold + HasNextState {
+    type Error = CodecErr;
+
+    fn encode(&mut self, item: P, dst: &mut bytes::BytesMut) -> Result<(), Self::Error> {
+        let mut out = Vec::new();
+        PacketCodec::encode(self, &item, &mut out)?;
+        dst.extend_from_slice(out.as_slice());
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "v1_16_3"))]
+mod tests {
+    use super::*;
+    use crate::frame::FrameErr;
+    use crate::types::{Chat, VarInt};
+    use crate::uuid::UUID4;
+    use crate::v1_16_3::*;
+    use alloc::{borrow::ToOwned, vec};
+
+    fn login_start() -> Packet753 {
+        Packet753::LoginStart(LoginStartSpec { name: "Notch".to_owned() })
+    }
+
+    fn chat(message: &str) -> Packet753 {
+        Packet753::PlayServerChatMessage(PlayServerChatMessageSpec {
+            message: Chat::from_text(message),
+            position: ChatPosition::ChatBox,
+            sender: UUID4::from(0u128),
+        })
+    }
+
+    fn decode_all(codec: &mut PacketCodec<Packet753>, mut wire: Vec<u8>) -> Vec<Packet753> {
+        let mut out = Vec::new();
+        while let Some((consumed, packet)) = codec.decode(wire.as_mut_slice()).expect("decodes") {
+            wire.drain(..consumed);
+            out.push(packet);
+        }
+        assert!(wire.is_empty());
+        out
+    }
+
+    #[test]
+    fn test_partial_decode() {
+        let mut client = PacketCodec::<Packet753>::client();
+        client.set_state(State::Login);
+        let mut server = PacketCodec::<Packet753>::server();
+        server.set_state(State::Login);
+
+        let mut wire = Vec::new();
+        client.encode(&login_start(), &mut wire).expect("encodes");
+        for end in 0..wire.len() {
+            assert!(server.decode(&mut wire.clone()[..end]).expect("partial is ok").is_none());
+        }
+        assert_eq!(decode_all(&mut server, wire), vec![login_start()]);
+    }
+
+    #[cfg(all(feature = "compression", feature = "encryption"))]
+    #[test]
+    fn test_compression_and_encryption_mid_stream() {
+        let mut client = PacketCodec::<Packet753>::client();
+        client.set_state(State::Login);
+        let mut server = PacketCodec::<Packet753>::server();
+        server.set_state(State::Login);
+        let secret = crate::encryption::generate_shared_secret();
+
+        // everything the server sends arrives in one read, so the client has to switch
+        // compression on halfway through its buffer
+        server.enable_encryption(&secret).unwrap();
+        let mut wire = Vec::new();
+        let set_compression = Packet753::LoginSetCompression(LoginSetCompressionSpec { threshold: VarInt(16) });
+        let success = Packet753::LoginSuccess(LoginSuccessSpec {
+            uuid: UUID4::from(1u128),
+            username: "a".repeat(64),
+        });
+        server.encode(&set_compression, &mut wire).unwrap();
+        assert_eq!(server.compression().threshold(), Some(16));
+        server.encode(&success, &mut wire).unwrap();
+
+        client.enable_encryption(&secret).unwrap();
+        assert_eq!(decode_all(&mut client, wire), vec![set_compression, success]);
+        assert_eq!(client.compression().threshold(), Some(16));
+    }
+
+    #[cfg(feature = "encryption")]
+    #[test]
+    fn test_encryption_enabled_with_buffered_bytes() {
+        let mut client = PacketCodec::<Packet753>::client();
+        client.set_state(State::Play);
+        let mut server = PacketCodec::<Packet753>::server();
+        server.set_state(State::Play);
+        let secret = crate::encryption::generate_shared_secret();
+
+        let mut wire = Vec::new();
+        server.encode(&chat("plain"), &mut wire).unwrap();
+        server.enable_encryption(&secret).unwrap();
+        server.encode(&chat("secret"), &mut wire).unwrap();
+
+        let (consumed, first) = client.decode(wire.as_mut_slice()).unwrap().expect("complete");
+        assert_eq!(first, chat("plain"));
+        wire.drain(..consumed);
+        client.enable_encryption(&secret).unwrap();
+        assert_eq!(decode_all(&mut client, wire), vec![chat("secret")]);
+    }
+
+    #[test]
+    fn test_follows_login_into_play() {
+        let mut client = PacketCodec::<Packet753>::client();
+        let mut server = PacketCodec::<Packet753>::server();
+
+        let mut wire = Vec::new();
+        client.encode(&Packet753::Handshake(HandshakeSpec {
+            version: VarInt(753),
+            server_address: "localhost".to_owned(),
+            server_port: 25565,
+            next_state: HandshakeNextState::Login,
+        }), &mut wire).unwrap();
+        client.encode(&login_start(), &mut wire).unwrap();
+        assert_eq!(decode_all(&mut server, wire).len(), 2);
+        assert_eq!(server.state(), State::Login);
+
+        // the client has to switch to play halfway through its buffer
+        let mut wire = Vec::new();
+        server.encode(&Packet753::LoginSuccess(LoginSuccessSpec {
+            uuid: UUID4::from(1u128),
+            username: "Notch".to_owned(),
+        }), &mut wire).unwrap();
+        server.encode(&chat("welcome"), &mut wire).unwrap();
+        assert_eq!(decode_all(&mut client, wire)[1], chat("welcome"));
+        assert_eq!(client.state(), State::Play);
+        assert_eq!(server.state(), State::Play);
+
+        let mut wire = Vec::new();
+        assert!(matches!(server.encode(&login_start(), &mut wire), Err(FrameErr::BadState(_))));
+        assert!(wire.is_empty());
+    }
+
+    #[test]
+    fn test_decode_with_limits() {
+        let mut client = PacketCodec::<Packet753>::client();
+        client.set_state(State::Login);
+        let mut server = PacketCodec::<Packet753>::server();
+        server.set_state(State::Login);
+        server.set_limits(DeserializeLimits::VANILLA);
+
+        let long_name = Packet753::LoginStart(LoginStartSpec { name: "a".repeat(17) });
+        let mut wire = Vec::new();
+        client.encode(&login_start(), &mut wire).unwrap();
+        assert_eq!(decode_all(&mut server, wire), vec![login_start()]);
+
+        let mut wire = Vec::new();
+        client.encode(&long_name, &mut wire).unwrap();
+        assert!(matches!(
+            server.decode(wire.as_mut_slice()),
+            Err(FrameErr::BadPacket(crate::protocol::PacketErr::DeserializeFailed(
+                crate::DeserializeErr::LimitExceeded { what: "username", length: 17, max: 16 }
+            )))
+        ));
+
+        // without limits set, only the generic ones apply
+        let mut server = PacketCodec::<Packet753>::server();
+        server.set_state(State::Login);
+        let mut wire = Vec::new();
+        client.encode(&long_name, &mut wire).unwrap();
+        assert_eq!(decode_all(&mut server, wire), vec![long_name]);
+    }
+
+    #[cfg(feature = "tokio-codec")]
+    #[tokio::test]
+    async fn test_tokio_framed_login() {
+        use futures::{SinkExt, StreamExt};
+        use tokio_util::codec::Framed;
+
+        let (client_io, server_io) = tokio::io::duplex(64);
+        let mut client = Framed::new(client_io, PacketCodec::<Packet753>::client());
+        let mut server = Framed::new(server_io, PacketCodec::<Packet753>::server());
+        let secret = crate::encryption::generate_shared_secret();
+
+        let server_task = tokio::spawn(async move {
+            let handshake = server.next().await.expect("handshake").expect("decodes");
+            assert!(matches!(handshake, Packet753::Handshake(_)));
+            let start = server.next().await.expect("login start").expect("decodes");
+            assert_eq!(start, login_start());
+
+            server.codec_mut().enable_encryption(&secret).unwrap();
+            server.send(Packet753::LoginSetCompression(LoginSetCompressionSpec { threshold: VarInt(32) })).await.unwrap();
+            server.send(Packet753::LoginSuccess(LoginSuccessSpec {
+                uuid: UUID4::from(7u128),
+                username: "Notch".to_owned(),
+            })).await.unwrap();
+            server.send(chat(&"x".repeat(1000))).await.unwrap();
+        });
+
+        client.send(Packet753::Handshake(HandshakeSpec {
+            version: VarInt(753),
+            server_address: "localhost".to_owned(),
+            server_port: 25565,
+            next_state: HandshakeNextState::Login,
+        })).await.unwrap();
+        client.send(login_start()).await.unwrap();
+        client.codec_mut().enable_encryption(&secret).unwrap();
+
+        let set_compression = client.next().await.expect("set compression").expect("decodes");
+        assert!(matches!(set_compression, Packet753::LoginSetCompression(_)));
+        assert_eq!(client.codec().compression().threshold(), Some(32));
+        let success = client.next().await.expect("success").expect("decodes");
+        assert!(matches!(success, Packet753::LoginSuccess(_)));
+        assert_eq!(client.next().await.expect("chat").expect("decodes"), chat(&"x".repeat(1000)));
+
+        server_task.await.expect("server succeeds");
+    }
+}
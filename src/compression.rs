@@ -0,0 +1,246 @@
+use crate::frame::{read_frame, serialize_packet_payload, write_frame, FrameErr, FrameResult};
+use crate::protocol::{HasCompressionThreshold, Packet};
+use crate::types::{BytesSerializer, VarInt};
+use crate::{Deserialize, DeserializeErr, Deserialized, Serializer};
+use alloc::{borrow::Cow, format, vec::Vec};
+use flate2::{read::ZlibDecoder, write::ZlibEncoder};
+use std::io::{Read, Write};
+
+// vanilla refuses to inflate anything larger than this
+pub const MAX_UNCOMPRESSED_LENGTH: usize = 0x200000;
+
+/// Tracks the threshold sent in `LoginSetCompression`, and encodes/decodes frame payloads in the
+/// `VarInt data length | zlib(id + body)` format once it is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Compression {
+    threshold: Option<usize>,
+}
+
+impl Compression {
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    pub fn with_threshold(threshold: i32) -> Self {
+        let mut out = Self::default();
+        out.set_threshold(threshold);
+        out
+    }
+
+    pub fn threshold(&self) -> Option<usize> {
+        self.threshold
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.threshold.is_some()
+    }
+
+    // a negative threshold disables compression, just like in vanilla
+    pub fn set_threshold(&mut self, threshold: i32) {
+        self.threshold = if threshold < 0 {
+            None
+        } else {
+            Some(threshold as usize)
+        };
+    }
+
+    /// Switches compression on (or off) if `packet` is a `LoginSetCompression` packet.
+    ///
+    /// This should be called after the packet was read or written, since the packet itself is
+    /// never compressed. Returns whether the threshold was changed.
+    pub fn observe<P: HasCompressionThreshold>(&mut self, packet: &P) -> bool {
+        if let Some(threshold) = packet.compression_threshold() {
+            self.set_threshold(threshold);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn encode_payload<'a>(&self, payload: &'a [u8]) -> FrameResult<Cow<'a, [u8]>> {
+        let threshold = match self.threshold {
+            Some(threshold) => threshold,
+            None => return Ok(Cow::Borrowed(payload)),
+        };
+
+        let mut header = BytesSerializer::with_capacity(payload.len() + 5);
+        if payload.len() < threshold {
+            header.serialize_other(&VarInt(0))?;
+            header.serialize_bytes(payload)?;
+            return Ok(Cow::Owned(header.into_bytes()));
+        }
+
+        if payload.len() > MAX_UNCOMPRESSED_LENGTH {
+            return Err(FrameErr::BadDataLength(payload.len()));
+        }
+
+        header.serialize_other(&VarInt(payload.len() as i32))?;
+        let mut encoder = ZlibEncoder::new(header.into_bytes(), flate2::Compression::default());
+        encoder.write_all(payload).map_err(compression_failed)?;
+        Ok(Cow::Owned(encoder.finish().map_err(compression_failed)?))
+    }
+
+    pub fn decode_payload<'a>(&self, payload: &'a [u8]) -> FrameResult<Cow<'a, [u8]>> {
+        let threshold = match self.threshold {
+            Some(threshold) => threshold,
+            None => return Ok(Cow::Borrowed(payload)),
+        };
+
+        let Deserialized { value: data_length, data: compressed } = VarInt::mc_deserialize(payload)?;
+        if data_length.0 < 0 {
+            return Err(DeserializeErr::NegativeLength(data_length).into());
+        }
+
+        let data_length = data_length.0 as usize;
+        if data_length == 0 {
+            return Ok(Cow::Borrowed(compressed));
+        }
+
+        if data_length < threshold || data_length > MAX_UNCOMPRESSED_LENGTH {
+            return Err(FrameErr::BadDataLength(data_length));
+        }
+
+        // read at most one byte more than declared, so lying about the length can't blow up memory
+        let mut out = Vec::with_capacity(data_length);
+        ZlibDecoder::new(compressed)
+            .take(data_length as u64 + 1)
+            .read_to_end(&mut out)
+            .map_err(compression_failed)?;
+
+        if out.len() != data_length {
+            return Err(FrameErr::CompressionFailed(format!(
+                "declared {} uncompressed bytes, but got {}",
+                data_length,
+                out.len()
+            )));
+        }
+
+        Ok(Cow::Owned(out))
+    }
+
+    /// Like `frame::read_frame`, but also decompresses the payload of the frame.
+    pub fn read_frame<'a>(&self, data: &'a [u8]) -> FrameResult<Option<Deserialized<'a, Cow<'a, [u8]>>>> {
+        match read_frame(data)? {
+            Some(Deserialized { value: payload, data: rest }) => {
+                Ok(Some(Deserialized::create(self.decode_payload(payload)?, rest)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn write_frame<S: Serializer>(&self, payload: &[u8], to: &mut S) -> FrameResult<()> {
+        write_frame(self.encode_payload(payload)?.as_ref(), to)
+    }
+
+    pub fn write_packet<P: Packet, S: Serializer>(&self, packet: &P, to: &mut S) -> FrameResult<()> {
+        self.write_frame(serialize_packet_payload(packet)?.as_slice(), to)
+    }
+}
+
+fn compression_failed(err: std::io::Error) -> FrameErr {
+    FrameErr::CompressionFailed(format!("{}", err))
+}
+
+#[cfg(all(test, feature = "v1_16_3"))]
+mod tests {
+    use super::*;
+    use crate::frame::RawFrame;
+    use crate::protocol::{PacketDirection::*, RawPacket, State::*};
+    use crate::v1_16_3::*;
+    use alloc::{borrow::ToOwned, vec};
+
+    fn chat(message: &str) -> Packet753 {
+        Packet753::PlayClientChatMessage(PlayClientChatMessageSpec {
+            message: message.to_owned(),
+        })
+    }
+
+    fn round_trip(compression: &Compression, packet: &Packet753) -> Vec<u8> {
+        let mut out = BytesSerializer::default();
+        compression.write_packet(packet, &mut out).expect("write succeeds");
+        let bytes = out.into_bytes();
+
+        let Deserialized { value: payload, data: rest } = compression
+            .read_frame(bytes.as_slice())
+            .expect("valid frame")
+            .expect("complete frame");
+        assert!(rest.is_empty());
+
+        let raw: RawPacket753 = RawFrame::parse(payload.as_ref(), Play, ServerBound)
+            .expect("valid payload")
+            .packet()
+            .expect("known id");
+        assert_eq!(&raw.deserialize().expect("deserializes"), packet);
+        bytes
+    }
+
+    #[test]
+    fn test_below_threshold_not_compressed() {
+        let compression = Compression::with_threshold(256);
+        let bytes = round_trip(&compression, &chat("hi"));
+        // packet length, then a data length of 0 marks an uncompressed payload
+        assert_eq!(bytes[1], 0x00);
+        assert_eq!(bytes[0] as usize, bytes.len() - 1);
+    }
+
+    #[test]
+    fn test_above_threshold_compressed() {
+        let compression = Compression::with_threshold(64);
+        let message = "a".repeat(200);
+        let bytes = round_trip(&compression, &chat(message.as_str()));
+        assert!(bytes.len() < message.len());
+    }
+
+    #[test]
+    fn test_disabled_is_plain_framing() {
+        let packet = chat("hello");
+        let mut plain = BytesSerializer::default();
+        crate::frame::write_packet(&packet, &mut plain).expect("write succeeds");
+        assert_eq!(round_trip(&Compression::disabled(), &packet), plain.into_bytes());
+        assert_eq!(Compression::with_threshold(-1), Compression::disabled());
+    }
+
+    #[test]
+    fn test_observe_set_compression() {
+        let mut compression = Compression::disabled();
+        assert!(!compression.observe(&chat("hello")));
+        assert!(!compression.is_enabled());
+
+        let set = Packet753::LoginSetCompression(LoginSetCompressionSpec { threshold: VarInt(128) });
+        assert!(compression.observe(&set));
+        assert_eq!(compression.threshold(), Some(128));
+    }
+
+    #[test]
+    fn test_rejects_oversized_data_length() {
+        let compression = Compression::with_threshold(64);
+        let mut payload = BytesSerializer::default();
+        payload.serialize_other(&VarInt((MAX_UNCOMPRESSED_LENGTH + 1) as i32)).unwrap();
+        payload.serialize_bytes(&[0x78, 0x9C, 0x03, 0x00]).unwrap();
+        match compression.decode_payload(payload.into_bytes().as_slice()) {
+            Err(FrameErr::BadDataLength(size)) => assert_eq!(size, MAX_UNCOMPRESSED_LENGTH + 1),
+            other => panic!("expected bad data length, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_rejects_data_length_below_threshold() {
+        let compression = Compression::with_threshold(64);
+        let payload = [0x10, 0x78, 0x9C, 0x03, 0x00];
+        assert!(matches!(compression.decode_payload(&payload), Err(FrameErr::BadDataLength(0x10))));
+    }
+
+    #[test]
+    fn test_rejects_lying_data_length() {
+        let compression = Compression::with_threshold(0);
+        let data = vec![7u8; 100];
+        let encoded = compression.encode_payload(data.as_slice()).expect("encodes");
+        let mut tampered = BytesSerializer::default();
+        tampered.serialize_other(&VarInt(50)).unwrap();
+        tampered.serialize_bytes(&encoded[1..]).unwrap();
+        assert!(matches!(
+            compression.decode_payload(tampered.into_bytes().as_slice()),
+            Err(FrameErr::CompressionFailed(_))
+        ));
+    }
+}
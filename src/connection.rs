@@ -0,0 +1,175 @@
+use crate::protocol::{HasCompressionThreshold, HasNextState, HasPacketId, Id, State};
+use alloc::fmt;
+
+pub enum StateErr {
+    WrongState { id: Id, expected: State },
+}
+
+impl fmt::Display for StateErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use StateErr::*;
+        match self {
+            WrongState { id, expected } => f.write_fmt(format_args!(
+                "packet {:?} is not allowed in state {:?}",
+                id, expected
+            )),
+        }
+    }
+}
+
+impl fmt::Debug for StateErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <dyn fmt::Display>::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StateErr {}
+
+pub type StateResult<T> = Result<T, StateErr>;
+
+/// Follows the state of a connection by observing the packets sent in either direction.
+///
+/// A `Handshake` moves to the requested state, `LoginSuccess` moves to `Play`, and the threshold
+/// from `LoginSetCompression` is recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConnectionState {
+    state: State,
+    compression_threshold: Option<usize>,
+}
+
+impl Default for ConnectionState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConnectionState {
+    pub fn new() -> Self {
+        Self::with_state(State::Handshaking)
+    }
+
+    pub fn with_state(state: State) -> Self {
+        Self {
+            state,
+            compression_threshold: None,
+        }
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn set_state(&mut self, state: State) {
+        self.state = state;
+    }
+
+    // a negative threshold disables compression, so it's recorded as None
+    pub fn compression_threshold(&self) -> Option<usize> {
+        self.compression_threshold
+    }
+
+    /// Fails if `packet` can't be sent in the current state.
+    pub fn check<P: HasPacketId>(&self, packet: &P) -> StateResult<()> {
+        let id = packet.id();
+        if id.state == self.state {
+            Ok(())
+        } else {
+            Err(StateErr::WrongState { id, expected: self.state })
+        }
+    }
+
+    /// Checks `packet` against the current state, then applies any transition it causes.
+    ///
+    /// Returns the new state if it changed.
+    pub fn observe<P>(&mut self, packet: &P) -> StateResult<Option<State>>
+    where
+        P: HasPacketId + HasNextState + HasCompressionThreshold,
+    {
+        self.check(packet)?;
+
+        if let Some(threshold) = packet.compression_threshold() {
+            self.compression_threshold = if threshold < 0 {
+                None
+            } else {
+                Some(threshold as usize)
+            };
+        }
+
+        match packet.next_state() {
+            Some(next) if next != self.state => {
+                self.state = next;
+                Ok(Some(next))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "v1_16_3"))]
+mod tests {
+    use super::*;
+    use crate::types::VarInt;
+    use crate::uuid::UUID4;
+    use crate::v1_16_3::*;
+    use alloc::borrow::ToOwned;
+
+    fn handshake(next_state: HandshakeNextState) -> Packet753 {
+        Packet753::Handshake(HandshakeSpec {
+            version: VarInt(753),
+            server_address: "localhost".to_owned(),
+            server_port: 25565,
+            next_state,
+        })
+    }
+
+    #[test]
+    fn test_status_flow() {
+        let mut state = ConnectionState::new();
+        assert_eq!(state.observe(&handshake(HandshakeNextState::Status)).unwrap(), Some(State::Status));
+        assert_eq!(state.observe(&Packet753::StatusRequest(StatusRequestSpec)).unwrap(), None);
+        assert_eq!(state.observe(&Packet753::StatusPing(StatusPingSpec { payload: 1 })).unwrap(), None);
+        assert_eq!(state.state(), State::Status);
+    }
+
+    #[test]
+    fn test_login_flow() {
+        let mut state = ConnectionState::new();
+        state.observe(&handshake(HandshakeNextState::Login)).unwrap();
+        assert_eq!(state.state(), State::Login);
+
+        state.observe(&Packet753::LoginStart(LoginStartSpec { name: "Notch".to_owned() })).unwrap();
+        state.observe(&Packet753::LoginSetCompression(LoginSetCompressionSpec { threshold: VarInt(256) })).unwrap();
+        assert_eq!(state.compression_threshold(), Some(256));
+
+        let success = Packet753::LoginSuccess(LoginSuccessSpec {
+            uuid: UUID4::from(1u128),
+            username: "Notch".to_owned(),
+        });
+        assert_eq!(state.observe(&success).unwrap(), Some(State::Play));
+        assert_eq!(state.state(), State::Play);
+        assert_eq!(state.compression_threshold(), Some(256));
+    }
+
+    #[test]
+    fn test_negative_threshold_disables_compression() {
+        let mut state = ConnectionState::with_state(State::Login);
+        state.observe(&Packet753::LoginSetCompression(LoginSetCompressionSpec { threshold: VarInt(64) })).unwrap();
+        state.observe(&Packet753::LoginSetCompression(LoginSetCompressionSpec { threshold: VarInt(-1) })).unwrap();
+        assert_eq!(state.compression_threshold(), None);
+    }
+
+    #[test]
+    fn test_wrong_state() {
+        let mut state = ConnectionState::new();
+        let start = Packet753::LoginStart(LoginStartSpec { name: "Notch".to_owned() });
+        match state.observe(&start) {
+            Err(StateErr::WrongState { id, expected }) => {
+                assert_eq!(id, start.id());
+                assert_eq!(expected, State::Handshaking);
+            }
+            other => panic!("expected wrong state, got {:?}", other),
+        }
+        assert_eq!(state.state(), State::Handshaking);
+    }
+}
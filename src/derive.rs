@@ -0,0 +1,262 @@
+//! Support for `#[derive(McSerialize, McDeserialize)]`, from the `mcproto-derive` crate, which are
+//! re-exported at the root of this crate with the `derive` feature.
+//!
+//! Fields are written in order with their `Serialize` impl, unless one of these says otherwise:
+//!
+//! * `#[mc(varint)]` on an `i32`, or `#[mc(varlong)]` on an `i64`, writes a `VarInt` or `VarLong`
+//! * `#[mc(count = "VarInt")]` on a `Vec<T>` writes the length with that `ArrayCounter` first, like
+//!   `CountedArray<T, VarInt>`
+//! * `#[mc(rest)]` on a `Vec<u8>`, which has to be the last field, takes everything that is left
+//! * `#[mc(if = "flags & 0x01")]` on an `Option<T>` is only there if the expression, which can use
+//!   the fields before it, `holds` (see `Condition`). The other attributes then describe the `T`.
+//!   Serializing fails if the field is `Some` while the condition doesn't hold, or the other way around.
+//!
+//! Enums start with an id, which is a `VarInt` unless the enum has `#[mc(tag = "u8")]` (any type
+//! which is `From` the ids works). Each variant gives its id with `#[mc(id = 0x01)]`, or with a
+//! discriminant if the enum only has unit variants.
+
+use crate::types::{ArrayCounter, CountedArray, RemainingBytes, VarInt, VarLong};
+use crate::{Deserialize, DeserializeResult, SerializeResult, Serializer};
+use alloc::vec::Vec;
+
+/// Whether a field marked `#[mc(if = "...")]` is present.
+pub trait Condition {
+
+    fn holds(&self) -> bool;
+}
+
+impl Condition for bool {
+    fn holds(&self) -> bool {
+        *self
+    }
+}
+
+macro_rules! int_condition {
+    ($($typ: ty),*) => {
+        $(impl Condition for $typ {
+            fn holds(&self) -> bool {
+                *self != 0
+            }
+        })*
+    };
+}
+
+int_condition!(u8, i8, u16, i16, u32, i32, u64, i64, usize, isize);
+
+impl Condition for VarInt {
+    fn holds(&self) -> bool {
+        self.0 != 0
+    }
+}
+
+impl Condition for VarLong {
+    fn holds(&self) -> bool {
+        self.0 != 0
+    }
+}
+
+impl<T> Condition for Option<T> {
+    fn holds(&self) -> bool {
+        self.is_some()
+    }
+}
+
+// conditions on serialize see the fields by reference
+impl<T> Condition for &T where T: Condition + ?Sized {
+    fn holds(&self) -> bool {
+        (**self).holds()
+    }
+}
+
+// used by the generated code, which can't name alloc or the helpers below in no_std crates
+#[doc(hidden)]
+pub mod __private {
+    use super::*;
+
+    pub use alloc::format;
+
+    pub fn deserialize_varint(data: &[u8]) -> DeserializeResult<'_, i32> {
+        Ok(VarInt::mc_deserialize(data)?.map(move |v| v.0))
+    }
+
+    pub fn deserialize_varlong(data: &[u8]) -> DeserializeResult<'_, i64> {
+        Ok(VarLong::mc_deserialize(data)?.map(move |v| v.0))
+    }
+
+    pub fn serialize_counted<C, E, S>(items: &[E], to: &mut S) -> SerializeResult
+        where C: ArrayCounter, E: crate::Serialize, S: Serializer
+    {
+        to.serialize_other(&C::from_count(items.len()))?;
+        for item in items {
+            to.serialize_other(item)?;
+        }
+        Ok(())
+    }
+
+    pub fn deserialize_counted<C, E>(data: &[u8]) -> DeserializeResult<'_, Vec<E>>
+        where C: ArrayCounter, E: Deserialize
+    {
+        Ok(<CountedArray<E, C>>::mc_deserialize(data)?.map(Into::into))
+    }
+
+    pub fn deserialize_rest(data: &[u8]) -> DeserializeResult<'_, Vec<u8>> {
+        Ok(RemainingBytes::mc_deserialize(data)?.map(Into::into))
+    }
+}
+
+#[cfg(all(test, feature = "derive"))]
+mod tests {
+    use crate::types::{BytesSerializer, VarInt};
+    use crate::{Deserialize, DeserializeErr, McDeserialize, McSerialize, PathSegment, Serialize, SerializeErr, Serializer};
+    use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
+    use core::fmt::Debug;
+
+    #[derive(McSerialize, McDeserialize, Debug, Clone, PartialEq)]
+    struct ChannelPayload {
+        #[mc(varint)]
+        protocol: i32,
+        flags: u8,
+        #[mc(count = "VarInt")]
+        names: Vec<String>,
+        #[mc(if = "flags & 0x01", varlong)]
+        timestamp: Option<i64>,
+        #[mc(if = "flags & 0x02")]
+        motd: Option<String>,
+        #[mc(rest)]
+        extra: Vec<u8>,
+    }
+
+    #[derive(McSerialize, McDeserialize, Debug, Clone, PartialEq)]
+    #[mc(tag = "u8")]
+    enum Action {
+        #[mc(id = 0x00)]
+        Reset,
+        #[mc(id = 0x01)]
+        Move(i32, i32),
+        #[mc(id = 0x05)]
+        Rename {
+            #[mc(count = "i16")]
+            bytes: Vec<u8>,
+            #[mc(varint)]
+            slot: i32,
+        },
+    }
+
+    #[derive(McSerialize, McDeserialize, Debug, Clone, PartialEq)]
+    enum Mode {
+        Off = 0,
+        On = 7,
+    }
+
+    #[derive(McSerialize, McDeserialize, Debug, Clone, PartialEq)]
+    struct Wrapper<T>(Mode, T);
+
+    #[derive(McSerialize, McDeserialize, Debug, Clone, PartialEq)]
+    enum Flagged {
+        #[mc(id = 0)]
+        Pair {
+            present: bool,
+            #[mc(if = "present")]
+            value: Option<VarInt>,
+        },
+    }
+
+    #[derive(McSerialize, McDeserialize, Debug, Clone, PartialEq)]
+    struct Empty;
+
+    fn bytes<T: Serialize>(value: &T) -> Vec<u8> {
+        let mut out = BytesSerializer::default();
+        value.mc_serialize(&mut out).unwrap();
+        out.into_bytes()
+    }
+
+    fn round_trip<T: Serialize + Deserialize + Debug + PartialEq>(value: T) -> Vec<u8> {
+        let data = bytes(&value);
+        let read = T::mc_deserialize(data.as_slice()).expect("deserializes");
+        assert!(read.data.is_empty());
+        assert_eq!(read.value, value);
+        data
+    }
+
+    fn payload() -> ChannelPayload {
+        ChannelPayload {
+            protocol: 756,
+            flags: 0x02,
+            names: vec!["a".to_owned(), "bc".to_owned()],
+            timestamp: None,
+            motd: Some("hi".to_owned()),
+            extra: vec![9, 9],
+        }
+    }
+
+    #[test]
+    fn test_struct() {
+        assert_eq!(round_trip(payload()), vec![
+            0xF4, 0x05, // protocol
+            0x02, // flags
+            0x02, 0x01, b'a', 0x02, b'b', b'c', // names
+            0x02, b'h', b'i', // motd, but no timestamp
+            9, 9,
+        ]);
+
+        round_trip(ChannelPayload { flags: 0x03, timestamp: Some(-1), ..payload() });
+        round_trip(ChannelPayload { flags: 0, motd: None, extra: vec![], ..payload() });
+        round_trip(Empty);
+    }
+
+    #[test]
+    fn test_enum() {
+        assert_eq!(round_trip(Action::Reset), vec![0x00]);
+        assert_eq!(round_trip(Action::Move(1, -1)), vec![0x01, 0, 0, 0, 1, 0xFF, 0xFF, 0xFF, 0xFF]);
+        assert_eq!(round_trip(Action::Rename { bytes: vec![7], slot: 300 }), vec![0x05, 0x00, 0x01, 7, 0xAC, 0x02]);
+        assert_eq!(round_trip(Mode::On), vec![0x07]);
+        assert_eq!(round_trip(Wrapper(Mode::Off, VarInt(3))), vec![0x00, 0x03]);
+
+        assert!(matches!(
+            Action::mc_deserialize(&[0x02]),
+            Err(DeserializeErr::CannotUnderstandValue(msg)) if msg == "invalid Action id 2"
+        ));
+    }
+
+    #[test]
+    fn test_error_location() {
+        let data = bytes(&payload());
+        let err = match ChannelPayload::mc_deserialize(&data[..7]) {
+            Err(err) => err,
+            Ok(_) => panic!("cut off inside names"),
+        };
+        assert!(matches!(err.root(), DeserializeErr::Eof));
+        assert_eq!(err.path(), &[PathSegment::Name("names"), PathSegment::Index(1)]);
+        assert_eq!(err.offset(), Some(6));
+
+        let err = match Action::mc_deserialize(&[0x05, 0x00, 0x01, 7]) {
+            Err(err) => err,
+            Ok(_) => panic!("slot is missing"),
+        };
+        assert_eq!(alloc::format!("{}", err), "at Rename.slot (byte 4): unexpected eof");
+    }
+
+    #[test]
+    fn test_condition_mismatch() {
+        let mut out = BytesSerializer::default();
+        let err = ChannelPayload { flags: 0, ..payload() }.mc_serialize(&mut out).unwrap_err();
+        assert!(matches!(err, SerializeErr::CannotSerialize(msg) if msg == "motd is set, but `flags & 0x02` doesn't hold"));
+
+        let mut out = BytesSerializer::default();
+        let err = ChannelPayload { flags: 0x03, ..payload() }.mc_serialize(&mut out).unwrap_err();
+        assert!(matches!(err, SerializeErr::CannotSerialize(msg) if msg == "timestamp is missing, but `flags & 0x01` holds"));
+
+        let mut out = BytesSerializer::default();
+        let err = Flagged::Pair { present: true, value: None }.mc_serialize(&mut out).unwrap_err();
+        assert!(matches!(err, SerializeErr::CannotSerialize(_)));
+        round_trip(Flagged::Pair { present: true, value: Some(VarInt(1)) });
+        round_trip(Flagged::Pair { present: false, value: None });
+    }
+
+    #[test]
+    fn test_serialize_through_serializer() {
+        let mut out = BytesSerializer::default();
+        out.serialize_other(&Wrapper(Mode::On, 1u8)).unwrap();
+        assert_eq!(out.into_bytes(), vec![0x07, 0x01]);
+    }
+}
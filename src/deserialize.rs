@@ -1,17 +1,102 @@
 use crate::types::VarInt;
-use alloc::{vec::Vec, string::{FromUtf8Error, String}, fmt};
+use alloc::{boxed::Box, vec, vec::Vec, string::{FromUtf8Error, String}, fmt};
+use core::str::Utf8Error;
 
 pub enum DeserializeErr {
     Eof,
     VarNumTooLong(Vec<u8>),
     NegativeLength(VarInt),
     BadStringEncoding(FromUtf8Error),
+    BadStrEncoding(Utf8Error),
     InvalidBool(u8),
     NbtUnknownTagType(u8),
     NbtBadLength(isize),
     NbtInvalidStartTag(u8),
     CannotUnderstandValue(String),
     FailedJsonDeserialize(String),
+    LimitExceeded { what: &'static str, length: usize, max: usize },
+    // err happened in the field at path, which starts offset bytes into the packet body (or
+    // whatever value was being decoded)
+    At { path: Vec<PathSegment>, offset: usize, err: Box<DeserializeErr> },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    Name(&'static str),
+    Index(usize),
+}
+
+impl DeserializeErr {
+    /// Records that this error happened in the field (or enum variant, or packet) `name`, which
+    /// starts `offset` bytes into the enclosing value.
+    pub fn in_field(self, name: &'static str, offset: usize) -> Self {
+        self.located(Some(PathSegment::Name(name)), offset)
+    }
+
+    /// Records that this error happened in element `index` of an array, which starts `offset` bytes
+    /// into the array.
+    pub fn at_index(self, index: usize, offset: usize) -> Self {
+        self.located(Some(PathSegment::Index(index)), offset)
+    }
+
+    /// Moves the offset of this error `offset` bytes further, without adding to its path.
+    pub fn offset_by(self, offset: usize) -> Self {
+        self.located(None, offset)
+    }
+
+    fn located(self, segment: Option<PathSegment>, by: usize) -> Self {
+        use DeserializeErr::At;
+        match self {
+            At { mut path, offset, err } => {
+                if let Some(segment) = segment {
+                    path.insert(0, segment);
+                }
+                At { path, offset: offset + by, err }
+            }
+            err => At {
+                path: segment.map(move |segment| vec![segment]).unwrap_or_default(),
+                offset: by,
+                err: Box::new(err),
+            },
+        }
+    }
+
+    /// The error without the location recorded by `in_field` and friends.
+    pub fn root(&self) -> &DeserializeErr {
+        match self {
+            DeserializeErr::At { err, .. } => err.root(),
+            other => other,
+        }
+    }
+
+    pub fn path(&self) -> &[PathSegment] {
+        match self {
+            DeserializeErr::At { path, .. } => path.as_slice(),
+            _ => &[],
+        }
+    }
+
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            DeserializeErr::At { offset, .. } => Some(*offset),
+            _ => None,
+        }
+    }
+}
+
+struct DisplayPath<'a>(&'a [PathSegment]);
+
+impl fmt::Display for DisplayPath<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, segment) in self.0.iter().enumerate() {
+            match segment {
+                PathSegment::Name(name) if idx == 0 => f.write_str(name)?,
+                PathSegment::Name(name) => f.write_fmt(format_args!(".{}", name))?,
+                PathSegment::Index(index) => f.write_fmt(format_args!("[{}]", index))?,
+            }
+        }
+        Ok(())
+    }
 }
 
 impl fmt::Display for DeserializeErr {
@@ -29,6 +114,10 @@ impl fmt::Display for DeserializeErr {
                 "failed to decode string, utf error: {:?}",
                 data
             )),
+            BadStrEncoding(data) => f.write_fmt(format_args!(
+                "failed to decode string, utf error: {:?}",
+                data
+            )),
             InvalidBool(value) => f.write_fmt(format_args!(
                 "could not decode boolean, unexpected byte: {:?}",
                 value
@@ -44,6 +133,17 @@ impl fmt::Display for DeserializeErr {
             FailedJsonDeserialize(data) => {
                 f.write_fmt(format_args!("failed to deserialize json: {:?}", data))
             }
+            LimitExceeded { what, length, max } => f.write_fmt(format_args!(
+                "{} length {} exceeds the limit of {}",
+                what, length, max
+            )),
+            At { path, offset, err } if path.is_empty() => {
+                f.write_fmt(format_args!("at byte {}: {}", offset, err))
+            }
+            At { path, offset, err } => f.write_fmt(format_args!(
+                "at {} (byte {}): {}",
+                DisplayPath(path.as_slice()), offset, err
+            )),
         }
     }
 }
@@ -132,3 +232,15 @@ pub type DeserializeResult<'b, R> = Result<Deserialized<'b, R>, DeserializeErr>;
 pub trait Deserialize: Sized {
     fn mc_deserialize(data: &[u8]) -> DeserializeResult<Self>;
 }
+
+/// Like `Deserialize`, but for types which borrow from the data they are read from instead of
+/// copying it, such as `&'a str`. Everything which is `Deserialize` is this too.
+pub trait BorrowDeserialize<'a>: Sized {
+    fn mc_deserialize_borrowed(data: &'a [u8]) -> DeserializeResult<'a, Self>;
+}
+
+impl<'a, T> BorrowDeserialize<'a> for T where T: Deserialize {
+    fn mc_deserialize_borrowed(data: &'a [u8]) -> DeserializeResult<'a, Self> {
+        T::mc_deserialize(data)
+    }
+}
@@ -0,0 +1,369 @@
+use crate::frame::{serialize_packet_payload, FrameResult, RawFrame};
+use crate::protocol::{
+    HasCompressionThreshold, HasNextState, HasPacketBody, HasPacketId, Id, Packet, PacketErr, State,
+};
+use crate::types::VarInt;
+use crate::{Deserialize, DeserializeErr, SerializeResult, Serializer};
+use alloc::{collections::BTreeMap, fmt, vec::Vec};
+
+#[cfg(feature = "v1_8_9")]
+use crate::v1_8_9::Packet47;
+#[cfg(feature = "v1_12_2")]
+use crate::v1_12_2::Packet340;
+#[cfg(feature = "v1_15_2")]
+use crate::v1_15_2::Packet578;
+#[cfg(feature = "v1_16_3")]
+use crate::v1_16_3::Packet753;
+#[cfg(feature = "v1_16_5")]
+use crate::v1_16_5::Packet754;
+#[cfg(feature = "v1_17_1")]
+use crate::v1_17_1::Packet756;
+
+/// A packet of any protocol version compiled into this crate.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnyPacket {
+    #[cfg(feature = "v1_8_9")]
+    V1_8_9(Packet47),
+    #[cfg(feature = "v1_12_2")]
+    V1_12_2(Packet340),
+    #[cfg(feature = "v1_15_2")]
+    V1_15_2(Packet578),
+    #[cfg(feature = "v1_16_3")]
+    V1_16_3(Packet753),
+    #[cfg(feature = "v1_16_5")]
+    V1_16_5(Packet754),
+    #[cfg(feature = "v1_17_1")]
+    V1_17_1(Packet756),
+}
+
+macro_rules! any_packet_dispatch {
+    ($self: ident, $packet: ident => $body: expr) => {
+        match $self {
+            #[cfg(feature = "v1_8_9")]
+            AnyPacket::V1_8_9($packet) => $body,
+            #[cfg(feature = "v1_12_2")]
+            AnyPacket::V1_12_2($packet) => $body,
+            #[cfg(feature = "v1_15_2")]
+            AnyPacket::V1_15_2($packet) => $body,
+            #[cfg(feature = "v1_16_3")]
+            AnyPacket::V1_16_3($packet) => $body,
+            #[cfg(feature = "v1_16_5")]
+            AnyPacket::V1_16_5($packet) => $body,
+            #[cfg(feature = "v1_17_1")]
+            AnyPacket::V1_17_1($packet) => $body,
+        }
+    };
+}
+
+impl AnyPacket {
+    pub fn protocol_version(&self) -> i32 {
+        fn version_of<P: HasPacketId>(_: &P) -> i32 {
+            P::version().0
+        }
+
+        any_packet_dispatch!(self, packet => version_of(packet))
+    }
+
+    pub fn id(&self) -> Id {
+        any_packet_dispatch!(self, packet => packet.id())
+    }
+
+    /// Serializes the `VarInt id | body` payload, like `frame::serialize_packet_payload`.
+    pub fn payload(&self) -> FrameResult<Vec<u8>> {
+        any_packet_dispatch!(self, packet => serialize_packet_payload(packet))
+    }
+}
+
+impl HasPacketBody for AnyPacket {
+    fn mc_serialize_body<S>(&self, to: &mut S) -> SerializeResult where S: Serializer {
+        any_packet_dispatch!(self, packet => packet.mc_serialize_body(to))
+    }
+}
+
+impl HasCompressionThreshold for AnyPacket {
+    fn compression_threshold(&self) -> Option<i32> {
+        any_packet_dispatch!(self, packet => packet.compression_threshold())
+    }
+}
+
+impl HasNextState for AnyPacket {
+    fn next_state(&self) -> Option<State> {
+        any_packet_dispatch!(self, packet => packet.next_state())
+    }
+}
+
+#[cfg(feature = "v1_8_9")]
+impl From<Packet47> for AnyPacket {
+    fn from(packet: Packet47) -> Self {
+        AnyPacket::V1_8_9(packet)
+    }
+}
+
+#[cfg(feature = "v1_12_2")]
+impl From<Packet340> for AnyPacket {
+    fn from(packet: Packet340) -> Self {
+        AnyPacket::V1_12_2(packet)
+    }
+}
+
+#[cfg(feature = "v1_15_2")]
+impl From<Packet578> for AnyPacket {
+    fn from(packet: Packet578) -> Self {
+        AnyPacket::V1_15_2(packet)
+    }
+}
+
+#[cfg(feature = "v1_16_3")]
+impl From<Packet753> for AnyPacket {
+    fn from(packet: Packet753) -> Self {
+        AnyPacket::V1_16_3(packet)
+    }
+}
+
+#[cfg(feature = "v1_16_5")]
+impl From<Packet754> for AnyPacket {
+    fn from(packet: Packet754) -> Self {
+        AnyPacket::V1_16_5(packet)
+    }
+}
+
+#[cfg(feature = "v1_17_1")]
+impl From<Packet756> for AnyPacket {
+    fn from(packet: Packet756) -> Self {
+        AnyPacket::V1_17_1(packet)
+    }
+}
+
+pub enum DispatchErr {
+    UnsupportedVersion(i32),
+    DeserializeFailed(DeserializeErr),
+}
+
+impl fmt::Display for DispatchErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use DispatchErr::*;
+        match self {
+            UnsupportedVersion(version) => {
+                f.write_fmt(format_args!("unsupported protocol version {}", version))
+            }
+            DeserializeFailed(err) => {
+                f.write_fmt(format_args!("failed to read handshake version: {:?}", err))
+            }
+        }
+    }
+}
+
+impl fmt::Debug for DispatchErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <dyn fmt::Display>::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DispatchErr {}
+
+impl From<DeserializeErr> for DispatchErr {
+    fn from(err: DeserializeErr) -> Self {
+        DispatchErr::DeserializeFailed(err)
+    }
+}
+
+pub type DispatchResult<T> = Result<T, DispatchErr>;
+
+/// Decodes packet bodies of one protocol version into `AnyPacket`.
+#[derive(Clone, Copy)]
+pub struct ProtocolDecoder {
+    protocol: i32,
+    decode: fn(Id, &[u8]) -> Result<AnyPacket, PacketErr>,
+}
+
+impl ProtocolDecoder {
+    pub fn of<P>() -> Self where P: Packet + Into<AnyPacket> {
+        Self {
+            protocol: P::version().0,
+            decode: |id, data| P::mc_deserialize_body(id, data).map(Into::into),
+        }
+    }
+
+    pub fn protocol(&self) -> i32 {
+        self.protocol
+    }
+
+    pub fn decode(&self, id: Id, body: &[u8]) -> Result<AnyPacket, PacketErr> {
+        (self.decode)(id, body)
+    }
+
+    pub fn decode_frame(&self, frame: &RawFrame<'_>) -> FrameResult<AnyPacket> {
+        Ok(self.decode(frame.id, frame.body)?)
+    }
+}
+
+impl fmt::Debug for ProtocolDecoder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!("ProtocolDecoder({})", self.protocol))
+    }
+}
+
+/// Maps protocol numbers, as sent in `HandshakeSpec::version`, to the decoder for that version.
+///
+/// `ProtocolRegistry::default()` contains every version enabled by features.
+#[derive(Debug, Clone)]
+pub struct ProtocolRegistry {
+    decoders: BTreeMap<i32, ProtocolDecoder>,
+}
+
+impl Default for ProtocolRegistry {
+    fn default() -> Self {
+        let mut out = Self::empty();
+        #[cfg(feature = "v1_8_9")]
+        out.register::<Packet47>();
+        #[cfg(feature = "v1_12_2")]
+        out.register::<Packet340>();
+        #[cfg(feature = "v1_15_2")]
+        out.register::<Packet578>();
+        #[cfg(feature = "v1_16_3")]
+        out.register::<Packet753>();
+        #[cfg(feature = "v1_16_5")]
+        out.register::<Packet754>();
+        #[cfg(feature = "v1_17_1")]
+        out.register::<Packet756>();
+        out
+    }
+}
+
+impl ProtocolRegistry {
+    pub fn empty() -> Self {
+        Self {
+            decoders: BTreeMap::new(),
+        }
+    }
+
+    pub fn register<P>(&mut self) -> &mut Self where P: Packet + Into<AnyPacket> {
+        self.insert(ProtocolDecoder::of::<P>())
+    }
+
+    /// Adds `decoder`, replacing any decoder already registered for its protocol number.
+    pub fn insert(&mut self, decoder: ProtocolDecoder) -> &mut Self {
+        self.decoders.insert(decoder.protocol(), decoder);
+        self
+    }
+
+    pub fn get(&self, protocol: i32) -> DispatchResult<&ProtocolDecoder> {
+        self.decoders.get(&protocol).ok_or(DispatchErr::UnsupportedVersion(protocol))
+    }
+
+    /// Picks the decoder for the version requested by a handshake, given the body of the
+    /// handshake packet. The handshake starts with the protocol version in every version, so
+    /// this works before the version is known.
+    pub fn for_handshake(&self, body: &[u8]) -> DispatchResult<&ProtocolDecoder> {
+        self.get(VarInt::mc_deserialize(body)?.value.0)
+    }
+
+    pub fn supports(&self, protocol: i32) -> bool {
+        self.decoders.contains_key(&protocol)
+    }
+
+    pub fn protocols(&self) -> impl Iterator<Item = i32> + '_ {
+        self.decoders.keys().copied()
+    }
+}
+
+#[cfg(all(test, feature = "std", feature = "v1_15_2", feature = "v1_16_3"))]
+mod tests {
+    use super::*;
+    use crate::frame::{read_frame, write_packet};
+    use crate::protocol::{PacketDirection::*, State::*};
+    use crate::types::BytesSerializer;
+    use alloc::borrow::ToOwned;
+
+    fn login<P: Packet>(handshake: P, start: P) -> Vec<u8> {
+        let mut out = BytesSerializer::default();
+        write_packet(&handshake, &mut out).unwrap();
+        write_packet(&start, &mut out).unwrap();
+        out.into_bytes()
+    }
+
+    fn login_578() -> Vec<u8> {
+        use crate::v1_15_2::*;
+        login(
+            Packet578::Handshake(HandshakeSpec {
+                version: VarInt(578),
+                server_address: "localhost".to_owned(),
+                server_port: 25565,
+                next_state: HandshakeNextState::Login,
+            }),
+            Packet578::LoginStart(LoginStartSpec { name: "Notch".to_owned() }),
+        )
+    }
+
+    fn login_753() -> Vec<u8> {
+        use crate::v1_16_3::*;
+        login(
+            Packet753::Handshake(HandshakeSpec {
+                version: VarInt(753),
+                server_address: "localhost".to_owned(),
+                server_port: 25565,
+                next_state: HandshakeNextState::Login,
+            }),
+            Packet753::LoginStart(LoginStartSpec { name: "Notch".to_owned() }),
+        )
+    }
+
+    // what a multi-version server does with the first two frames of a connection
+    fn accept(registry: &ProtocolRegistry, wire: &[u8]) -> DispatchResult<(AnyPacket, AnyPacket)> {
+        let handshake = read_frame(wire).unwrap().unwrap();
+        let frame = RawFrame::parse(handshake.value, Handshaking, ServerBound).unwrap();
+        let decoder = registry.for_handshake(frame.body)?;
+        let first = decoder.decode_frame(&frame).unwrap();
+        assert_eq!(first.next_state(), Some(Login));
+
+        let start = read_frame(handshake.data).unwrap().unwrap();
+        let frame = RawFrame::parse(start.value, Login, ServerBound).unwrap();
+        Ok((first, decoder.decode_frame(&frame).unwrap()))
+    }
+
+    #[test]
+    fn test_default_registry() {
+        let registry = ProtocolRegistry::default();
+        let protocols = registry.protocols().collect::<Vec<_>>();
+        assert!(protocols.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(registry.supports(578));
+        assert!(registry.supports(753));
+        assert_eq!(registry.supports(754), cfg!(feature = "v1_16_5"));
+        assert_eq!(registry.supports(756), cfg!(feature = "v1_17_1"));
+        assert_eq!(registry.supports(340), cfg!(feature = "v1_12_2"));
+        assert_eq!(registry.supports(47), cfg!(feature = "v1_8_9"));
+        assert!(!registry.supports(498));
+    }
+
+    #[test]
+    fn test_dispatch_by_handshake() {
+        let registry = ProtocolRegistry::default();
+
+        let (handshake, start) = accept(&registry, login_578().as_slice()).unwrap();
+        assert!(matches!(handshake, AnyPacket::V1_15_2(_)));
+        assert!(matches!(start, AnyPacket::V1_15_2(crate::v1_15_2::Packet578::LoginStart(_))));
+        assert_eq!(start.protocol_version(), 578);
+
+        let (_, start) = accept(&registry, login_753().as_slice()).unwrap();
+        assert!(matches!(start, AnyPacket::V1_16_3(crate::v1_16_3::Packet753::LoginStart(_))));
+        assert_eq!(start.protocol_version(), 753);
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        let mut registry = ProtocolRegistry::empty();
+        registry.register::<Packet753>();
+        assert!(matches!(accept(&registry, login_578().as_slice()), Err(DispatchErr::UnsupportedVersion(578))));
+        assert!(accept(&registry, login_753().as_slice()).is_ok());
+    }
+
+    #[test]
+    fn test_payload_round_trip() {
+        let registry = ProtocolRegistry::default();
+        let (_, start) = accept(&registry, login_578().as_slice()).unwrap();
+        let payload = start.payload().unwrap();
+        let frame = RawFrame::parse(payload.as_slice(), Login, ServerBound).unwrap();
+        assert_eq!(registry.get(578).unwrap().decode_frame(&frame).unwrap(), start);
+    }
+}
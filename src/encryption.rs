@@ -0,0 +1,266 @@
+use aes::Aes128;
+use aes::cipher::{generic_array::GenericArray, BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use alloc::fmt;
+
+pub const SHARED_SECRET_LENGTH: usize = 16;
+pub const VERIFY_TOKEN_LENGTH: usize = 4;
+
+pub enum EncryptionErr {
+    BadSharedSecretLength(usize),
+}
+
+impl fmt::Display for EncryptionErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use EncryptionErr::*;
+        match self {
+            BadSharedSecretLength(size) => f.write_fmt(format_args!(
+                "shared secret must be {} bytes long, got {} bytes",
+                SHARED_SECRET_LENGTH, size
+            )),
+        }
+    }
+}
+
+impl fmt::Debug for EncryptionErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <dyn fmt::Display>::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EncryptionErr {}
+
+pub type EncryptionResult<T> = Result<T, EncryptionErr>;
+
+// minecraft uses the shared secret as both the key and the iv
+fn key_iv(shared_secret: &[u8]) -> EncryptionResult<&[u8]> {
+    if shared_secret.len() != SHARED_SECRET_LENGTH {
+        Err(EncryptionErr::BadSharedSecretLength(shared_secret.len()))
+    } else {
+        Ok(shared_secret)
+    }
+}
+
+pub struct CraftEncryptor {
+    inner: cfb8::Encryptor<Aes128>,
+}
+
+impl CraftEncryptor {
+    pub fn new(shared_secret: &[u8]) -> EncryptionResult<Self> {
+        let key = key_iv(shared_secret)?;
+        Ok(Self::with_key_iv(key, key))
+    }
+
+    fn with_key_iv(key: &[u8], iv: &[u8]) -> Self {
+        Self {
+            inner: cfb8::Encryptor::new(GenericArray::from_slice(key), GenericArray::from_slice(iv)),
+        }
+    }
+
+    /// Encrypts `data` in place, continuing the stream from any previously encrypted bytes.
+    pub fn encrypt(&mut self, data: &mut [u8]) {
+        for byte in data.chunks_mut(1) {
+            self.inner.encrypt_block_mut(GenericArray::from_mut_slice(byte));
+        }
+    }
+}
+
+impl fmt::Debug for CraftEncryptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CraftEncryptor(AES-128/CFB8)")
+    }
+}
+
+pub struct CraftDecryptor {
+    inner: cfb8::Decryptor<Aes128>,
+}
+
+impl CraftDecryptor {
+    pub fn new(shared_secret: &[u8]) -> EncryptionResult<Self> {
+        let key = key_iv(shared_secret)?;
+        Ok(Self::with_key_iv(key, key))
+    }
+
+    fn with_key_iv(key: &[u8], iv: &[u8]) -> Self {
+        Self {
+            inner: cfb8::Decryptor::new(GenericArray::from_slice(key), GenericArray::from_slice(iv)),
+        }
+    }
+
+    /// Decrypts `data` in place, continuing the stream from any previously decrypted bytes.
+    ///
+    /// Bytes must be decrypted exactly once and in the order they were received, so this should
+    /// be applied to bytes as they arrive, before they are handed to `frame::read_frame`.
+    pub fn decrypt(&mut self, data: &mut [u8]) {
+        for byte in data.chunks_mut(1) {
+            self.inner.decrypt_block_mut(GenericArray::from_mut_slice(byte));
+        }
+    }
+}
+
+impl fmt::Debug for CraftDecryptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CraftDecryptor(AES-128/CFB8)")
+    }
+}
+
+/// Both directions of an encrypted connection, keyed by the shared secret from
+/// `LoginEncryptionResponse`.
+#[derive(Debug)]
+pub struct CraftCipher {
+    pub encryptor: CraftEncryptor,
+    pub decryptor: CraftDecryptor,
+}
+
+impl CraftCipher {
+    pub fn new(shared_secret: &[u8]) -> EncryptionResult<Self> {
+        Ok(Self {
+            encryptor: CraftEncryptor::new(shared_secret)?,
+            decryptor: CraftDecryptor::new(shared_secret)?,
+        })
+    }
+
+    pub fn encrypt(&mut self, data: &mut [u8]) {
+        self.encryptor.encrypt(data)
+    }
+
+    pub fn decrypt(&mut self, data: &mut [u8]) {
+        self.decryptor.decrypt(data)
+    }
+
+    pub fn split(self) -> (CraftEncryptor, CraftDecryptor) {
+        (self.encryptor, self.decryptor)
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn generate_shared_secret() -> [u8; SHARED_SECRET_LENGTH] {
+    let mut out = [0u8; SHARED_SECRET_LENGTH];
+    rand::RngCore::fill_bytes(&mut rand::rngs::OsRng, &mut out);
+    out
+}
+
+#[cfg(feature = "std")]
+pub fn generate_verify_token() -> [u8; VERIFY_TOKEN_LENGTH] {
+    let mut out = [0u8; VERIFY_TOKEN_LENGTH];
+    rand::RngCore::fill_bytes(&mut rand::rngs::OsRng, &mut out);
+    out
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    // NIST SP 800-38A, F.3.7 CFB8-AES128.Encrypt
+    const NIST_KEY: [u8; 16] = [
+        0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6,
+        0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c,
+    ];
+    const NIST_IV: [u8; 16] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+        0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+    ];
+    const NIST_PLAINTEXT: [u8; 18] = [
+        0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9,
+        0x3d, 0x7e, 0x11, 0x73, 0x93, 0x17, 0x2a, 0xae, 0x2d,
+    ];
+    const NIST_CIPHERTEXT: [u8; 18] = [
+        0x3b, 0x79, 0x42, 0x4c, 0x9c, 0x0d, 0xd4, 0x36, 0xba,
+        0xce, 0x9e, 0x0e, 0xd4, 0x58, 0x6a, 0x4f, 0x32, 0xb9,
+    ];
+
+    #[test]
+    fn test_nist_vectors() {
+        let mut data = NIST_PLAINTEXT;
+        CraftEncryptor::with_key_iv(&NIST_KEY, &NIST_IV).encrypt(&mut data);
+        assert_eq!(data, NIST_CIPHERTEXT);
+
+        CraftDecryptor::with_key_iv(&NIST_KEY, &NIST_IV).decrypt(&mut data);
+        assert_eq!(data, NIST_PLAINTEXT);
+    }
+
+    #[test]
+    fn test_stream_in_chunks() {
+        let mut whole = NIST_PLAINTEXT;
+        CraftEncryptor::with_key_iv(&NIST_KEY, &NIST_IV).encrypt(&mut whole);
+
+        let mut chunked = NIST_PLAINTEXT;
+        let mut encryptor = CraftEncryptor::with_key_iv(&NIST_KEY, &NIST_IV);
+        for chunk in chunked.chunks_mut(5) {
+            encryptor.encrypt(chunk);
+        }
+        assert_eq!(whole, chunked);
+
+        let mut decryptor = CraftDecryptor::with_key_iv(&NIST_KEY, &NIST_IV);
+        let (head, tail) = chunked.split_at_mut(7);
+        decryptor.decrypt(head);
+        decryptor.decrypt(tail);
+        assert_eq!(chunked, NIST_PLAINTEXT);
+    }
+
+    #[test]
+    fn test_bad_secret_length() {
+        assert!(matches!(CraftCipher::new(&[0u8; 8]), Err(EncryptionErr::BadSharedSecretLength(8))));
+    }
+
+    #[test]
+    fn test_generated_secrets() {
+        let secret = generate_shared_secret();
+        assert_ne!(secret, generate_shared_secret());
+        assert_eq!(generate_verify_token().len(), VERIFY_TOKEN_LENGTH);
+
+        let mut client = CraftCipher::new(&secret).expect("valid secret");
+        let mut server = CraftCipher::new(&secret).expect("valid secret");
+        let message: Vec<u8> = (0..=255u8).collect();
+
+        let mut data = message.clone();
+        client.encrypt(data.as_mut_slice());
+        assert_ne!(data, message);
+        server.decrypt(data.as_mut_slice());
+        assert_eq!(data, message);
+
+        let mut data = message.clone();
+        server.encrypt(data.as_mut_slice());
+        client.decrypt(data.as_mut_slice());
+        assert_eq!(data, message);
+    }
+
+    #[cfg(feature = "v1_16_3")]
+    #[test]
+    fn test_encrypted_frames() {
+        use crate::frame::{read_raw_packet, write_packet};
+        use crate::protocol::{PacketDirection::ServerBound, RawPacket, State::Login};
+        use crate::types::BytesSerializer;
+        use crate::v1_16_3::*;
+        use alloc::borrow::ToOwned;
+
+        let secret = generate_shared_secret();
+        let packet = Packet753::LoginStart(LoginStartSpec { name: "Notch".to_owned() });
+        let mut out = BytesSerializer::default();
+        write_packet(&packet, &mut out).expect("write succeeds");
+        write_packet(&packet, &mut out).expect("write succeeds");
+        let mut wire = out.into_bytes();
+        CraftEncryptor::new(&secret).unwrap().encrypt(wire.as_mut_slice());
+
+        // bytes arrive one at a time, and are decrypted as they arrive
+        let mut decryptor = CraftDecryptor::new(&secret).unwrap();
+        let mut buf = Vec::new();
+        let mut read = Vec::new();
+        for byte in wire {
+            buf.push(byte);
+            let at = buf.len() - 1;
+            decryptor.decrypt(&mut buf[at..]);
+            let consumed = match read_raw_packet::<RawPacket753>(buf.as_slice(), Login, ServerBound).unwrap() {
+                Some(raw) => {
+                    read.push(raw.value.deserialize().expect("deserializes"));
+                    buf.len() - raw.data.len()
+                }
+                None => 0,
+            };
+            buf.drain(..consumed);
+        }
+
+        assert_eq!(read, alloc::vec![packet.clone(), packet]);
+    }
+}
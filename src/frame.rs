@@ -0,0 +1,428 @@
+use crate::connection::StateErr;
+use crate::limits::DeserializeLimits;
+use crate::protocol::{Id, Packet, PacketDirection, PacketErr, RawPacket, State};
+use crate::types::{BytesSerializer, VarInt};
+use crate::utils::take;
+use crate::{Deserialize, DeserializeErr, Deserialized, Serialize, SerializeErr, Serializer};
+use alloc::{fmt, string::String, vec::Vec};
+
+// the vanilla client and server never send a length prefix longer than 3 bytes
+pub const MAX_FRAME_LENGTH: usize = 0x1FFFFF;
+
+pub enum FrameErr {
+    FrameTooLong(usize),
+    DeserializeFailed(DeserializeErr),
+    SerializeFailed(SerializeErr),
+    BadPacket(PacketErr),
+    BadDataLength(usize),
+    CompressionFailed(String),
+    BadState(StateErr),
+}
+
+impl fmt::Display for FrameErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use FrameErr::*;
+        match self {
+            FrameTooLong(size) => f.write_fmt(format_args!(
+                "frame of {} bytes exceeds maximum of {} bytes",
+                size, MAX_FRAME_LENGTH
+            )),
+            DeserializeFailed(err) => f.write_fmt(format_args!("failed to read frame: {:?}", err)),
+            SerializeFailed(err) => f.write_fmt(format_args!("failed to write frame: {:?}", err)),
+            BadPacket(err) => f.write_fmt(format_args!("bad packet in frame: {:?}", err)),
+            BadDataLength(size) => f.write_fmt(format_args!(
+                "bad uncompressed data length {} in frame",
+                size
+            )),
+            CompressionFailed(message) => {
+                f.write_fmt(format_args!("frame compression failed: {}", message))
+            }
+            BadState(err) => f.write_fmt(format_args!("{}", err)),
+        }
+    }
+}
+
+impl fmt::Debug for FrameErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <dyn fmt::Display>::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FrameErr {}
+
+impl From<DeserializeErr> for FrameErr {
+    fn from(err: DeserializeErr) -> Self {
+        FrameErr::DeserializeFailed(err)
+    }
+}
+
+impl From<SerializeErr> for FrameErr {
+    fn from(err: SerializeErr) -> Self {
+        FrameErr::SerializeFailed(err)
+    }
+}
+
+impl From<PacketErr> for FrameErr {
+    fn from(err: PacketErr) -> Self {
+        FrameErr::BadPacket(err)
+    }
+}
+
+impl From<StateErr> for FrameErr {
+    fn from(err: StateErr) -> Self {
+        FrameErr::BadState(err)
+    }
+}
+
+pub type FrameResult<T> = Result<T, FrameErr>;
+
+/// Reads one `VarInt length | payload` frame from the front of `data`.
+///
+/// Returns `Ok(None)` if `data` does not yet contain the whole frame, in which case the caller
+/// should read more bytes and try again with the same buffer.
+pub fn read_frame(data: &[u8]) -> FrameResult<Option<Deserialized<'_, &[u8]>>> {
+    let Deserialized { value: length, data: rest } = match VarInt::mc_deserialize(data) {
+        Ok(v) => v,
+        Err(DeserializeErr::Eof) => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+
+    if length.0 < 0 {
+        return Err(DeserializeErr::NegativeLength(length).into());
+    }
+
+    let length = length.0 as usize;
+    if length > MAX_FRAME_LENGTH {
+        return Err(FrameErr::FrameTooLong(length));
+    }
+
+    match take(length, rest) {
+        Ok(frame) => Ok(Some(frame)),
+        Err(DeserializeErr::Eof) => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RawFrame<'a> {
+    pub id: Id,
+    pub body: &'a [u8],
+}
+
+impl<'a> RawFrame<'a> {
+    /// Splits a frame payload (as returned by `read_frame`) into its packet id and body.
+    pub fn parse(payload: &'a [u8], state: State, direction: PacketDirection) -> FrameResult<Self> {
+        let Deserialized { value: id, data: body } = VarInt::mc_deserialize(payload)?;
+        Ok(Self {
+            id: Id { id: id.0, state, direction },
+            body,
+        })
+    }
+
+    pub fn packet<P>(&self) -> FrameResult<P> where P: RawPacket<'a> {
+        Ok(P::create(self.id, self.body)?)
+    }
+
+    pub fn deserialize<P: Packet>(&self) -> FrameResult<P> {
+        Ok(P::mc_deserialize_body(self.id, self.body)?)
+    }
+
+    pub fn deserialize_with_limits<P: Packet>(&self, limits: &DeserializeLimits) -> FrameResult<P> {
+        Ok(P::mc_deserialize_body_with_limits(self.id, self.body, limits)?)
+    }
+
+    pub fn write<S: Serializer>(&self, to: &mut S) -> FrameResult<()> {
+        let id = VarInt(self.id.id);
+        write_frame_length(id.serialized_len() + self.body.len(), to)?;
+        to.serialize_other(&id)?;
+        to.serialize_bytes(self.body)?;
+        Ok(())
+    }
+}
+
+/// Reads one frame from the front of `data` and resolves its id against `state` and `direction`.
+///
+/// Like `read_frame`, this returns `Ok(None)` when more bytes are needed.
+pub fn read_raw_packet<'a, P>(
+    data: &'a [u8],
+    state: State,
+    direction: PacketDirection,
+) -> FrameResult<Option<Deserialized<'a, P>>> where P: RawPacket<'a> {
+    match read_frame(data)? {
+        Some(Deserialized { value: payload, data: rest }) => {
+            let packet = RawFrame::parse(payload, state, direction)?.packet()?;
+            Ok(Some(Deserialized::create(packet, rest)))
+        }
+        None => Ok(None),
+    }
+}
+
+pub fn write_frame<S: Serializer>(payload: &[u8], to: &mut S) -> FrameResult<()> {
+    write_frame_length(payload.len(), to)?;
+    to.serialize_bytes(payload)?;
+    Ok(())
+}
+
+fn write_frame_length<S: Serializer>(length: usize, to: &mut S) -> FrameResult<()> {
+    if length > MAX_FRAME_LENGTH {
+        return Err(FrameErr::FrameTooLong(length));
+    }
+
+    to.serialize_other(&VarInt(length as i32))?;
+    Ok(())
+}
+
+/// The length of the `VarInt id | body` payload of a packet, without serializing it.
+pub fn packet_payload_len<P: Packet>(packet: &P) -> usize {
+    VarInt(packet.id().id).serialized_len() + packet.serialized_body_len()
+}
+
+/// Serializes the `VarInt id | body` payload of a packet, without a length prefix.
+pub fn serialize_packet_payload<P: Packet>(packet: &P) -> FrameResult<Vec<u8>> {
+    let mut payload = BytesSerializer::with_capacity(packet_payload_len(packet));
+    payload.serialize_other(&VarInt(packet.id().id))?;
+    packet.mc_serialize_body(&mut payload)?;
+    Ok(payload.into_bytes())
+}
+
+/// Writes a packet as a frame straight into `to`, working out the length prefix up front with
+/// `packet_payload_len` instead of serializing the payload into a buffer first.
+///
+/// If serializing the packet fails, part of the frame may already have been written to `to`.
+pub fn write_packet<P: Packet, S: Serializer>(packet: &P, to: &mut S) -> FrameResult<()> {
+    write_frame_length(packet_payload_len(packet), to)?;
+    to.serialize_other(&VarInt(packet.id().id))?;
+    packet.mc_serialize_body(to)?;
+    Ok(())
+}
+
+/// Serializes a packet as a frame into a buffer of exactly the right size.
+pub fn serialize_packet_frame<P: Packet>(packet: &P) -> FrameResult<Vec<u8>> {
+    let payload_len = packet_payload_len(packet);
+    let mut out = BytesSerializer::with_capacity(VarInt(payload_len as i32).serialized_len() + payload_len);
+    write_packet(packet, &mut out)?;
+    Ok(out.into_bytes())
+}
+
+#[cfg(all(test, feature = "std", feature = "v1_16_3"))]
+mod tests {
+    use super::*;
+    use crate::protocol::{HasPacketBody, HasPacketId, PacketDirection::*, State::*};
+    use crate::v1_16_3::*;
+    use alloc::{borrow::ToOwned, vec};
+
+    fn handshake() -> Packet753 {
+        Packet753::Handshake(HandshakeSpec {
+            version: VarInt(753),
+            server_address: "localhost".to_owned(),
+            server_port: 25565,
+            next_state: HandshakeNextState::Login,
+        })
+    }
+
+    fn login_start() -> Packet753 {
+        Packet753::LoginStart(LoginStartSpec {
+            name: "Notch".to_owned(),
+        })
+    }
+
+    fn frame_bytes(packets: &[Packet753]) -> Vec<u8> {
+        let mut out = BytesSerializer::default();
+        for packet in packets {
+            write_packet(packet, &mut out).expect("write succeeds");
+        }
+        out.into_bytes()
+    }
+
+    #[test]
+    fn test_round_trip_frames() {
+        let bytes = frame_bytes(&[handshake(), login_start()]);
+
+        let Deserialized { value: raw, data: rest } =
+            read_raw_packet::<RawPacket753>(bytes.as_slice(), Handshaking, ServerBound)
+                .expect("valid frame")
+                .expect("complete frame");
+        assert_eq!(raw.deserialize().expect("deserializes"), handshake());
+
+        let Deserialized { value: raw, data: rest } =
+            read_raw_packet::<RawPacket753>(rest, Login, ServerBound)
+                .expect("valid frame")
+                .expect("complete frame");
+        assert_eq!(raw.deserialize().expect("deserializes"), login_start());
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn test_partial_frames_need_more() {
+        let bytes = frame_bytes(&[handshake()]);
+        for end in 0..bytes.len() {
+            let partial = read_raw_packet::<RawPacket753>(&bytes[..end], Handshaking, ServerBound)
+                .expect("partial frame is not an error");
+            assert!(partial.is_none(), "{} of {} bytes should need more", end, bytes.len());
+        }
+
+        assert!(read_frame(bytes.as_slice()).expect("valid").is_some());
+    }
+
+    #[test]
+    fn test_raw_frame_write_round_trip() {
+        let bytes = frame_bytes(&[login_start()]);
+        let payload = read_frame(bytes.as_slice()).expect("valid").expect("complete").value;
+        let frame = RawFrame::parse(payload, Login, ServerBound).expect("valid id");
+        assert_eq!(frame.id, Id { id: 0x00, state: Login, direction: ServerBound });
+        assert_eq!(frame.deserialize::<Packet753>().expect("deserializes"), login_start());
+
+        let mut out = BytesSerializer::default();
+        frame.write(&mut out).expect("write succeeds");
+        assert_eq!(out.into_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_unknown_id() {
+        let mut bytes = BytesSerializer::default();
+        write_frame(&[0x7F, 0x01, 0x02], &mut bytes).expect("write succeeds");
+        let bytes = bytes.into_bytes();
+        match read_raw_packet::<RawPacket753>(bytes.as_slice(), Status, ServerBound) {
+            Err(FrameErr::BadPacket(PacketErr::UnknownId(id))) => assert_eq!(id.id, 0x7F),
+            other => panic!("expected unknown id, got {:?}", other.map(|p| p.is_some())),
+        }
+    }
+
+    #[test]
+    fn test_frame_too_long() {
+        let bytes = [0xFF, 0xFF, 0xFF, 0x01];
+        match read_frame(&bytes) {
+            Err(FrameErr::FrameTooLong(size)) => assert_eq!(size, 0x3FFFFF),
+            other => panic!("expected frame too long, got {:?}", other.map(|p| p.is_some())),
+        }
+
+        let mut out = BytesSerializer::default();
+        let payload = vec![0u8; MAX_FRAME_LENGTH + 1];
+        assert!(matches!(write_frame(payload.as_slice(), &mut out), Err(FrameErr::FrameTooLong(_))));
+    }
+
+    fn chunk_data(size: usize) -> Packet753 {
+        Packet753::PlayChunkData(PlayChunkDataWrapper {
+            data: ChunkData {
+                position: crate::types::ChunkPosition { x: 3, z: -7 },
+                primary_bit_mask: VarInt(0xFFFF),
+                heightmaps: crate::types::NamedNbtTag {
+                    root: crate::nbt::NamedTag {
+                        name: "".to_owned(),
+                        payload: crate::nbt::Tag::Compound(vec![]),
+                    },
+                },
+                biomes: Some(vec![VarInt(1); 1024].into()),
+                data: vec![7u8; size].into(),
+                block_entities: vec![],
+            },
+        })
+    }
+
+    fn chat_message() -> Packet753 {
+        Packet753::PlayClientChatMessage(PlayClientChatMessageSpec { message: "hello there".to_owned() })
+    }
+
+    // what write_packet did before it knew the length up front
+    fn write_packet_two_pass<S: Serializer>(packet: &Packet753, to: &mut S) -> FrameResult<()> {
+        let mut payload = BytesSerializer::default();
+        payload.serialize_other(&VarInt(packet.id().id))?;
+        packet.mc_serialize_body(&mut payload)?;
+        write_frame(payload.into_bytes().as_slice(), to)
+    }
+
+    #[test]
+    fn test_single_pass_matches_two_pass() {
+        for packet in &[handshake(), login_start(), chat_message(), chunk_data(0), chunk_data(200), chunk_data(70000)] {
+            let mut out = BytesSerializer::default();
+            write_packet_two_pass(packet, &mut out).expect("write succeeds");
+            let expected = out.into_bytes();
+
+            assert_eq!(frame_bytes(core::slice::from_ref(packet)), expected);
+            let single = serialize_packet_frame(packet).expect("write succeeds");
+            assert_eq!(single.capacity(), single.len());
+            assert_eq!(single, expected);
+        }
+    }
+
+    #[test]
+    fn test_write_packet_too_long() {
+        let mut out = BytesSerializer::default();
+        assert!(matches!(write_packet(&chunk_data(MAX_FRAME_LENGTH), &mut out), Err(FrameErr::FrameTooLong(_))));
+        assert!(out.into_bytes().is_empty());
+    }
+
+    #[test]
+    fn test_write_packet_without_buffer() {
+        let expected = frame_bytes(&[handshake(), login_start()]);
+
+        let mut out = crate::WriteSerializer::new(Vec::new());
+        write_packet(&handshake(), &mut out).expect("write succeeds");
+        write_packet(&login_start(), &mut out).expect("write succeeds");
+        assert_eq!(out.into_inner(), expected);
+
+        let mut buf = [0u8; 64];
+        let mut out = crate::SliceSerializer::new(&mut buf);
+        write_packet(&handshake(), &mut out).expect("write succeeds");
+        write_packet(&login_start(), &mut out).expect("write succeeds");
+        assert_eq!(out.written(), expected.as_slice());
+
+        let mut buf = [0u8; 8];
+        let mut out = crate::SliceSerializer::new(&mut buf);
+        assert!(matches!(
+            write_packet(&handshake(), &mut out),
+            Err(FrameErr::SerializeFailed(SerializeErr::BufferFull { .. }))
+        ));
+    }
+
+    #[cfg(feature = "bench")]
+    fn bench_write(b: &mut test::Bencher, packet: Packet753, single_pass: bool) {
+        let mut out = crate::test_macros::BenchSerializer::default();
+        write_packet(&packet, &mut out).expect("write succeeds");
+        b.bytes = out.len() as u64;
+        out.reset();
+
+        b.iter(|| {
+            if single_pass {
+                write_packet(&packet, &mut out).expect("write succeeds");
+            } else {
+                write_packet_two_pass(&packet, &mut out).expect("write succeeds");
+            }
+            out.reset();
+        })
+    }
+
+    #[cfg(feature = "bench")]
+    #[bench]
+    fn bench_write_chunk_single_pass(b: &mut test::Bencher) {
+        bench_write(b, chunk_data(65536), true);
+    }
+
+    #[cfg(feature = "bench")]
+    #[bench]
+    fn bench_write_chunk_two_pass(b: &mut test::Bencher) {
+        bench_write(b, chunk_data(65536), false);
+    }
+
+    #[cfg(feature = "bench")]
+    #[bench]
+    fn bench_write_chat_single_pass(b: &mut test::Bencher) {
+        bench_write(b, chat_message(), true);
+    }
+
+    #[cfg(feature = "bench")]
+    #[bench]
+    fn bench_write_chat_two_pass(b: &mut test::Bencher) {
+        bench_write(b, chat_message(), false);
+    }
+
+    #[test]
+    fn test_negative_length() {
+        let bytes = [0xFF, 0xFF, 0xFF, 0xFF, 0x0F];
+        assert!(matches!(
+            read_frame(&bytes),
+            Err(FrameErr::DeserializeFailed(DeserializeErr::NegativeLength(_)))
+        ));
+    }
+}
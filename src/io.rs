@@ -0,0 +1,266 @@
+use crate::codec::{CodecErr, PacketCodec};
+use crate::protocol::{HasCompressionThreshold, HasNextState, Packet, PacketDirection, State};
+use alloc::vec::Vec;
+use std::io::{self, Read, Write};
+
+#[cfg(feature = "encryption")]
+use crate::encryption::EncryptionResult;
+
+const READ_CHUNK_SIZE: usize = 4096;
+
+pub type IoResult<T> = Result<T, CodecErr>;
+
+/// Reads packets from a blocking `Read`, such as a `TcpStream`.
+#[derive(Debug)]
+pub struct PacketReader<R, P> {
+    inner: R,
+    codec: PacketCodec<P>,
+    buf: Vec<u8>,
+}
+
+impl<R, P> PacketReader<R, P> where R: Read, P: Packet + HasCompressionThreshold + HasNextState {
+    /// Creates a reader for packets sent in `direction`.
+    pub fn new(inner: R, direction: PacketDirection) -> Self {
+        Self {
+            inner,
+            codec: PacketCodec::new(direction),
+            buf: Vec::new(),
+        }
+    }
+
+    pub fn client(inner: R) -> Self {
+        Self::new(inner, PacketDirection::ClientBound)
+    }
+
+    pub fn server(inner: R) -> Self {
+        Self::new(inner, PacketDirection::ServerBound)
+    }
+
+    /// Blocks until a whole packet was read, and resolves it in the current state.
+    ///
+    /// Fails with `UnexpectedEof` if the stream ends, even between two packets.
+    pub fn read_packet(&mut self) -> IoResult<P> {
+        loop {
+            if let Some((consumed, packet)) = self.codec.decode(self.buf.as_mut_slice())? {
+                self.buf.drain(..consumed);
+                return Ok(packet);
+            }
+
+            let start = self.buf.len();
+            self.buf.resize(start + READ_CHUNK_SIZE, 0);
+            let n = match self.inner.read(&mut self.buf[start..]) {
+                Ok(n) => n,
+                Err(err) => {
+                    self.buf.truncate(start);
+                    if err.kind() == io::ErrorKind::Interrupted {
+                        continue;
+                    }
+                    return Err(err.into());
+                }
+            };
+            self.buf.truncate(start + n);
+            if n == 0 {
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+            }
+        }
+    }
+
+    pub fn state(&self) -> State {
+        self.codec.state()
+    }
+
+    pub fn set_state(&mut self, state: State) {
+        self.codec.set_state(state)
+    }
+
+    #[cfg(feature = "compression")]
+    pub fn set_compression_threshold(&mut self, threshold: i32) {
+        self.codec.set_compression_threshold(threshold)
+    }
+
+    #[cfg(feature = "encryption")]
+    pub fn enable_encryption(&mut self, shared_secret: &[u8]) -> EncryptionResult<()> {
+        self.codec.enable_encryption(shared_secret)
+    }
+
+    pub fn codec(&self) -> &PacketCodec<P> {
+        &self.codec
+    }
+
+    pub fn codec_mut(&mut self) -> &mut PacketCodec<P> {
+        &mut self.codec
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+/// Writes packets to a blocking `Write`, such as a `TcpStream`.
+#[derive(Debug)]
+pub struct PacketWriter<W, P> {
+    inner: W,
+    codec: PacketCodec<P>,
+    buf: Vec<u8>,
+}
+
+impl<W, P> PacketWriter<W, P> where W: Write, P: Packet + HasCompressionThreshold + HasNextState {
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            // the direction is only used when decoding, so it doesn't matter here
+            codec: PacketCodec::new(PacketDirection::ServerBound),
+            buf: Vec::new(),
+        }
+    }
+
+    /// Writes the whole frame for `packet` and flushes the underlying writer.
+    pub fn write_packet(&mut self, packet: &P) -> IoResult<()> {
+        self.buf.clear();
+        self.codec.encode(packet, &mut self.buf)?;
+        self.inner.write_all(self.buf.as_slice())?;
+        self.inner.flush()?;
+        Ok(())
+    }
+
+    pub fn state(&self) -> State {
+        self.codec.state()
+    }
+
+    pub fn set_state(&mut self, state: State) {
+        self.codec.set_state(state)
+    }
+
+    #[cfg(feature = "compression")]
+    pub fn set_compression_threshold(&mut self, threshold: i32) {
+        self.codec.set_compression_threshold(threshold)
+    }
+
+    #[cfg(feature = "encryption")]
+    pub fn enable_encryption(&mut self, shared_secret: &[u8]) -> EncryptionResult<()> {
+        self.codec.enable_encryption(shared_secret)
+    }
+
+    pub fn codec(&self) -> &PacketCodec<P> {
+        &self.codec
+    }
+
+    pub fn codec_mut(&mut self) -> &mut PacketCodec<P> {
+        &mut self.codec
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+#[cfg(all(test, feature = "v1_16_3"))]
+mod tests {
+    use super::*;
+    use crate::status::*;
+    use crate::types::{Chat, VarInt};
+    use crate::v1_16_3::*;
+    use alloc::borrow::ToOwned;
+    use std::io::Cursor;
+
+    // hands out one byte per read, to exercise partial frames
+    struct Trickle<R>(R);
+
+    impl<R: Read> Read for Trickle<R> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = buf.len().min(1);
+            self.0.read(&mut buf[..n])
+        }
+    }
+
+    fn status_response() -> Packet753 {
+        Packet753::StatusResponse(StatusResponseSpec {
+            response: StatusSpec::builder()
+                .version("1.16.3", 753)
+                .players(1, 20)
+                .description(Chat::from_text("hello"))
+                .build(),
+        })
+    }
+
+    #[test]
+    fn test_status_exchange() {
+        let mut client_out = PacketWriter::<_, Packet753>::new(Vec::new());
+        client_out.write_packet(&Packet753::Handshake(HandshakeSpec {
+            version: VarInt(753),
+            server_address: "localhost".to_owned(),
+            server_port: 25565,
+            next_state: HandshakeNextState::Status,
+        })).unwrap();
+        assert_eq!(client_out.state(), State::Status);
+        client_out.write_packet(&Packet753::StatusRequest(StatusRequestSpec)).unwrap();
+        client_out.write_packet(&Packet753::StatusPing(StatusPingSpec { payload: 42 })).unwrap();
+
+        let mut server_in = PacketReader::<_, Packet753>::server(Trickle(Cursor::new(client_out.into_inner())));
+        assert!(matches!(server_in.read_packet().unwrap(), Packet753::Handshake(_)));
+        assert_eq!(server_in.state(), State::Status);
+        assert_eq!(server_in.read_packet().unwrap(), Packet753::StatusRequest(StatusRequestSpec));
+        assert_eq!(server_in.read_packet().unwrap(), Packet753::StatusPing(StatusPingSpec { payload: 42 }));
+
+        let mut server_out = PacketWriter::<_, Packet753>::new(Vec::new());
+        server_out.set_state(State::Status);
+        server_out.write_packet(&status_response()).unwrap();
+        server_out.write_packet(&Packet753::StatusPong(StatusPongSpec { payload: 42 })).unwrap();
+
+        let mut client_in = PacketReader::<_, Packet753>::client(Cursor::new(server_out.into_inner()));
+        client_in.set_state(State::Status);
+        assert_eq!(client_in.read_packet().unwrap(), status_response());
+        assert_eq!(client_in.read_packet().unwrap(), Packet753::StatusPong(StatusPongSpec { payload: 42 }));
+        assert!(matches!(client_in.read_packet(), Err(CodecErr::Io(err)) if err.kind() == io::ErrorKind::UnexpectedEof));
+    }
+
+    #[cfg(feature = "encryption")]
+    #[test]
+    fn test_encrypted_trickle() {
+        let secret = crate::encryption::generate_shared_secret();
+        let mut out = PacketWriter::<_, Packet753>::new(Vec::new());
+        out.set_state(State::Status);
+        out.enable_encryption(&secret).unwrap();
+        out.write_packet(&status_response()).unwrap();
+
+        let mut input = PacketReader::<_, Packet753>::client(Trickle(Cursor::new(out.into_inner())));
+        input.set_state(State::Status);
+        input.enable_encryption(&secret).unwrap();
+        assert_eq!(input.read_packet().unwrap(), status_response());
+    }
+
+    #[test]
+    fn test_wrong_state_is_an_error() {
+        let mut out = PacketWriter::<_, Packet753>::new(Vec::new());
+        assert!(matches!(
+            out.write_packet(&Packet753::LoginStart(LoginStartSpec { name: "Notch".to_owned() })),
+            Err(CodecErr::Frame(crate::frame::FrameErr::BadState(_)))
+        ));
+        assert!(out.get_ref().is_empty());
+        out.set_state(State::Login);
+        out.write_packet(&Packet753::LoginStart(LoginStartSpec { name: "Notch".to_owned() })).unwrap();
+
+        let mut input = PacketReader::<_, Packet753>::server(Cursor::new(out.into_inner()));
+        input.set_state(State::Status);
+        assert!(matches!(
+            input.read_packet(),
+            Err(CodecErr::Frame(crate::frame::FrameErr::BadPacket(_)))
+        ));
+    }
+}
@@ -0,0 +1,477 @@
+// The server list ping used before the netty rewrite (1.7). Vanilla servers still answer it, so a
+// server has to check for it before reading a normal handshake frame: a modern connection never
+// starts with 0xFE, because that would be a 254+ byte handshake.
+//
+// request formats:
+//   beta 1.8 - 1.3: 0xFE
+//   1.4 - 1.5:      0xFE 0x01
+//   1.6:            0xFE 0x01 0xFA, then a MC|PingHost plugin message
+//
+// every response is a 0xFF kick packet carrying a single UTF-16BE string, which is either
+// "motd§online§max" (beta) or "§1\0protocol\0version\0motd\0online\0max" (1.4+).
+use crate::status::{StatusSpec, StatusVersionSpec};
+use crate::utils::take;
+use crate::{
+    Deserialize, DeserializeErr, DeserializeResult, Deserialized, Serialize, SerializeErr,
+    SerializeResult, Serializer,
+};
+use alloc::{format, string::{String, ToString}, vec::Vec};
+
+pub const LEGACY_PING_ID: u8 = 0xFE;
+pub const LEGACY_PING_PAYLOAD: u8 = 0x01;
+pub const LEGACY_PLUGIN_MESSAGE_ID: u8 = 0xFA;
+pub const LEGACY_KICK_ID: u8 = 0xFF;
+pub const PING_HOST_CHANNEL: &str = "MC|PingHost";
+
+// what 1.4+ servers put in front of the \0 separated response
+const RESPONSE_PREFIX: &str = "\u{a7}1";
+
+/// Whether the first bytes read from a new connection are a legacy ping rather than a handshake.
+///
+/// One byte is enough to decide, but the request itself may still be incomplete, see
+/// `LegacyPingRequest`.
+pub fn is_legacy_ping(data: &[u8]) -> bool {
+    data.first() == Some(&LEGACY_PING_ID)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LegacyPingHostSpec {
+    pub protocol_version: u8,
+    pub hostname: String,
+    pub port: i32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LegacyPingRequest {
+    Beta,
+    V1_4,
+    V1_6(LegacyPingHostSpec),
+}
+
+impl LegacyPingRequest {
+    // the response format the client sending this request understands
+    pub fn expects_beta_response(&self) -> bool {
+        matches!(self, LegacyPingRequest::Beta)
+    }
+}
+
+impl Serialize for LegacyPingRequest {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        use LegacyPingRequest::*;
+
+        to.serialize_byte(LEGACY_PING_ID)?;
+        match self {
+            Beta => Ok(()),
+            V1_4 => to.serialize_byte(LEGACY_PING_PAYLOAD),
+            V1_6(host) => {
+                to.serialize_byte(LEGACY_PING_PAYLOAD)?;
+                to.serialize_byte(LEGACY_PLUGIN_MESSAGE_ID)?;
+                serialize_utf16(PING_HOST_CHANNEL, to)?;
+                let n_host_units = host.hostname.encode_utf16().count();
+                // protocol version byte, hostname length short, hostname, port int
+                let data_length = 1 + 2 + (2 * n_host_units) + 4;
+                if data_length > (i16::MAX as usize) {
+                    return Err(SerializeErr::CannotSerialize(format!(
+                        "hostname of {} chars is too long for a legacy ping",
+                        n_host_units
+                    )));
+                }
+                to.serialize_other(&(data_length as i16))?;
+                to.serialize_byte(host.protocol_version)?;
+                serialize_utf16(host.hostname.as_str(), to)?;
+                to.serialize_other(&host.port)
+            }
+        }
+    }
+}
+
+// Reads the request from the front of data. The 1.4 and 1.6 requests share a prefix, so a server
+// that sees just 0xFE 0x01 and wants to tell them apart has to wait briefly for more bytes the way
+// vanilla does. A truncated 1.6 request is an Eof.
+impl Deserialize for LegacyPingRequest {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: id, data } = u8::mc_deserialize(data)?;
+        if id != LEGACY_PING_ID {
+            return Err(DeserializeErr::CannotUnderstandValue(format!(
+                "expected legacy ping 0x{:02X}, got 0x{:02X}",
+                LEGACY_PING_ID, id
+            )));
+        }
+
+        let data = match data.split_first() {
+            None => return Deserialized::ok(LegacyPingRequest::Beta, data),
+            Some((&LEGACY_PING_PAYLOAD, rest)) => rest,
+            Some((other, _)) => {
+                return Err(DeserializeErr::CannotUnderstandValue(format!(
+                    "bad legacy ping payload 0x{:02X}",
+                    other
+                )))
+            }
+        };
+
+        let data = match data.split_first() {
+            None => return Deserialized::ok(LegacyPingRequest::V1_4, data),
+            Some((&LEGACY_PLUGIN_MESSAGE_ID, rest)) => rest,
+            Some((other, _)) => {
+                return Err(DeserializeErr::CannotUnderstandValue(format!(
+                    "expected legacy plugin message 0x{:02X}, got 0x{:02X}",
+                    LEGACY_PLUGIN_MESSAGE_ID, other
+                )))
+            }
+        };
+
+        let Deserialized { value: channel, data } = deserialize_utf16(data)?;
+        if channel != PING_HOST_CHANNEL {
+            return Err(DeserializeErr::CannotUnderstandValue(format!(
+                "expected legacy ping channel {}, got {}",
+                PING_HOST_CHANNEL, channel
+            )));
+        }
+
+        let Deserialized { value: data_length, data } = i16::mc_deserialize(data)?;
+        if data_length < 0 {
+            return Err(DeserializeErr::CannotUnderstandValue(format!(
+                "negative legacy ping data length {}",
+                data_length
+            )));
+        }
+        let Deserialized { value: body, data: rest } = take(data_length as usize, data)?;
+
+        let Deserialized { value: protocol_version, data: body } = u8::mc_deserialize(body)?;
+        let Deserialized { value: hostname, data: body } = deserialize_utf16(body)?;
+        let Deserialized { value: port, data: body } = i32::mc_deserialize(body)?;
+        if !body.is_empty() {
+            return Err(DeserializeErr::CannotUnderstandValue(format!(
+                "{} unexpected bytes after legacy ping host",
+                body.len()
+            )));
+        }
+
+        Deserialized::ok(
+            LegacyPingRequest::V1_6(LegacyPingHostSpec {
+                protocol_version,
+                hostname,
+                port,
+            }),
+            rest,
+        )
+    }
+}
+
+// The contents of the kick packet a legacy ping is answered with. version is None for the beta
+// format, which only has the motd and player counts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LegacyPingResponse {
+    pub version: Option<StatusVersionSpec>,
+    pub motd: String,
+    pub online: i32,
+    pub max: i32,
+}
+
+impl LegacyPingResponse {
+    // the same response in the format the client that sent request can read
+    pub fn for_request(mut self, request: &LegacyPingRequest) -> Self {
+        if request.expects_beta_response() {
+            self.version = None;
+        } else if self.version.is_none() {
+            self.version = Some(StatusVersionSpec {
+                name: String::new(),
+                protocol: 0,
+            });
+        }
+
+        self
+    }
+
+    fn to_kick_message(&self) -> Result<String, SerializeErr> {
+        match &self.version {
+            Some(version) => {
+                let fields = [version.name.as_str(), self.motd.as_str()];
+                if fields.iter().any(move |field| field.contains('\0')) {
+                    return Err(SerializeErr::CannotSerialize(
+                        "legacy ping response fields cannot contain \\0".to_string(),
+                    ));
+                }
+
+                Ok(format!(
+                    "{}\0{}\0{}\0{}\0{}\0{}",
+                    RESPONSE_PREFIX, version.protocol, version.name, self.motd, self.online, self.max
+                ))
+            }
+            None => {
+                if self.motd.contains('\u{a7}') {
+                    return Err(SerializeErr::CannotSerialize(
+                        "beta ping motd cannot contain \u{a7}".to_string(),
+                    ));
+                }
+
+                Ok(format!("{}\u{a7}{}\u{a7}{}", self.motd, self.online, self.max))
+            }
+        }
+    }
+
+    fn from_kick_message(message: &str) -> Result<Self, DeserializeErr> {
+        if let Some(rest) = message.strip_prefix(RESPONSE_PREFIX) {
+            let parts: Vec<&str> = rest.split('\0').collect();
+            if parts.len() != 6 || !parts[0].is_empty() {
+                return Err(DeserializeErr::CannotUnderstandValue(format!(
+                    "legacy ping response has {} fields, expected 5",
+                    parts.len().saturating_sub(1)
+                )));
+            }
+
+            Ok(Self {
+                version: Some(StatusVersionSpec {
+                    protocol: parse_legacy_int("protocol", parts[1])?,
+                    name: parts[2].to_string(),
+                }),
+                motd: parts[3].to_string(),
+                online: parse_legacy_int("online", parts[4])?,
+                max: parse_legacy_int("max", parts[5])?,
+            })
+        } else {
+            let parts: Vec<&str> = message.rsplitn(3, '\u{a7}').collect();
+            if parts.len() != 3 {
+                return Err(DeserializeErr::CannotUnderstandValue(format!(
+                    "bad beta ping response {:?}",
+                    message
+                )));
+            }
+
+            Ok(Self {
+                version: None,
+                motd: parts[2].to_string(),
+                online: parse_legacy_int("online", parts[1])?,
+                max: parse_legacy_int("max", parts[0])?,
+            })
+        }
+    }
+}
+
+impl Serialize for LegacyPingResponse {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        to.serialize_byte(LEGACY_KICK_ID)?;
+        serialize_utf16(self.to_kick_message()?.as_str(), to)
+    }
+}
+
+impl Deserialize for LegacyPingResponse {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: id, data } = u8::mc_deserialize(data)?;
+        if id != LEGACY_KICK_ID {
+            return Err(DeserializeErr::CannotUnderstandValue(format!(
+                "expected legacy kick 0x{:02X}, got 0x{:02X}",
+                LEGACY_KICK_ID, id
+            )));
+        }
+
+        deserialize_utf16(data)?.try_map(move |message| Self::from_kick_message(message.as_str()))
+    }
+}
+
+impl From<LegacyPingResponse> for StatusSpec {
+    fn from(other: LegacyPingResponse) -> Self {
+        let mut out = StatusSpec::builder()
+            .players(other.online, other.max)
+            .motd(other.motd.as_str())
+            .build();
+        out.version = other.version;
+        out
+    }
+}
+
+// The motd is flattened to legacy color codes, so anything but plain text components is lost.
+impl From<&StatusSpec> for LegacyPingResponse {
+    fn from(other: &StatusSpec) -> Self {
+        Self {
+            version: other.version.clone(),
+            motd: other.description.to_traditional().unwrap_or_default(),
+            online: other.players.online,
+            max: other.players.max,
+        }
+    }
+}
+
+fn parse_legacy_int(field: &str, value: &str) -> Result<i32, DeserializeErr> {
+    value.parse().map_err(move |_| {
+        DeserializeErr::CannotUnderstandValue(format!(
+            "bad {} value {:?} in legacy ping response",
+            field, value
+        ))
+    })
+}
+
+// strings in the old protocol are a short length in UTF-16 code units, then UTF-16BE data
+fn serialize_utf16<S: Serializer>(value: &str, to: &mut S) -> SerializeResult {
+    let units: Vec<u16> = value.encode_utf16().collect();
+    if units.len() > (i16::MAX as usize) {
+        return Err(SerializeErr::CannotSerialize(format!(
+            "legacy string of {} chars is too long",
+            units.len()
+        )));
+    }
+
+    to.serialize_other(&(units.len() as i16))?;
+    for unit in units {
+        to.serialize_other(&unit)?;
+    }
+
+    Ok(())
+}
+
+fn deserialize_utf16(data: &[u8]) -> DeserializeResult<'_, String> {
+    let Deserialized { value: length, data } = i16::mc_deserialize(data)?;
+    if length < 0 {
+        return Err(DeserializeErr::CannotUnderstandValue(format!(
+            "negative legacy string length {}",
+            length
+        )));
+    }
+
+    let Deserialized { value: raw, data } = take((length as usize) * 2, data)?;
+    let units = raw.chunks(2).map(move |pair| u16::from_be_bytes([pair[0], pair[1]]));
+    let value = core::char::decode_utf16(units)
+        .collect::<Result<String, _>>()
+        .map_err(move |err| DeserializeErr::CannotUnderstandValue(format!("bad legacy string: {}", err)))?;
+
+    Deserialized::ok(value, data)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::types::BytesSerializer;
+    use alloc::{borrow::ToOwned, vec};
+
+    fn utf16(value: &str) -> Vec<u8> {
+        let mut out = Vec::new();
+        let units: Vec<u16> = value.encode_utf16().collect();
+        out.extend_from_slice(&(units.len() as u16).to_be_bytes());
+        for unit in units {
+            out.extend_from_slice(&unit.to_be_bytes());
+        }
+        out
+    }
+
+    fn serialize<S: Serialize>(value: &S) -> Vec<u8> {
+        let mut out = BytesSerializer::default();
+        value.mc_serialize(&mut out).expect("serializes");
+        out.into_bytes()
+    }
+
+    fn ping_host_request() -> Vec<u8> {
+        let mut out = vec![0xFE, 0x01, 0xFA];
+        out.extend(utf16("MC|PingHost"));
+        out.extend_from_slice(&[0x00, 0x19, 0x4A]);
+        out.extend(utf16("localhost"));
+        out.extend_from_slice(&[0x00, 0x00, 0x63, 0xDD]);
+        out
+    }
+
+    #[test]
+    fn test_detect() {
+        assert!(is_legacy_ping(&[0xFE]));
+        assert!(is_legacy_ping(&[0xFE, 0x01, 0xFA]));
+        // length prefixed 1.16 handshake
+        assert!(!is_legacy_ping(&[0x10, 0x00, 0xF1, 0x05]));
+        assert!(!is_legacy_ping(&[]));
+    }
+
+    #[test]
+    fn test_read_requests() {
+        assert_eq!(LegacyPingRequest::mc_deserialize(&[0xFE]).unwrap().value, LegacyPingRequest::Beta);
+        assert_eq!(LegacyPingRequest::mc_deserialize(&[0xFE, 0x01]).unwrap().value, LegacyPingRequest::V1_4);
+
+        let bytes = ping_host_request();
+        let Deserialized { value, data } = LegacyPingRequest::mc_deserialize(&bytes).unwrap();
+        assert!(data.is_empty());
+        assert_eq!(value, LegacyPingRequest::V1_6(LegacyPingHostSpec {
+            protocol_version: 74,
+            hostname: "localhost".to_owned(),
+            port: 25565,
+        }));
+        assert_eq!(serialize(&value), bytes);
+
+        assert!(matches!(LegacyPingRequest::mc_deserialize(&bytes[..bytes.len() - 1]), Err(DeserializeErr::Eof)));
+        assert!(LegacyPingRequest::mc_deserialize(&[0x10, 0x00]).is_err());
+        assert!(LegacyPingRequest::mc_deserialize(&[0xFE, 0x02]).is_err());
+    }
+
+    #[test]
+    fn test_v1_4_response() {
+        let mut bytes = vec![0xFF];
+        bytes.extend(utf16("\u{a7}1\u{0}127\u{0}1.6.4\u{0}A Minecraft Server\u{0}3\u{0}20"));
+
+        let Deserialized { value, data } = LegacyPingResponse::mc_deserialize(&bytes).unwrap();
+        assert!(data.is_empty());
+        assert_eq!(value, LegacyPingResponse {
+            version: Some(StatusVersionSpec {
+                name: "1.6.4".to_owned(),
+                protocol: 127,
+            }),
+            motd: "A Minecraft Server".to_owned(),
+            online: 3,
+            max: 20,
+        });
+        assert_eq!(serialize(&value), bytes);
+    }
+
+    #[test]
+    fn test_beta_response() {
+        let mut bytes = vec![0xFF];
+        bytes.extend(utf16("A Minecraft Server\u{a7}0\u{a7}20"));
+
+        let value = LegacyPingResponse::mc_deserialize(&bytes).unwrap().value;
+        assert_eq!(value, LegacyPingResponse {
+            version: None,
+            motd: "A Minecraft Server".to_owned(),
+            online: 0,
+            max: 20,
+        });
+        assert_eq!(serialize(&value), bytes);
+
+        let mut colored = value.clone();
+        colored.motd = "\u{a7}cred".to_owned();
+        assert!(matches!(colored.mc_serialize(&mut BytesSerializer::default()), Err(SerializeErr::CannotSerialize(_))));
+    }
+
+    #[test]
+    fn test_bad_responses() {
+        let mut missing_field = vec![0xFF];
+        missing_field.extend(utf16("\u{a7}1\u{0}127\u{0}1.6.4\u{0}motd\u{0}3"));
+        assert!(LegacyPingResponse::mc_deserialize(&missing_field).is_err());
+
+        let mut bad_count = vec![0xFF];
+        bad_count.extend(utf16("motd\u{a7}lots\u{a7}20"));
+        assert!(LegacyPingResponse::mc_deserialize(&bad_count).is_err());
+
+        // unpaired surrogate
+        assert!(matches!(LegacyPingResponse::mc_deserialize(&[0xFF, 0x00, 0x01, 0xD8, 0x00]), Err(DeserializeErr::CannotUnderstandValue(_))));
+        assert!(matches!(LegacyPingResponse::mc_deserialize(&[0xFF, 0x00, 0x02, 0x00]), Err(DeserializeErr::Eof)));
+    }
+
+    #[test]
+    fn test_status_conversion() {
+        let response = LegacyPingResponse {
+            version: Some(StatusVersionSpec {
+                name: "1.16.5".to_owned(),
+                protocol: 754,
+            }),
+            motd: "hello".to_owned(),
+            online: 5,
+            max: 100,
+        };
+
+        let status: StatusSpec = response.clone().into();
+        assert_eq!(status.version, response.version);
+        assert_eq!(status.players.online, 5);
+        assert_eq!(status.players.max, 100);
+        assert_eq!(status.description.to_traditional().as_deref(), Some("hello"));
+        assert_eq!(LegacyPingResponse::from(&status), response);
+
+        let beta = response.clone().for_request(&LegacyPingRequest::Beta);
+        assert_eq!(beta.version, None);
+        let modern = beta.for_request(&LegacyPingRequest::V1_4);
+        assert_eq!(modern.version.map(|v| v.protocol), Some(0));
+    }
+}
@@ -4,27 +4,63 @@
 
 extern crate alloc;
 
+// lets the code generated by mcproto-derive, which names ::mcproto_rs, work in here too
+#[cfg(feature = "derive")]
+extern crate self as mcproto_rs;
+
 #[cfg(all(test, feature = "std", feature = "bench"))]
 extern crate test;
 
 mod deserialize;
 mod serialize;
+pub mod limits;
 pub mod nbt;
 pub mod protocol;
 pub mod status;
+pub mod legacy_ping;
+pub mod query;
+pub mod rcon;
 pub mod types;
 pub mod utils;
 pub mod uuid;
 mod chat;
 pub mod byte_order;
+pub mod frame;
+pub mod connection;
+#[cfg(feature = "compression")]
+pub mod compression;
+#[cfg(feature = "encryption")]
+pub mod encryption;
+#[cfg(feature = "auth")]
+pub mod auth;
+pub mod codec;
+pub mod derive;
+#[cfg(feature = "std")]
+pub mod io;
+#[cfg(feature = "std")]
+pub mod ping;
 
+#[cfg(feature = "v1_8_9")]
+pub mod v1_8_9;
+#[cfg(feature = "v1_12_2")]
+pub mod v1_12_2;
 #[cfg(feature = "v1_15_2")]
 pub mod v1_15_2;
 #[cfg(feature = "v1_16_3")]
 pub mod v1_16_3;
+#[cfg(feature = "v1_16_5")]
+pub mod v1_16_5;
+#[cfg(feature = "v1_17_1")]
+pub mod v1_17_1;
+#[cfg(any(feature = "v1_8_9", feature = "v1_12_2", feature = "v1_15_2", feature = "v1_16_3"))]
+pub mod dispatch;
+#[cfg(all(feature = "v1_15_2", feature = "v1_16_3"))]
+pub mod translate;
 
 pub use deserialize::*;
 pub use serialize::*;
+#[cfg(feature = "derive")]
+pub use mcproto_derive::{McDeserialize, McSerialize};
 
 #[cfg(all(test, feature = "std"))]
 mod test_macros;
@@ -0,0 +1,189 @@
+use crate::DeserializeErr;
+
+// the caps the vanilla server applies when reading packets
+pub const MAX_STRING_CHARS: usize = 32767;
+pub const MAX_CHAT_CHARS: usize = 262144;
+pub const MAX_USERNAME_CHARS: usize = 16;
+pub const MAX_PACKET_BYTES: usize = 2097152;
+
+// the most elements reserved up front for an array, bigger ones grow as they are read
+const MAX_RESERVED_ELEMENTS: usize = 4096;
+
+/// Upper bounds on the lengths read from length prefixes while deserializing.
+///
+/// Exceeding any of them fails with `DeserializeErr::LimitExceeded` before anything is allocated
+/// for the value. String lengths are counted in UTF-16 code units, like the vanilla server does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeserializeLimits {
+    pub max_string_chars: usize,
+    pub max_chat_chars: usize,
+    pub max_username_chars: usize,
+    pub max_array_length: usize,
+    pub max_packet_bytes: usize,
+}
+
+impl DeserializeLimits {
+    pub const VANILLA: Self = Self {
+        max_string_chars: MAX_STRING_CHARS,
+        max_chat_chars: MAX_CHAT_CHARS,
+        max_username_chars: MAX_USERNAME_CHARS,
+        max_array_length: MAX_PACKET_BYTES,
+        max_packet_bytes: MAX_PACKET_BYTES,
+    };
+
+    pub const UNLIMITED: Self = Self {
+        max_string_chars: usize::MAX,
+        max_chat_chars: usize::MAX,
+        max_username_chars: usize::MAX,
+        max_array_length: usize::MAX,
+        max_packet_bytes: usize::MAX,
+    };
+
+    pub fn check_packet_bytes(&self, length: usize) -> Result<(), DeserializeErr> {
+        check("packet", length, self.max_packet_bytes)
+    }
+
+    pub fn check_array_length(&self, length: usize) -> Result<(), DeserializeErr> {
+        check("array", length, self.max_array_length)
+    }
+
+    pub fn check_username(&self, username: &str) -> Result<(), DeserializeErr> {
+        check_chars("username", username, self.max_username_chars)
+    }
+
+    /// Runs `f` with these limits applied to every decoder it calls on this thread, then restores
+    /// the previous ones.
+    ///
+    /// Without the `std` feature there is nowhere to keep them, so `f` just runs with the vanilla
+    /// limits; the packet budget and field limits are still checked by `deserialize_with_limits`.
+    pub fn scope<F, T>(&self, f: F) -> T where F: FnOnce() -> T {
+        #[cfg(feature = "std")]
+        {
+            struct Restore(DeserializeLimits);
+
+            impl Drop for Restore {
+                fn drop(&mut self) {
+                    CURRENT.with(|current| current.set(self.0));
+                }
+            }
+
+            let _restore = Restore(CURRENT.with(|current| current.replace(*self)));
+            f()
+        }
+
+        #[cfg(not(feature = "std"))]
+        {
+            f()
+        }
+    }
+}
+
+impl Default for DeserializeLimits {
+    fn default() -> Self {
+        Self::VANILLA
+    }
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static CURRENT: core::cell::Cell<DeserializeLimits> = const { core::cell::Cell::new(DeserializeLimits::VANILLA) };
+}
+
+/// The limits in effect on this thread, which are `DeserializeLimits::VANILLA` outside of
+/// `DeserializeLimits::scope`.
+pub fn current() -> DeserializeLimits {
+    #[cfg(feature = "std")]
+    {
+        CURRENT.with(|current| current.get())
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        DeserializeLimits::VANILLA
+    }
+}
+
+/// Checks an element count read from the wire, and returns how many elements to reserve room for.
+///
+/// Every element takes at least one byte, so a count larger than the remaining data is never
+/// trusted for the allocation; decoding such an array fails with `Eof` instead. Elements can be
+/// much bigger in memory than on the wire though, so at most 4096 are reserved and longer arrays
+/// grow while they are read.
+pub fn array_capacity(count: usize, remaining: &[u8]) -> Result<usize, DeserializeErr> {
+    current().check_array_length(count)?;
+    Ok(count.min(remaining.len()).min(MAX_RESERVED_ELEMENTS))
+}
+
+/// Checks the length prefix of a string, in bytes, before any of it is read.
+///
+/// A UTF-16 code unit never takes more than 3 bytes of UTF-8 (and a pair of them 4), so this
+/// rejects early what `check_chars` would reject after decoding. The error reports the length in
+/// bytes against that cap of 3 bytes per character.
+pub(crate) fn check_string_bytes(what: &'static str, length: usize, max_chars: usize) -> Result<(), DeserializeErr> {
+    check(what, length, max_chars.saturating_mul(3))
+}
+
+pub(crate) fn check_chars(what: &'static str, value: &str, max_chars: usize) -> Result<(), DeserializeErr> {
+    // utf-8 never takes fewer bytes than utf-16 code units, so short strings skip the count
+    if value.len() <= max_chars {
+        return Ok(());
+    }
+
+    check(what, value.encode_utf16().count(), max_chars)
+}
+
+fn check(what: &'static str, length: usize, max: usize) -> Result<(), DeserializeErr> {
+    if length > max {
+        Err(DeserializeErr::LimitExceeded { what, length, max })
+    } else {
+        Ok(())
+    }
+}
+
+/// Limits on specific fields of the packets of a protocol, such as the username in `LoginStart`,
+/// which are checked by `Packet::mc_deserialize_body_with_limits`.
+pub trait HasFieldLimits {
+
+    fn check_field_limits(&self, limits: &DeserializeLimits) -> Result<(), DeserializeErr>;
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_restores_limits() {
+        let limits = DeserializeLimits { max_string_chars: 4, ..DeserializeLimits::VANILLA };
+        assert_eq!(current(), DeserializeLimits::VANILLA);
+        assert_eq!(limits.scope(current), limits);
+        assert_eq!(current(), DeserializeLimits::VANILLA);
+
+        let nested = DeserializeLimits::UNLIMITED.scope(|| limits.scope(current));
+        assert_eq!(nested, limits);
+        assert_eq!(current(), DeserializeLimits::VANILLA);
+    }
+
+    #[test]
+    fn test_array_capacity() {
+        assert!(matches!(array_capacity(3, &[0; 8]), Ok(3)));
+        assert!(matches!(array_capacity(1_000_000, &[0; 8]), Ok(8)));
+        assert!(matches!(array_capacity(1_000_000, &[0; 100_000]), Ok(MAX_RESERVED_ELEMENTS)));
+        assert!(matches!(
+            array_capacity(MAX_PACKET_BYTES + 1, &[]),
+            Err(DeserializeErr::LimitExceeded { what: "array", max: MAX_PACKET_BYTES, .. })
+        ));
+    }
+
+    #[test]
+    fn test_check_username_counts_utf16() {
+        let limits = DeserializeLimits::VANILLA;
+        assert!(limits.check_username("Notch").is_ok());
+        assert!(limits.check_username("0123456789abcdef").is_ok());
+        // 16 characters, but 32 bytes of utf-8
+        assert!(limits.check_username("éééééééééééééééé").is_ok());
+        assert!(matches!(
+            limits.check_username("0123456789abcdefg"),
+            Err(DeserializeErr::LimitExceeded { what: "username", length: 17, max: 16 })
+        ));
+    }
+}
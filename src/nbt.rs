@@ -271,7 +271,7 @@ fn read_tag_list(data: &[u8]) -> DeserializeResult<Tag> {
     if list_length == 0 {
         Deserialized::ok(Tag::List(vec![]), data)
     } else {
-        let mut out_vec = Vec::with_capacity(list_length as usize);
+        let mut out_vec = Vec::with_capacity(crate::limits::array_capacity(list_length.max(0) as usize, data)?);
         let mut remaining_data = data;
         for _ in 0..list_length {
             let Deserialized { value: element, data: rest } =
@@ -324,7 +324,7 @@ fn read_array_tag<'a, R, F, M>(
     if count < 0 {
         Err(DeserializeErr::NbtBadLength(count as isize))
     } else {
-        let mut out = Vec::with_capacity(count as usize);
+        let mut out = Vec::with_capacity(crate::limits::array_capacity(count as usize, data)?);
         let mut data_remaining = data;
         for _ in 0..count {
             let Deserialized {
@@ -583,6 +583,28 @@ mod tests {
         assert_eq!(original, unserialized);
     }
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_array_limits() {
+        // lists and arrays claiming 2^31 - 1 elements, without any of them
+        for (tag_type_id, data) in &[(0x09u8, &[0x03, 0x7F, 0xFF, 0xFF, 0xFF][..]), (0x0B, &[0x7F, 0xFF, 0xFF, 0xFF][..]), (0x0C, &[0x7F, 0xFF, 0xFF, 0xFF][..])] {
+            assert!(matches!(
+                read_tag(*tag_type_id, data),
+                Err(DeserializeErr::LimitExceeded { what: "array", .. })
+            ), "tag {}", tag_type_id);
+        }
+
+        let limits = crate::limits::DeserializeLimits { max_array_length: 2, ..Default::default() };
+        for tag in [Tag::List(vec![Tag::Int(1), Tag::Int(2), Tag::Int(3)]), Tag::IntArray(vec![1, 2, 3]), Tag::LongArray(vec![1, 2, 3])] {
+            let bytes = tag.with_name("x").bytes();
+            assert!(matches!(
+                limits.scope(|| read_named_tag(bytes.as_slice())),
+                Err(DeserializeErr::LimitExceeded { what: "array", length: 3, max: 2 })
+            ));
+            assert!(read_named_tag(bytes.as_slice()).is_ok());
+        }
+    }
+
     #[cfg(feature = "std")]
     #[test]
     fn test_display() {
@@ -0,0 +1,324 @@
+use crate::codec::CodecErr;
+use crate::io::{PacketReader, PacketWriter};
+use crate::protocol::{HasCompressionThreshold, HasNextState, Id, Packet, State};
+use crate::status::StatusSpec;
+use alloc::vec::Vec;
+use std::fmt;
+use std::io::{Read, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub enum PingErr {
+    Codec(CodecErr),
+    UnexpectedPacket(Id),
+    PayloadMismatch { sent: i64, received: i64 },
+}
+
+impl fmt::Display for PingErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use PingErr::*;
+        match self {
+            Codec(err) => f.write_fmt(format_args!("{}", err)),
+            UnexpectedPacket(id) => f.write_fmt(format_args!("unexpected packet {:?} during ping", id)),
+            PayloadMismatch { sent, received } => f.write_fmt(format_args!(
+                "pong payload {} does not match ping payload {}",
+                received, sent
+            )),
+        }
+    }
+}
+
+impl fmt::Debug for PingErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <dyn fmt::Display>::fmt(self, f)
+    }
+}
+
+impl std::error::Error for PingErr {}
+
+impl From<CodecErr> for PingErr {
+    fn from(err: CodecErr) -> Self {
+        PingErr::Codec(err)
+    }
+}
+
+impl From<std::io::Error> for PingErr {
+    fn from(err: std::io::Error) -> Self {
+        PingErr::Codec(err.into())
+    }
+}
+
+pub type PingResult<T> = Result<T, PingErr>;
+
+/// The packets of the status exchange, which look the same in every protocol version.
+pub trait HasStatusPackets: Packet + HasCompressionThreshold + HasNextState {
+
+    fn status_handshake(server_address: &str, server_port: u16) -> Self;
+
+    fn status_request() -> Self;
+
+    fn status_ping(payload: i64) -> Self;
+
+    fn into_status_response(self) -> Result<StatusSpec, Self>;
+
+    fn status_pong_payload(&self) -> Option<i64>;
+}
+
+macro_rules! impl_status_packets {
+    ($feature: literal, $module: ident, $packett: ident) => {
+        #[cfg(feature = $feature)]
+        impl HasStatusPackets for crate::$module::$packett {
+            fn status_handshake(server_address: &str, server_port: u16) -> Self {
+                use crate::$module::{HandshakeNextState, HandshakeSpec};
+
+                Self::Handshake(HandshakeSpec {
+                    version: <Self as crate::protocol::HasPacketId>::version(),
+                    server_address: server_address.into(),
+                    server_port,
+                    next_state: HandshakeNextState::Status,
+                })
+            }
+
+            fn status_request() -> Self {
+                Self::StatusRequest(crate::$module::StatusRequestSpec {})
+            }
+
+            fn status_ping(payload: i64) -> Self {
+                Self::StatusPing(crate::$module::StatusPingSpec { payload })
+            }
+
+            fn into_status_response(self) -> Result<StatusSpec, Self> {
+                match self {
+                    Self::StatusResponse(body) => Ok(body.response),
+                    other => Err(other),
+                }
+            }
+
+            fn status_pong_payload(&self) -> Option<i64> {
+                match self {
+                    Self::StatusPong(body) => Some(body.payload),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+impl_status_packets!("v1_8_9", v1_8_9, Packet47);
+impl_status_packets!("v1_12_2", v1_12_2, Packet340);
+impl_status_packets!("v1_15_2", v1_15_2, Packet578);
+impl_status_packets!("v1_16_3", v1_16_3, Packet753);
+impl_status_packets!("v1_16_5", v1_16_5, Packet754);
+impl_status_packets!("v1_17_1", v1_17_1, Packet756);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PingResponse {
+    pub status: StatusSpec,
+    pub latency: Duration,
+}
+
+/// Runs the server list ping exchange of protocol `P` over `stream`, which is usually a
+/// `TcpStream` (or a `&mut` of one) connected to `server_address:server_port`.
+///
+/// The ping payload is the current time in milliseconds, as the vanilla client sends it, and the
+/// latency is how long ago the payload echoed in the pong was.
+pub fn ping<P, S>(stream: S, server_address: &str, server_port: u16) -> PingResult<PingResponse>
+    where P: HasStatusPackets, S: Read + Write
+{
+    let mut out = PacketWriter::<_, P>::new(Vec::new());
+    let mut input = PacketReader::<_, P>::client(stream);
+
+    out.write_packet(&P::status_handshake(server_address, server_port))?;
+    out.write_packet(&P::status_request())?;
+    flush_to(&mut out, input.get_mut())?;
+    input.set_state(State::Status);
+
+    let status = match input.read_packet()?.into_status_response() {
+        Ok(status) => status,
+        Err(other) => return Err(PingErr::UnexpectedPacket(other.id())),
+    };
+
+    let sent = millis_since_epoch();
+    out.write_packet(&P::status_ping(sent))?;
+    flush_to(&mut out, input.get_mut())?;
+
+    let pong = input.read_packet()?;
+    let received = match pong.status_pong_payload() {
+        Some(payload) => payload,
+        None => return Err(PingErr::UnexpectedPacket(pong.id())),
+    };
+    if received != sent {
+        return Err(PingErr::PayloadMismatch { sent, received });
+    }
+
+    let latency = Duration::from_millis(millis_since_epoch().saturating_sub(received).max(0) as u64);
+    Ok(PingResponse { status, latency })
+}
+
+fn flush_to<P, W>(out: &mut PacketWriter<Vec<u8>, P>, to: &mut W) -> PingResult<()>
+    where P: HasStatusPackets, W: Write
+{
+    to.write_all(out.get_ref().as_slice())?;
+    to.flush()?;
+    out.get_mut().clear();
+    Ok(())
+}
+
+fn millis_since_epoch() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(move |since| since.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+#[cfg(all(test, feature = "v1_16_3"))]
+mod tests {
+    use super::*;
+    use crate::codec::PacketCodec;
+    use crate::protocol::PacketDirection;
+    use crate::types::Chat;
+    use crate::v1_16_3::*;
+    use alloc::{borrow::ToOwned, boxed::Box, collections::VecDeque, vec};
+    use std::io;
+
+    type Responder<P> = Box<dyn FnMut(&P) -> Vec<P>>;
+
+    // answers every packet written to it with whatever respond returns, like a server would
+    struct FakeServer<P> {
+        decoder: PacketCodec<P>,
+        encoder: PacketCodec<P>,
+        received: Vec<P>,
+        incoming: Vec<u8>,
+        outgoing: VecDeque<u8>,
+        respond: Responder<P>,
+    }
+
+    impl<P: HasStatusPackets> FakeServer<P> {
+        fn new<F>(respond: F) -> Self where F: FnMut(&P) -> Vec<P> + 'static {
+            let mut encoder = PacketCodec::new(PacketDirection::ClientBound);
+            encoder.set_state(State::Status);
+            Self {
+                decoder: PacketCodec::new(PacketDirection::ServerBound),
+                encoder,
+                received: Vec::new(),
+                incoming: Vec::new(),
+                outgoing: VecDeque::new(),
+                respond: Box::new(respond),
+            }
+        }
+    }
+
+    impl<P: HasStatusPackets> Write for FakeServer<P> {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.incoming.extend_from_slice(buf);
+            while let Some((consumed, packet)) = self.decoder.decode(self.incoming.as_mut_slice()).expect("valid packet") {
+                self.incoming.drain(..consumed);
+                for response in (self.respond)(&packet) {
+                    let mut frame = Vec::new();
+                    self.encoder.encode(&response, &mut frame).expect("encodes");
+                    self.outgoing.extend(frame);
+                }
+                self.received.push(packet);
+            }
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<P> Read for FakeServer<P> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = buf.len().min(self.outgoing.len());
+            for (to, from) in buf.iter_mut().zip(self.outgoing.drain(..n)) {
+                *to = from;
+            }
+            Ok(n)
+        }
+    }
+
+    fn status() -> StatusSpec {
+        StatusSpec::builder()
+            .version("1.16.3", 753)
+            .players(1, 20)
+            .description(Chat::from_text("hello"))
+            .build()
+    }
+
+    fn vanilla_server(packet: &Packet753) -> Vec<Packet753> {
+        match packet {
+            Packet753::StatusRequest(_) => vec![Packet753::StatusResponse(StatusResponseSpec { response: status() })],
+            Packet753::StatusPing(body) => vec![Packet753::StatusPong(StatusPongSpec { payload: body.payload })],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_ping() {
+        let mut server = FakeServer::new(vanilla_server);
+        let response = ping::<Packet753, _>(&mut server, "localhost", 25565).expect("ping works");
+        assert_eq!(response.status, status());
+        assert!(response.latency < Duration::from_secs(5));
+
+        assert_eq!(server.received.len(), 3);
+        assert_eq!(server.received[0], Packet753::Handshake(HandshakeSpec {
+            version: crate::types::VarInt(753),
+            server_address: "localhost".to_owned(),
+            server_port: 25565,
+            next_state: HandshakeNextState::Status,
+        }));
+        assert_eq!(server.received[1], Packet753::StatusRequest(StatusRequestSpec));
+        assert!(matches!(server.received[2], Packet753::StatusPing(_)));
+    }
+
+    #[cfg(feature = "v1_8_9")]
+    #[test]
+    fn test_ping_1_8() {
+        use crate::v1_8_9::{Packet47, StatusPongSpec, StatusResponseSpec};
+
+        let mut server = FakeServer::new(|packet: &Packet47| match packet {
+            Packet47::StatusRequest(_) => vec![Packet47::StatusResponse(StatusResponseSpec { response: status() })],
+            Packet47::StatusPing(body) => vec![Packet47::StatusPong(StatusPongSpec { payload: body.payload })],
+            _ => vec![],
+        });
+        let response = ping::<Packet47, _>(&mut server, "localhost", 25565).expect("ping works");
+        assert_eq!(response.status, status());
+        assert!(matches!(&server.received[0], Packet47::Handshake(body) if body.version.0 == 47));
+    }
+
+    #[test]
+    fn test_pong_payload_mismatch() {
+        let mut server = FakeServer::new(|packet: &Packet753| match packet {
+            Packet753::StatusPing(body) => vec![Packet753::StatusPong(StatusPongSpec { payload: body.payload + 1 })],
+            other => vanilla_server(other),
+        });
+        assert!(matches!(
+            ping::<Packet753, _>(&mut server, "localhost", 25565),
+            Err(PingErr::PayloadMismatch { sent, received }) if received == sent + 1
+        ));
+    }
+
+    #[test]
+    fn test_unexpected_packet() {
+        let mut server = FakeServer::new(|packet: &Packet753| match packet {
+            Packet753::StatusRequest(_) => vec![Packet753::StatusPong(StatusPongSpec { payload: 0 })],
+            _ => vec![],
+        });
+        assert!(matches!(
+            ping::<Packet753, _>(&mut server, "localhost", 25565),
+            Err(PingErr::UnexpectedPacket(id)) if id.id == 0x01 && id.state == State::Status
+        ));
+    }
+
+    #[test]
+    fn test_server_closes() {
+        let mut server = FakeServer::new(|packet: &Packet753| match packet {
+            Packet753::StatusRequest(_) => vanilla_server(packet),
+            _ => vec![],
+        });
+        assert!(matches!(
+            ping::<Packet753, _>(&mut server, "localhost", 25565),
+            Err(PingErr::Codec(CodecErr::Io(err))) if err.kind() == io::ErrorKind::UnexpectedEof
+        ));
+    }
+}
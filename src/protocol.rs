@@ -1,4 +1,5 @@
 use crate::{Deserialize, DeserializeErr, Serialize, Serializer, SerializeResult};
+use crate::limits::DeserializeLimits;
 use alloc::{string::String, fmt, vec::Vec, borrow::ToOwned};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -87,6 +88,13 @@ pub struct ProtocolPacketField {
     pub kind: String,
 }
 
+/// The fields of a packet body, as `(name, type)`, which `describe()` reports for every packet
+/// using the body. `proto_struct!` implements this; hand-written bodies list their fields in the
+/// order they are sent.
+pub trait BodyFields {
+    const FIELDS: &'static [(&'static str, &'static str)];
+}
+
 pub trait HasPacketKind {
     type Kind: PacketKind;
 
@@ -114,6 +122,23 @@ pub trait HasPacketId {
 pub trait HasPacketBody {
 
     fn mc_serialize_body<S>(&self, to: &mut S) -> SerializeResult where S: Serializer;
+
+    /// The exact number of bytes `mc_serialize_body` writes, see `Serialize::serialized_len`.
+    fn serialized_body_len(&self) -> usize {
+        let mut counter = crate::LenSerializer::default();
+        let _ = self.mc_serialize_body(&mut counter);
+        counter.len()
+    }
+}
+
+pub trait HasCompressionThreshold {
+
+    fn compression_threshold(&self) -> Option<i32>;
+}
+
+pub trait HasNextState {
+
+    fn next_state(&self) -> Option<State>;
 }
 
 pub trait RawPacket<'a>: HasPacketId + Sized {
@@ -125,9 +150,19 @@ pub trait RawPacket<'a>: HasPacketId + Sized {
     fn data(&self) -> &'a [u8];
 
     fn deserialize(&self) -> Result<Self::Packet, PacketErr>;
+
+    fn deserialize_with_limits(&self, limits: &DeserializeLimits) -> Result<Self::Packet, PacketErr>;
 }
 
-pub trait Packet: HasPacketId + HasPacketBody + Sized {}
+pub trait Packet: HasPacketId + HasPacketBody + Sized {
+
+    /// Decodes a packet body under the limits of `crate::limits::current`, which are the vanilla
+    /// ones unless called inside `DeserializeLimits::scope`.
+    fn mc_deserialize_body(id: Id, data: &[u8]) -> Result<Self, PacketErr>;
+
+    /// Decodes a packet body under `limits`, including the limits on specific fields like usernames.
+    fn mc_deserialize_body_with_limits(id: Id, data: &[u8], limits: &DeserializeLimits) -> Result<Self, PacketErr>;
+}
 
 pub enum PacketErr {
     UnknownId(Id),
@@ -194,6 +229,10 @@ macro_rules! proto_struct {
             }
         }
 
+        impl $crate::protocol::BodyFields for $bodyt {
+            const FIELDS: &'static [(&'static str, &'static str)] = &[];
+        }
+
         #[cfg(all(test, feature = "std"))]
         impl TestRandom for $bodyt {
             fn test_gen_random() -> Self {
@@ -218,16 +257,26 @@ macro_rules! proto_struct {
                 )+
                 Ok(())
             }
+
+            fn serialized_len(&self) -> usize {
+                0 $(+ self.$fname.serialized_len())+
+            }
         }
 
         impl$(<$($g),*>)? Deserialize for $bodyt$(<$($g),*> where $($g: Deserialize + alloc::fmt::Debug + Clone + PartialEq),*)? {
-            fn mc_deserialize(_rest: &[u8]) -> DeserializeResult<'_, Self> {
-                $(let Deserialized{ value: $fname, data: _rest } = <$ftyp>::mc_deserialize(_rest)?;)+
+            fn mc_deserialize(start: &[u8]) -> DeserializeResult<'_, Self> {
+                let _rest = start;
+                $(let Deserialized{ value: $fname, data: _rest } = <$ftyp>::mc_deserialize(_rest)
+                    .map_err(|err| err.in_field(stringify!($fname), start.len() - _rest.len()))?;)+
 
                 Deserialized::ok(Self{ $($fname),+ }, _rest)
             }
         }
 
+        impl$(<$($g),*>)? $crate::protocol::BodyFields for $bodyt$(<$($g),*> where $($g: alloc::fmt::Debug + Clone + PartialEq),*)? {
+            const FIELDS: &'static [(&'static str, &'static str)] = &[$((stringify!($fname), stringify!($ftyp))),+];
+        }
+
         #[allow(unused_parens)]
         impl$(<$($g),*>)? From<($($ftyp),+)> for $bodyt$(<$($g),*>)? $(where $($g: alloc::fmt::Debug + Clone + PartialEq),*)? {
             fn from(other: ($($ftyp),+)) -> Self {
@@ -252,11 +301,49 @@ macro_rules! proto_struct {
     }
 }
 
+// like proto_struct!, but for structs borrowing from the data they are decoded from
+#[macro_export]
+macro_rules! proto_borrowed_struct {
+    ($bodyt: ident<$lt: lifetime> {
+        $($fname: ident: $ftyp: ty ),+
+    }) => {
+        $crate::as_item! {
+            #[derive(Debug, Clone, PartialEq)]
+            pub struct $bodyt<$lt> {
+               $(pub $fname: $ftyp),+
+            }
+        }
+
+        impl<$lt> Serialize for $bodyt<$lt> {
+            fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+                $(
+                    to.serialize_other(&self.$fname)?;
+                )+
+                Ok(())
+            }
+
+            fn serialized_len(&self) -> usize {
+                0 $(+ self.$fname.serialized_len())+
+            }
+        }
+
+        impl<$lt> $crate::BorrowDeserialize<$lt> for $bodyt<$lt> {
+            fn mc_deserialize_borrowed(start: &$lt [u8]) -> DeserializeResult<$lt, Self> {
+                let _rest = start;
+                $(let Deserialized{ value: $fname, data: _rest } = <$ftyp as $crate::BorrowDeserialize<$lt>>::mc_deserialize_borrowed(_rest)
+                    .map_err(|err| err.in_field(stringify!($fname), start.len() - _rest.len()))?;)+
+
+                Deserialized::ok(Self{ $($fname),+ }, _rest)
+            }
+        }
+    }
+}
+
 #[macro_export]
 macro_rules! define_protocol {
     ($version: literal, $packett: ident, $rawpackett: ident, $rawdt: ident, $kindt: ident => {
-        $($nam: ident, $id: literal, $state: ident, $direction: ident => $body: ident {
-            $($fnam: ident: $ftyp: ty),* }),*
+        $($nam: ident, $id: literal, $state: ident, $direction: ident => $body: ident $({
+            $($fnam: ident: $ftyp: ty),* })?),*
         }
     ) => {
         $crate::as_item! {
@@ -309,7 +396,21 @@ macro_rules! define_protocol {
             }
         }
 
-        impl crate::protocol::Packet for $packett {}
+        impl crate::protocol::Packet for $packett {
+            fn mc_deserialize_body(id: crate::protocol::Id, data: &[u8]) -> Result<Self, crate::protocol::PacketErr> {
+                use crate::protocol::RawPacket;
+                $rawpackett::create(id, data)?.deserialize()
+            }
+
+            fn mc_deserialize_body_with_limits(
+                id: crate::protocol::Id,
+                data: &[u8],
+                limits: &crate::limits::DeserializeLimits,
+            ) -> Result<Self, crate::protocol::PacketErr> {
+                use crate::protocol::RawPacket;
+                $rawpackett::create(id, data)?.deserialize_with_limits(limits)
+            }
+        }
 
         impl $packett {
             pub fn describe() -> crate::protocol::ProtocolSpec {
@@ -322,12 +423,13 @@ macro_rules! define_protocol {
                             id: $id,
                             name: stringify!($nam).to_owned(),
                             body_struct: stringify!($body).to_owned(),
-                            fields: alloc::vec!(
-                                $(crate::protocol::ProtocolPacketField{
-                                    name: stringify!($fnam).to_owned(),
-                                    kind: stringify!($ftyp).to_owned(),
-                                }),*
-                            )
+                            // also covers packets reusing a body declared elsewhere
+                            fields: <$body as crate::protocol::BodyFields>::FIELDS.iter()
+                                .map(move |(name, kind)| crate::protocol::ProtocolPacketField {
+                                    name: (*name).to_owned(),
+                                    kind: (*kind).to_owned(),
+                                })
+                                .collect(),
                         }),*,
                     )
                 }
@@ -378,11 +480,12 @@ macro_rules! define_protocol {
             fn deserialize(&self) -> Result<Self::Packet, crate::protocol::PacketErr> {
                 use crate::protocol::PacketErr::{ExtraData, DeserializeFailed};
 
+                crate::limits::current().check_packet_bytes(self.data().len()).map_err(DeserializeFailed)?;
                 match self {
                     $($rawpackett::$nam(bod) => {
                         let Deserialized { value: body, data: rest } =
                             $body::mc_deserialize(bod.data)
-                                .map_err(move |err| DeserializeFailed(err))?;
+                                .map_err(move |err| DeserializeFailed(err.in_field(stringify!($nam), 0)))?;
                         if !rest.is_empty() {
                             Err(ExtraData(rest.to_vec()))
                         } else {
@@ -391,11 +494,24 @@ macro_rules! define_protocol {
                     }),*,
                 }
             }
+
+            fn deserialize_with_limits(
+                &self,
+                limits: &crate::limits::DeserializeLimits,
+            ) -> Result<Self::Packet, crate::protocol::PacketErr> {
+                use crate::limits::HasFieldLimits;
+
+                limits.check_packet_bytes(self.data().len()).map_err(crate::protocol::PacketErr::DeserializeFailed)?;
+                let packet = limits.scope(move || self.deserialize())?;
+                packet.check_field_limits(limits).map_err(crate::protocol::PacketErr::DeserializeFailed)?;
+                Ok(packet)
+            }
         }
 
         #[derive(PartialEq, Debug)]
         pub struct $rawdt<'a, T> {
             pub data: &'a [u8],
+            name: &'static str,
             _typ: core::marker::PhantomData<T>
         }
 
@@ -403,6 +519,7 @@ macro_rules! define_protocol {
             pub fn deserialize(&self) -> Result<T, crate::protocol::PacketErr> {
                 use crate::protocol::PacketErr::*;
 
+                crate::limits::current().check_packet_bytes(self.data.len()).map_err(DeserializeFailed)?;
                 let Deserialized { value: body, data: rest } = T::mc_deserialize(self.data).map_err(DeserializeFailed)?;
                 if !rest.is_empty() {
                     Err(ExtraData(rest.to_vec()))
@@ -410,6 +527,21 @@ macro_rules! define_protocol {
                     Ok(body)
                 }
             }
+
+            /// Decodes the body as `B`, usually one of the borrowed packet types like
+            /// `PlayClientChatMessageRef`, which point into `data` instead of copying out of it.
+            pub fn deserialize_borrowed<B>(&self) -> Result<B, crate::protocol::PacketErr> where B: crate::BorrowDeserialize<'a> {
+                use crate::protocol::PacketErr::*;
+
+                crate::limits::current().check_packet_bytes(self.data.len()).map_err(DeserializeFailed)?;
+                let Deserialized { value: body, data: rest } = B::mc_deserialize_borrowed(self.data)
+                    .map_err(|err| DeserializeFailed(err.in_field(self.name, 0)))?;
+                if !rest.is_empty() {
+                    Err(ExtraData(rest.to_vec()))
+                } else {
+                    Ok(body)
+                }
+            }
         }
 
         impl crate::protocol::HasPacketId for $kindt {
@@ -457,13 +589,15 @@ macro_rules! define_protocol {
                 match self {
                     $($kindt::$nam => $rawpackett::$nam($rawdt{
                         data,
+                        name: stringify!($nam),
                         _typ: core::marker::PhantomData,
                     })),*,
                 }
             }
         }
 
-        $($crate::proto_struct!($body { $($fnam: $ftyp),* });)*
+        // packets declared without fields reuse a body type that is already in scope
+        $($($crate::proto_struct!($body { $($fnam: $ftyp),* });)?)*
     };
 }
 
@@ -477,7 +611,9 @@ macro_rules! strip_plus {
 #[macro_export]
 macro_rules! proto_enum_deserialize_variant {
     ($data: ident, $ty: ident :: $nam: ident ($bod: ty)) => {
-        Ok(<$bod>::mc_deserialize($data)?.map(move |body| $ty::$nam(body)))
+        Ok(<$bod>::mc_deserialize($data)
+            .map_err(move |err| err.in_field(stringify!($nam), 0))?
+            .map(move |body| $ty::$nam(body)))
     };
     ($data: ident, $ty: ident :: $nam: ident) => {
         Deserialized::ok($ty::$nam, $data)
@@ -514,7 +650,7 @@ macro_rules! proto_enum_with_type {
         impl Deserialize for $typname {
             fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
                 <$typ>::mc_deserialize(data)?.and_then(move |id, rest| {
-                    Self::deserialize_with_id(id, rest)
+                    Self::deserialize_with_id(id, rest).map_err(move |err| err.offset_by(data.len() - rest.len()))
                 })
             }
         }
@@ -617,7 +753,7 @@ macro_rules! proto_str_enum {
         impl Deserialize for $typname {
             fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
                 String::mc_deserialize(data)?.and_then(move |name, rest| {
-                    Self::deserialize_with_id(name.as_str(), rest)
+                    Self::deserialize_with_id(name.as_str(), rest).map_err(move |err| err.offset_by(data.len() - rest.len()))
                 })
             }
         }
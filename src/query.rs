@@ -0,0 +1,489 @@
+// The GameSpy4 based query protocol servers answer over UDP when enable-query is set. Every request
+// starts with the magic 0xFE 0xFD, a type byte and a session id, and every response echoes the type
+// and session id. Strings are null terminated, numbers in the stat responses are sent as decimal
+// strings, except for the host port of the basic stat which is a little endian short.
+//
+// A stat request has to carry the challenge token the server handed out in the handshake, and is a
+// full stat if the token is followed by 4 bytes of padding.
+use crate::byte_order::{ByteOrder, LittleEndian};
+use crate::utils::take;
+use crate::{
+    Deserialize, DeserializeErr, DeserializeResult, Deserialized, Serialize, SerializeErr,
+    SerializeResult, Serializer,
+};
+use alloc::{format, string::{String, ToString}, vec::Vec};
+
+pub const QUERY_MAGIC: [u8; 2] = [0xFE, 0xFD];
+pub const QUERY_TYPE_HANDSHAKE: u8 = 0x09;
+pub const QUERY_TYPE_STAT: u8 = 0x00;
+// vanilla servers ignore the high bits of each byte of the session id
+pub const SESSION_ID_MASK: i32 = 0x0F0F0F0F;
+
+const FULL_STAT_PADDING: [u8; 4] = [0x00; 4];
+const FULL_STAT_VALUES_PREFIX: &[u8] = b"splitnum\x00\x80\x00";
+const FULL_STAT_PLAYERS_PREFIX: &[u8] = b"\x01player_\x00\x00";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QueryRequestKind {
+    Handshake,
+    BasicStat { challenge_token: i32 },
+    FullStat { challenge_token: i32 },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QueryRequest {
+    pub session_id: i32,
+    pub kind: QueryRequestKind,
+}
+
+impl QueryRequest {
+    /// Masks `session_id` the way vanilla servers do, so it matches the id echoed in the response.
+    pub fn new(session_id: i32, kind: QueryRequestKind) -> Self {
+        Self {
+            session_id: session_id & SESSION_ID_MASK,
+            kind,
+        }
+    }
+}
+
+impl Serialize for QueryRequest {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        use QueryRequestKind::*;
+
+        to.serialize_bytes(&QUERY_MAGIC)?;
+        match self.kind {
+            Handshake => {
+                to.serialize_byte(QUERY_TYPE_HANDSHAKE)?;
+                to.serialize_other(&self.session_id)
+            }
+            BasicStat { challenge_token } => {
+                to.serialize_byte(QUERY_TYPE_STAT)?;
+                to.serialize_other(&self.session_id)?;
+                to.serialize_other(&challenge_token)
+            }
+            FullStat { challenge_token } => {
+                to.serialize_byte(QUERY_TYPE_STAT)?;
+                to.serialize_other(&self.session_id)?;
+                to.serialize_other(&challenge_token)?;
+                to.serialize_bytes(&FULL_STAT_PADDING)
+            }
+        }
+    }
+}
+
+// Requests are whole datagrams, so anything after a basic stat request other than the full stat
+// padding is an error rather than left over data.
+impl Deserialize for QueryRequest {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: magic, data } = take(2, data)?;
+        if magic != QUERY_MAGIC {
+            return Err(DeserializeErr::CannotUnderstandValue(format!(
+                "bad query magic {:02X}{:02X}",
+                magic[0], magic[1]
+            )));
+        }
+
+        let Deserialized { value: kind, data } = u8::mc_deserialize(data)?;
+        let Deserialized { value: session_id, data } = i32::mc_deserialize(data)?;
+        let Deserialized { value: kind, data } = match kind {
+            QUERY_TYPE_HANDSHAKE => Deserialized::ok(QueryRequestKind::Handshake, data)?,
+            QUERY_TYPE_STAT => {
+                let Deserialized { value: challenge_token, data } = i32::mc_deserialize(data)?;
+                match data.len() {
+                    0 => Deserialized::ok(QueryRequestKind::BasicStat { challenge_token }, data)?,
+                    4 => Deserialized::ok(QueryRequestKind::FullStat { challenge_token }, &data[4..])?,
+                    other => {
+                        return Err(DeserializeErr::CannotUnderstandValue(format!(
+                            "bad query stat request padding of {} bytes",
+                            other
+                        )))
+                    }
+                }
+            }
+            other => {
+                return Err(DeserializeErr::CannotUnderstandValue(format!(
+                    "unknown query request type 0x{:02X}",
+                    other
+                )))
+            }
+        };
+
+        Deserialized::ok(Self { session_id, kind }, data)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryBasicStatSpec {
+    pub motd: String,
+    pub game_type: String,
+    pub map: String,
+    pub num_players: i32,
+    pub max_players: i32,
+    pub host_port: u16,
+    pub host_ip: String,
+}
+
+impl Serialize for QueryBasicStatSpec {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        serialize_cstr(self.motd.as_str(), to)?;
+        serialize_cstr(self.game_type.as_str(), to)?;
+        serialize_cstr(self.map.as_str(), to)?;
+        serialize_cstr(self.num_players.to_string().as_str(), to)?;
+        serialize_cstr(self.max_players.to_string().as_str(), to)?;
+        to.serialize_bytes(&LittleEndian::write_ushort(self.host_port))?;
+        serialize_cstr(self.host_ip.as_str(), to)
+    }
+}
+
+impl Deserialize for QueryBasicStatSpec {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: motd, data } = deserialize_cstr(data)?;
+        let Deserialized { value: game_type, data } = deserialize_cstr(data)?;
+        let Deserialized { value: map, data } = deserialize_cstr(data)?;
+        let Deserialized { value: num_players, data } = deserialize_cstr(data)?;
+        let Deserialized { value: max_players, data } = deserialize_cstr(data)?;
+        let Deserialized { value: host_port, data } = LittleEndian::read_ushort(data)?;
+        let Deserialized { value: host_ip, data } = deserialize_cstr(data)?;
+
+        Deserialized::ok(Self {
+            motd,
+            game_type,
+            map,
+            num_players: parse_number("numplayers", num_players.as_str())?,
+            max_players: parse_number("maxplayers", max_players.as_str())?,
+            host_port,
+            host_ip,
+        }, data)
+    }
+}
+
+// The key/value section keeps the order the server sent, vanilla sends hostname, gametype,
+// game_id, version, plugins, map, numplayers, maxplayers, hostport and hostip.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct QueryFullStatSpec {
+    pub values: Vec<(String, String)>,
+    pub players: Vec<String>,
+}
+
+impl QueryFullStatSpec {
+    pub fn value(&self, key: &str) -> Option<&str> {
+        self.values.iter()
+            .find(move |(k, _)| k == key)
+            .map(move |(_, v)| v.as_str())
+    }
+
+    pub fn motd(&self) -> Option<&str> {
+        self.value("hostname")
+    }
+
+    pub fn version(&self) -> Option<&str> {
+        self.value("version")
+    }
+
+    pub fn num_players(&self) -> Option<i32> {
+        self.value("numplayers")?.parse().ok()
+    }
+
+    pub fn max_players(&self) -> Option<i32> {
+        self.value("maxplayers")?.parse().ok()
+    }
+
+    pub fn host_port(&self) -> Option<u16> {
+        self.value("hostport")?.parse().ok()
+    }
+}
+
+impl Serialize for QueryFullStatSpec {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        to.serialize_bytes(FULL_STAT_VALUES_PREFIX)?;
+        for (key, value) in &self.values {
+            if key.is_empty() {
+                return Err(SerializeErr::CannotSerialize("query full stat key cannot be empty".to_string()));
+            }
+            serialize_cstr(key.as_str(), to)?;
+            serialize_cstr(value.as_str(), to)?;
+        }
+        to.serialize_byte(0)?;
+
+        to.serialize_bytes(FULL_STAT_PLAYERS_PREFIX)?;
+        for player in &self.players {
+            if player.is_empty() {
+                return Err(SerializeErr::CannotSerialize("query player name cannot be empty".to_string()));
+            }
+            serialize_cstr(player.as_str(), to)?;
+        }
+        to.serialize_byte(0)
+    }
+}
+
+impl Deserialize for QueryFullStatSpec {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let mut data = expect_prefix(FULL_STAT_VALUES_PREFIX, data)?;
+        let mut values = Vec::new();
+        loop {
+            let Deserialized { value: key, data: rest } = deserialize_cstr(data)?;
+            if key.is_empty() {
+                data = rest;
+                break;
+            }
+            let Deserialized { value, data: rest } = deserialize_cstr(rest)?;
+            data = rest;
+            values.push((key, value));
+        }
+
+        let mut data = expect_prefix(FULL_STAT_PLAYERS_PREFIX, data)?;
+        let mut players = Vec::new();
+        loop {
+            let Deserialized { value: player, data: rest } = deserialize_cstr(data)?;
+            data = rest;
+            if player.is_empty() {
+                break;
+            }
+            players.push(player);
+        }
+
+        Deserialized::ok(Self { values, players }, data)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum QueryResponseKind {
+    Handshake { challenge_token: i32 },
+    BasicStat(QueryBasicStatSpec),
+    FullStat(QueryFullStatSpec),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryResponse {
+    pub session_id: i32,
+    pub kind: QueryResponseKind,
+}
+
+impl Serialize for QueryResponse {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        use QueryResponseKind::*;
+
+        match &self.kind {
+            Handshake { challenge_token } => {
+                to.serialize_byte(QUERY_TYPE_HANDSHAKE)?;
+                to.serialize_other(&self.session_id)?;
+                serialize_cstr(challenge_token.to_string().as_str(), to)
+            }
+            BasicStat(body) => {
+                to.serialize_byte(QUERY_TYPE_STAT)?;
+                to.serialize_other(&self.session_id)?;
+                to.serialize_other(body)
+            }
+            FullStat(body) => {
+                to.serialize_byte(QUERY_TYPE_STAT)?;
+                to.serialize_other(&self.session_id)?;
+                to.serialize_other(body)
+            }
+        }
+    }
+}
+
+// Basic and full stat responses share a type byte, they are told apart by the padding the full
+// stat starts with.
+impl Deserialize for QueryResponse {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: kind, data } = u8::mc_deserialize(data)?;
+        let Deserialized { value: session_id, data } = i32::mc_deserialize(data)?;
+        let Deserialized { value: kind, data } = match kind {
+            QUERY_TYPE_HANDSHAKE => {
+                let Deserialized { value: token, data } = deserialize_cstr(data)?;
+                let challenge_token = parse_number("challenge token", token.as_str())?;
+                Deserialized::ok(QueryResponseKind::Handshake { challenge_token }, data)?
+            }
+            QUERY_TYPE_STAT => {
+                if data.starts_with(FULL_STAT_VALUES_PREFIX) {
+                    QueryFullStatSpec::mc_deserialize(data)?.map(QueryResponseKind::FullStat)
+                } else {
+                    QueryBasicStatSpec::mc_deserialize(data)?.map(QueryResponseKind::BasicStat)
+                }
+            }
+            other => {
+                return Err(DeserializeErr::CannotUnderstandValue(format!(
+                    "unknown query response type 0x{:02X}",
+                    other
+                )))
+            }
+        };
+
+        Deserialized::ok(Self { session_id, kind }, data)
+    }
+}
+
+fn expect_prefix<'a>(prefix: &[u8], data: &'a [u8]) -> Result<&'a [u8], DeserializeErr> {
+    let Deserialized { value, data } = take(prefix.len(), data)?;
+    if value != prefix {
+        return Err(DeserializeErr::CannotUnderstandValue(format!(
+            "bad query full stat padding {:?}",
+            value
+        )));
+    }
+
+    Ok(data)
+}
+
+fn parse_number<T: core::str::FromStr>(field: &str, value: &str) -> Result<T, DeserializeErr> {
+    value.parse().map_err(move |_| {
+        DeserializeErr::CannotUnderstandValue(format!("bad query {} {:?}", field, value))
+    })
+}
+
+fn serialize_cstr<S: Serializer>(value: &str, to: &mut S) -> SerializeResult {
+    if value.contains('\0') {
+        return Err(SerializeErr::CannotSerialize(format!(
+            "query string {:?} cannot contain \\0",
+            value
+        )));
+    }
+
+    to.serialize_bytes(value.as_bytes())?;
+    to.serialize_byte(0)
+}
+
+fn deserialize_cstr(data: &[u8]) -> DeserializeResult<'_, String> {
+    let end = data.iter().position(move |b| *b == 0).ok_or(DeserializeErr::Eof)?;
+    let value = String::from_utf8(data[..end].to_vec()).map_err(DeserializeErr::BadStringEncoding)?;
+    Deserialized::ok(value, &data[end + 1..])
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::types::BytesSerializer;
+    use alloc::{borrow::ToOwned, vec, vec::Vec};
+
+    fn serialize<S: Serialize>(value: &S) -> Vec<u8> {
+        let mut out = BytesSerializer::default();
+        value.mc_serialize(&mut out).expect("serializes");
+        out.into_bytes()
+    }
+
+    fn check_fixture<T: Serialize + Deserialize + PartialEq + core::fmt::Debug>(bytes: &[u8], expected: T) {
+        let Deserialized { value, data } = T::mc_deserialize(bytes).expect("deserializes");
+        assert!(data.is_empty());
+        assert_eq!(value, expected);
+        assert_eq!(serialize(&value), bytes);
+    }
+
+    #[test]
+    fn test_requests() {
+        check_fixture(&[0xFE, 0xFD, 0x09, 0x00, 0x00, 0x00, 0x01], QueryRequest {
+            session_id: 1,
+            kind: QueryRequestKind::Handshake,
+        });
+        check_fixture(&[0xFE, 0xFD, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x91, 0x29, 0x9B], QueryRequest {
+            session_id: 1,
+            kind: QueryRequestKind::BasicStat { challenge_token: 9513371 },
+        });
+        check_fixture(&[0xFE, 0xFD, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x91, 0x29, 0x9B, 0x00, 0x00, 0x00, 0x00], QueryRequest {
+            session_id: 1,
+            kind: QueryRequestKind::FullStat { challenge_token: 9513371 },
+        });
+    }
+
+    #[test]
+    fn test_request_masks_session_id() {
+        let request = QueryRequest::new(0x7FFF_FFFF, QueryRequestKind::Handshake);
+        assert_eq!(request.session_id, 0x0F0F_0F0F);
+        assert_eq!(serialize(&request), &[0xFE, 0xFD, 0x09, 0x0F, 0x0F, 0x0F, 0x0F]);
+        assert_eq!(QueryRequest::new(-1, QueryRequestKind::Handshake).session_id, SESSION_ID_MASK);
+        assert_eq!(QueryRequest::new(0x0102_0304, QueryRequestKind::Handshake).session_id, 0x0102_0304);
+    }
+
+    #[test]
+    fn test_bad_requests() {
+        assert!(QueryRequest::mc_deserialize(&[0xFE, 0xFC, 0x09, 0x00, 0x00, 0x00, 0x01]).is_err());
+        assert!(QueryRequest::mc_deserialize(&[0xFE, 0xFD, 0x05, 0x00, 0x00, 0x00, 0x01]).is_err());
+        assert!(QueryRequest::mc_deserialize(&[0xFE, 0xFD, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x91, 0x29, 0x9B, 0x00]).is_err());
+        assert!(matches!(QueryRequest::mc_deserialize(&[0xFE, 0xFD, 0x00, 0x00, 0x00, 0x00, 0x01]), Err(DeserializeErr::Eof)));
+    }
+
+    #[test]
+    fn test_handshake_response() {
+        let mut bytes = vec![0x09, 0x00, 0x00, 0x00, 0x01];
+        bytes.extend_from_slice(b"9513307\x00");
+        check_fixture(&bytes, QueryResponse {
+            session_id: 1,
+            kind: QueryResponseKind::Handshake { challenge_token: 9513307 },
+        });
+
+        let mut negative = vec![0x09, 0x00, 0x00, 0x00, 0x01];
+        negative.extend_from_slice(b"-42\x00");
+        assert_eq!(QueryResponse::mc_deserialize(&negative).unwrap().value.kind, QueryResponseKind::Handshake { challenge_token: -42 });
+    }
+
+    #[test]
+    fn test_basic_stat_response() {
+        let mut bytes = vec![0x00, 0x00, 0x00, 0x00, 0x01];
+        bytes.extend_from_slice(b"A Minecraft Server\x00SMP\x00world\x002\x0020\x00\xDD\x63127.0.0.1\x00");
+        check_fixture(&bytes, QueryResponse {
+            session_id: 1,
+            kind: QueryResponseKind::BasicStat(QueryBasicStatSpec {
+                motd: "A Minecraft Server".to_owned(),
+                game_type: "SMP".to_owned(),
+                map: "world".to_owned(),
+                num_players: 2,
+                max_players: 20,
+                host_port: 25565,
+                host_ip: "127.0.0.1".to_owned(),
+            }),
+        });
+    }
+
+    #[test]
+    fn test_full_stat_response() {
+        let mut bytes = vec![0x00, 0x00, 0x00, 0x00, 0x01];
+        bytes.extend_from_slice(b"splitnum\x00\x80\x00");
+        bytes.extend_from_slice(b"hostname\x00A Minecraft Server\x00gametype\x00SMP\x00game_id\x00MINECRAFT\x00");
+        bytes.extend_from_slice(b"version\x001.16.5\x00plugins\x00\x00map\x00world\x00numplayers\x002\x00");
+        bytes.extend_from_slice(b"maxplayers\x0020\x00hostport\x0025565\x00hostip\x00127.0.0.1\x00\x00");
+        bytes.extend_from_slice(b"\x01player_\x00\x00");
+        bytes.extend_from_slice(b"barneygale\x00Vivalahelvig\x00\x00");
+
+        let stat = QueryFullStatSpec {
+            values: [
+                ("hostname", "A Minecraft Server"),
+                ("gametype", "SMP"),
+                ("game_id", "MINECRAFT"),
+                ("version", "1.16.5"),
+                ("plugins", ""),
+                ("map", "world"),
+                ("numplayers", "2"),
+                ("maxplayers", "20"),
+                ("hostport", "25565"),
+                ("hostip", "127.0.0.1"),
+            ].iter().map(|(k, v)| ((*k).to_owned(), (*v).to_owned())).collect(),
+            players: vec!["barneygale".to_owned(), "Vivalahelvig".to_owned()],
+        };
+        assert_eq!(stat.motd(), Some("A Minecraft Server"));
+        assert_eq!(stat.version(), Some("1.16.5"));
+        assert_eq!(stat.num_players(), Some(2));
+        assert_eq!(stat.max_players(), Some(20));
+        assert_eq!(stat.host_port(), Some(25565));
+        assert_eq!(stat.value("plugins"), Some(""));
+        assert_eq!(stat.value("missing"), None);
+
+        check_fixture(&bytes, QueryResponse {
+            session_id: 1,
+            kind: QueryResponseKind::FullStat(stat),
+        });
+    }
+
+    #[test]
+    fn test_bad_responses() {
+        let mut bad_count = vec![0x00, 0x00, 0x00, 0x00, 0x01];
+        bad_count.extend_from_slice(b"motd\x00SMP\x00world\x00two\x0020\x00\xDD\x63127.0.0.1\x00");
+        assert!(matches!(QueryResponse::mc_deserialize(&bad_count), Err(DeserializeErr::CannotUnderstandValue(_))));
+
+        let mut unterminated = vec![0x09, 0x00, 0x00, 0x00, 0x01];
+        unterminated.extend_from_slice(b"9513307");
+        assert!(matches!(QueryResponse::mc_deserialize(&unterminated), Err(DeserializeErr::Eof)));
+
+        let empty_player = QueryFullStatSpec { values: vec![], players: vec![String::new()] };
+        assert!(matches!(empty_player.mc_serialize(&mut BytesSerializer::default()), Err(SerializeErr::CannotSerialize(_))));
+    }
+}
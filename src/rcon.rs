@@ -0,0 +1,549 @@
+// The source RCON protocol servers speak over TCP when enable-rcon is set. Unlike the game
+// protocol everything is little endian:
+//
+//   i32 length (of everything after it), i32 request id, i32 type, payload, 0x00, 0x00
+//
+// Type 2 means a command when sent to the server but an auth response when sent by it, so requests
+// and responses are separate types. The server splits long command output into several response
+// packets of at most 4096 payload bytes, which all carry the id of the command.
+use crate::byte_order::{ByteOrder, LittleEndian};
+use crate::utils::take;
+use crate::{
+    Deserialize, DeserializeErr, DeserializeResult, Deserialized, Serialize, SerializeErr,
+    SerializeResult, Serializer,
+};
+use alloc::{fmt, format, string::String, vec::Vec};
+
+pub const RCON_TYPE_RESPONSE: i32 = 0;
+pub const RCON_TYPE_COMMAND: i32 = 2;
+pub const RCON_TYPE_AUTH_RESPONSE: i32 = 2;
+pub const RCON_TYPE_LOGIN: i32 = 3;
+// request id the server answers a login with if the password was wrong
+pub const RCON_AUTH_FAILED_ID: i32 = -1;
+// the vanilla server drops requests with a longer payload
+pub const RCON_MAX_REQUEST_PAYLOAD: usize = 1446;
+pub const RCON_MAX_RESPONSE_PAYLOAD: usize = 4096;
+// request id, type and the two trailing nulls
+const RCON_MIN_LENGTH: usize = 10;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RconRequestBody {
+    Login(String),
+    Command(String),
+    // anything else, the vanilla server answers these with an "Unknown request" response
+    Other(i32, String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RconRequest {
+    pub request_id: i32,
+    pub body: RconRequestBody,
+}
+
+impl RconRequest {
+    pub fn login(request_id: i32, password: &str) -> Self {
+        Self { request_id, body: RconRequestBody::Login(password.into()) }
+    }
+
+    pub fn command(request_id: i32, command: &str) -> Self {
+        Self { request_id, body: RconRequestBody::Command(command.into()) }
+    }
+}
+
+impl Serialize for RconRequest {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        use RconRequestBody::*;
+
+        let (kind, payload) = match &self.body {
+            Login(password) => (RCON_TYPE_LOGIN, password),
+            Command(command) => (RCON_TYPE_COMMAND, command),
+            Other(kind, payload) => (*kind, payload),
+        };
+        if payload.len() > RCON_MAX_REQUEST_PAYLOAD {
+            return Err(SerializeErr::CannotSerialize(format!(
+                "rcon request payload of {} bytes exceeds maximum of {} bytes",
+                payload.len(), RCON_MAX_REQUEST_PAYLOAD
+            )));
+        }
+
+        serialize_packet(self.request_id, kind, payload.as_bytes(), to)
+    }
+}
+
+impl Deserialize for RconRequest {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: (request_id, kind, payload), data } = deserialize_packet(data)?;
+        let payload = String::from_utf8(payload.to_vec()).map_err(DeserializeErr::BadStringEncoding)?;
+        let body = match kind {
+            RCON_TYPE_LOGIN => RconRequestBody::Login(payload),
+            RCON_TYPE_COMMAND => RconRequestBody::Command(payload),
+            other => RconRequestBody::Other(other, payload),
+        };
+
+        Deserialized::ok(Self { request_id, body }, data)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RconResponseBody {
+    AuthResponse,
+    // raw bytes, because a fragment can end in the middle of a UTF-8 sequence
+    Response(Vec<u8>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RconResponse {
+    pub request_id: i32,
+    pub body: RconResponseBody,
+}
+
+impl RconResponse {
+    pub fn is_auth_failure(&self) -> bool {
+        self.body == RconResponseBody::AuthResponse && self.request_id == RCON_AUTH_FAILED_ID
+    }
+}
+
+impl Serialize for RconResponse {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        match &self.body {
+            RconResponseBody::AuthResponse => serialize_packet(self.request_id, RCON_TYPE_AUTH_RESPONSE, &[], to),
+            RconResponseBody::Response(payload) => {
+                if payload.len() > RCON_MAX_RESPONSE_PAYLOAD {
+                    return Err(SerializeErr::CannotSerialize(format!(
+                        "rcon response payload of {} bytes exceeds maximum of {} bytes",
+                        payload.len(), RCON_MAX_RESPONSE_PAYLOAD
+                    )));
+                }
+
+                serialize_packet(self.request_id, RCON_TYPE_RESPONSE, payload.as_slice(), to)
+            }
+        }
+    }
+}
+
+impl Deserialize for RconResponse {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: (request_id, kind, payload), data } = deserialize_packet(data)?;
+        let body = match kind {
+            RCON_TYPE_AUTH_RESPONSE => RconResponseBody::AuthResponse,
+            RCON_TYPE_RESPONSE => RconResponseBody::Response(payload.to_vec()),
+            other => {
+                return Err(DeserializeErr::CannotUnderstandValue(format!(
+                    "unknown rcon response type {}",
+                    other
+                )))
+            }
+        };
+
+        Deserialized::ok(Self { request_id, body }, data)
+    }
+}
+
+fn serialize_packet<S: Serializer>(request_id: i32, kind: i32, payload: &[u8], to: &mut S) -> SerializeResult {
+    if payload.contains(&0) {
+        return Err(SerializeErr::CannotSerialize("rcon payload cannot contain \\0".into()));
+    }
+
+    let length = RCON_MIN_LENGTH + payload.len();
+    to.serialize_bytes(&LittleEndian::write_int(length as i32))?;
+    to.serialize_bytes(&LittleEndian::write_int(request_id))?;
+    to.serialize_bytes(&LittleEndian::write_int(kind))?;
+    to.serialize_bytes(payload)?;
+    to.serialize_bytes(&[0, 0])
+}
+
+// Eof if data does not hold the whole packet yet
+fn deserialize_packet(data: &[u8]) -> DeserializeResult<'_, (i32, i32, &[u8])> {
+    let Deserialized { value: length, data } = LittleEndian::read_int(data)?;
+    if length < (RCON_MIN_LENGTH as i32) || length > ((RCON_MIN_LENGTH + RCON_MAX_RESPONSE_PAYLOAD) as i32) {
+        return Err(DeserializeErr::CannotUnderstandValue(format!("bad rcon packet length {}", length)));
+    }
+
+    let Deserialized { value: packet, data: rest } = take(length as usize, data)?;
+    let Deserialized { value: request_id, data: packet } = LittleEndian::read_int(packet)?;
+    let Deserialized { value: kind, data: packet } = LittleEndian::read_int(packet)?;
+    let (payload, padding) = packet.split_at(packet.len() - 2);
+    if padding != [0, 0] {
+        return Err(DeserializeErr::CannotUnderstandValue(format!("bad rcon packet padding {:?}", padding)));
+    }
+
+    Deserialized::ok((request_id, kind, payload), rest)
+}
+
+pub enum RconErr {
+    DeserializeFailed(DeserializeErr),
+    SerializeFailed(SerializeErr),
+    AuthFailed,
+    UnexpectedResponse(i32),
+    BadEncoding,
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+}
+
+impl fmt::Display for RconErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use RconErr::*;
+        match self {
+            DeserializeFailed(err) => f.write_fmt(format_args!("failed to read rcon packet: {:?}", err)),
+            SerializeFailed(err) => f.write_fmt(format_args!("failed to write rcon packet: {:?}", err)),
+            AuthFailed => f.write_str("rcon password was rejected"),
+            UnexpectedResponse(id) => f.write_fmt(format_args!("unexpected rcon response for request {}", id)),
+            BadEncoding => f.write_str("rcon response is not valid UTF-8"),
+            #[cfg(feature = "std")]
+            Io(err) => f.write_fmt(format_args!("io error: {}", err)),
+        }
+    }
+}
+
+impl fmt::Debug for RconErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <dyn fmt::Display>::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RconErr {}
+
+impl From<DeserializeErr> for RconErr {
+    fn from(err: DeserializeErr) -> Self {
+        RconErr::DeserializeFailed(err)
+    }
+}
+
+impl From<SerializeErr> for RconErr {
+    fn from(err: SerializeErr) -> Self {
+        RconErr::SerializeFailed(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for RconErr {
+    fn from(err: std::io::Error) -> Self {
+        RconErr::Io(err)
+    }
+}
+
+pub type RconResult<T> = Result<T, RconErr>;
+
+/// Collects the response packets to one command.
+///
+/// The protocol doesn't mark the last fragment, so clients send a second request with
+/// `sentinel_id` right after the command. The server answers in order, so once the answer to that
+/// request arrives every fragment of the command output has been received.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RconFragments {
+    pub request_id: i32,
+    pub sentinel_id: i32,
+    payload: Vec<u8>,
+}
+
+impl RconFragments {
+    pub fn new(request_id: i32, sentinel_id: i32) -> Self {
+        Self { request_id, sentinel_id, payload: Vec::new() }
+    }
+
+    /// Returns `true` once the sentinel response arrived and the output is complete.
+    pub fn push(&mut self, response: RconResponse) -> RconResult<bool> {
+        match response {
+            RconResponse { request_id, body: RconResponseBody::Response(payload) } if request_id == self.request_id => {
+                self.payload.extend(payload);
+                Ok(false)
+            }
+            RconResponse { request_id, .. } if request_id == self.sentinel_id => Ok(true),
+            RconResponse { request_id, .. } => Err(RconErr::UnexpectedResponse(request_id)),
+        }
+    }
+
+    pub fn into_string(self) -> RconResult<String> {
+        String::from_utf8(self.payload).map_err(move |_| RconErr::BadEncoding)
+    }
+}
+
+#[cfg(feature = "std")]
+pub use client::RconClient;
+
+#[cfg(feature = "std")]
+mod client {
+    use super::*;
+    use crate::types::BytesSerializer;
+    use std::io::{self, Read, Write};
+
+    const READ_CHUNK_SIZE: usize = 4096;
+
+    /// A blocking RCON client over any stream, usually a `TcpStream` to the rcon port.
+    #[derive(Debug)]
+    pub struct RconClient<S> {
+        inner: S,
+        buf: Vec<u8>,
+        next_id: i32,
+    }
+
+    impl<S> RconClient<S> where S: Read + Write {
+        pub fn new(inner: S) -> Self {
+            Self { inner, buf: Vec::new(), next_id: 1 }
+        }
+
+        pub fn login(&mut self, password: &str) -> RconResult<()> {
+            let request_id = self.next_request_id();
+            self.write_request(&RconRequest::login(request_id, password))?;
+            loop {
+                let response = self.read_response()?;
+                if response.is_auth_failure() {
+                    return Err(RconErr::AuthFailed);
+                }
+
+                match response.body {
+                    RconResponseBody::AuthResponse if response.request_id == request_id => return Ok(()),
+                    // some servers send an empty response before the auth response
+                    RconResponseBody::Response(_) if response.request_id == request_id => continue,
+                    _ => return Err(RconErr::UnexpectedResponse(response.request_id)),
+                }
+            }
+        }
+
+        /// Runs `command` and returns its whole output, reassembled from all fragments.
+        pub fn command(&mut self, command: &str) -> RconResult<String> {
+            let request_id = self.next_request_id();
+            let sentinel_id = self.next_request_id();
+            self.write_request(&RconRequest::command(request_id, command))?;
+            self.write_request(&RconRequest {
+                request_id: sentinel_id,
+                body: RconRequestBody::Other(RCON_TYPE_RESPONSE, String::new()),
+            })?;
+
+            let mut fragments = RconFragments::new(request_id, sentinel_id);
+            while !fragments.push(self.read_response()?)? {}
+            fragments.into_string()
+        }
+
+        pub fn write_request(&mut self, request: &RconRequest) -> RconResult<()> {
+            let mut out = BytesSerializer::default();
+            request.mc_serialize(&mut out)?;
+            self.inner.write_all(out.into_bytes().as_slice())?;
+            self.inner.flush()?;
+            Ok(())
+        }
+
+        pub fn read_response(&mut self) -> RconResult<RconResponse> {
+            loop {
+                match RconResponse::mc_deserialize(self.buf.as_slice()) {
+                    Ok(Deserialized { value, data }) => {
+                        let consumed = self.buf.len() - data.len();
+                        self.buf.drain(..consumed);
+                        return Ok(value);
+                    }
+                    Err(DeserializeErr::Eof) => {}
+                    Err(err) => return Err(err.into()),
+                }
+
+                let start = self.buf.len();
+                self.buf.resize(start + READ_CHUNK_SIZE, 0);
+                let n = match self.inner.read(&mut self.buf[start..]) {
+                    Ok(n) => n,
+                    Err(err) => {
+                        self.buf.truncate(start);
+                        if err.kind() == io::ErrorKind::Interrupted {
+                            continue;
+                        }
+                        return Err(err.into());
+                    }
+                };
+                self.buf.truncate(start + n);
+                if n == 0 {
+                    return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+                }
+            }
+        }
+
+        fn next_request_id(&mut self) -> i32 {
+            let out = self.next_id;
+            // never hand out the id the server uses to signal a failed login
+            self.next_id = self.next_id.checked_add(1).unwrap_or(1);
+            out
+        }
+
+        pub fn get_ref(&self) -> &S {
+            &self.inner
+        }
+
+        pub fn get_mut(&mut self) -> &mut S {
+            &mut self.inner
+        }
+
+        pub fn into_inner(self) -> S {
+            self.inner
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::types::BytesSerializer;
+    use alloc::{borrow::ToOwned, boxed::Box, collections::VecDeque, vec};
+    use std::io::{self, Read, Write};
+
+    fn serialize<S: Serialize>(value: &S) -> Vec<u8> {
+        let mut out = BytesSerializer::default();
+        value.mc_serialize(&mut out).expect("serializes");
+        out.into_bytes()
+    }
+
+    #[test]
+    fn test_login_fixture() {
+        let bytes = [
+            0x11, 0x00, 0x00, 0x00, // length 17
+            0x01, 0x00, 0x00, 0x00, // request id
+            0x03, 0x00, 0x00, 0x00, // login
+            b'p', b'a', b's', b's', b'w', b'r', b'd', 0x00, 0x00,
+        ];
+        let Deserialized { value, data } = RconRequest::mc_deserialize(&bytes).unwrap();
+        assert!(data.is_empty());
+        assert_eq!(value, RconRequest::login(1, "passwrd"));
+        assert_eq!(serialize(&value), bytes);
+    }
+
+    #[test]
+    fn test_response_fixtures() {
+        let auth_failed = [
+            0x0A, 0x00, 0x00, 0x00,
+            0xFF, 0xFF, 0xFF, 0xFF,
+            0x02, 0x00, 0x00, 0x00,
+            0x00, 0x00,
+        ];
+        let value = RconResponse::mc_deserialize(&auth_failed).unwrap().value;
+        assert!(value.is_auth_failure());
+        assert_eq!(serialize(&value), auth_failed);
+
+        let response = [
+            0x0C, 0x00, 0x00, 0x00,
+            0x07, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00,
+            b'o', b'k', 0x00, 0x00,
+        ];
+        let value = RconResponse::mc_deserialize(&response).unwrap().value;
+        assert_eq!(value, RconResponse { request_id: 7, body: RconResponseBody::Response(b"ok".to_vec()) });
+        assert!(!value.is_auth_failure());
+        assert_eq!(serialize(&value), response);
+
+        assert!(matches!(RconResponse::mc_deserialize(&response[..15]), Err(DeserializeErr::Eof)));
+        let mut bad_padding = response;
+        bad_padding[15] = 0x01;
+        assert!(matches!(RconResponse::mc_deserialize(&bad_padding), Err(DeserializeErr::CannotUnderstandValue(_))));
+        let mut bad_length = response;
+        bad_length[0] = 0x02;
+        assert!(matches!(RconResponse::mc_deserialize(&bad_length), Err(DeserializeErr::CannotUnderstandValue(_))));
+    }
+
+    #[test]
+    fn test_payload_limits() {
+        let long_command = "a".repeat(RCON_MAX_REQUEST_PAYLOAD + 1);
+        assert!(matches!(
+            RconRequest::command(1, &long_command).mc_serialize(&mut BytesSerializer::default()),
+            Err(SerializeErr::CannotSerialize(_))
+        ));
+        assert!(matches!(
+            RconRequest::command(1, "say \0").mc_serialize(&mut BytesSerializer::default()),
+            Err(SerializeErr::CannotSerialize(_))
+        ));
+    }
+
+    #[test]
+    fn test_fragments_across_utf8() {
+        let text = "é".repeat(RCON_MAX_RESPONSE_PAYLOAD);
+        let mut fragments = RconFragments::new(2, 3);
+        for chunk in text.as_bytes().chunks(RCON_MAX_RESPONSE_PAYLOAD - 1) {
+            let done = fragments.push(RconResponse { request_id: 2, body: RconResponseBody::Response(chunk.to_vec()) }).unwrap();
+            assert!(!done);
+        }
+        assert!(matches!(
+            fragments.clone().push(RconResponse { request_id: 9, body: RconResponseBody::AuthResponse }),
+            Err(RconErr::UnexpectedResponse(9))
+        ));
+        assert!(fragments.push(RconResponse { request_id: 3, body: RconResponseBody::Response(b"Unknown request 0".to_vec()) }).unwrap());
+        assert_eq!(fragments.into_string().unwrap(), text);
+    }
+
+    type Handler = Box<dyn FnMut(RconRequest) -> Vec<RconResponse>>;
+
+    // answers every request written to it like a vanilla server, through handle
+    struct FakeServer {
+        incoming: Vec<u8>,
+        outgoing: VecDeque<u8>,
+        handle: Handler,
+    }
+
+    impl FakeServer {
+        fn new<F>(handle: F) -> Self where F: FnMut(RconRequest) -> Vec<RconResponse> + 'static {
+            Self { incoming: Vec::new(), outgoing: VecDeque::new(), handle: Box::new(handle) }
+        }
+    }
+
+    impl Write for FakeServer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.incoming.extend_from_slice(buf);
+            while let Ok(Deserialized { value, data }) = RconRequest::mc_deserialize(self.incoming.as_slice()) {
+                let consumed = self.incoming.len() - data.len();
+                self.incoming.drain(..consumed);
+                for response in (self.handle)(value) {
+                    self.outgoing.extend(serialize(&response));
+                }
+            }
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    // hands out at most 7 bytes per read, to exercise partial packets
+    impl Read for FakeServer {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = buf.len().min(self.outgoing.len()).min(7);
+            for (to, from) in buf.iter_mut().zip(self.outgoing.drain(..n)) {
+                *to = from;
+            }
+            Ok(n)
+        }
+    }
+
+    fn vanilla(output: String) -> impl FnMut(RconRequest) -> Vec<RconResponse> {
+        move |request| match request.body {
+            RconRequestBody::Login(password) => vec![RconResponse {
+                request_id: if password == "hunter2" { request.request_id } else { RCON_AUTH_FAILED_ID },
+                body: RconResponseBody::AuthResponse,
+            }],
+            RconRequestBody::Command(_) => output.as_bytes()
+                .chunks(RCON_MAX_RESPONSE_PAYLOAD)
+                .map(|chunk| RconResponse { request_id: request.request_id, body: RconResponseBody::Response(chunk.to_vec()) })
+                .collect(),
+            RconRequestBody::Other(kind, _) => vec![RconResponse {
+                request_id: request.request_id,
+                body: RconResponseBody::Response(format!("Unknown request {:x}", kind).into_bytes()),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_client() {
+        let output = "There are 0 of a max of 20 players online: ".to_owned() + &"x".repeat(10000);
+        let mut client = RconClient::new(FakeServer::new(vanilla(output.clone())));
+        client.login("hunter2").expect("logs in");
+        assert_eq!(client.command("list").expect("runs"), output);
+        assert_eq!(client.command("list").expect("runs again"), output);
+        assert!(client.get_ref().incoming.is_empty());
+    }
+
+    #[test]
+    fn test_client_bad_password() {
+        let mut client = RconClient::new(FakeServer::new(vanilla(String::new())));
+        assert!(matches!(client.login("password"), Err(RconErr::AuthFailed)));
+    }
+
+    #[test]
+    fn test_client_eof() {
+        let mut client = RconClient::new(FakeServer::new(|_| vec![]));
+        assert!(matches!(client.command("list"), Err(RconErr::Io(err)) if err.kind() == io::ErrorKind::UnexpectedEof));
+    }
+}
@@ -3,6 +3,8 @@ use alloc::{string::String, fmt};
 pub enum SerializeErr {
     FailedJsonEncode(String),
     CannotSerialize(String),
+    BufferFull { needed: usize, remaining: usize },
+    Io(IoError),
 }
 
 impl fmt::Display for SerializeErr {
@@ -15,6 +17,11 @@ impl fmt::Display for SerializeErr {
             CannotSerialize(message) => {
                 f.write_fmt(format_args!("cannot serialize value, invalid representation: {:?}", message))
             }
+            BufferFull { needed, remaining } => f.write_fmt(format_args!(
+                "buffer full, needed {} bytes but only {} remaining",
+                needed, remaining
+            )),
+            Io(err) => f.write_fmt(format_args!("io error: {}", err)),
         }
     }
 }
@@ -28,10 +35,75 @@ impl fmt::Debug for SerializeErr {
 #[cfg(feature = "std")]
 impl std::error::Error for SerializeErr {}
 
+#[cfg(feature = "std")]
+impl From<std::io::Error> for SerializeErr {
+    fn from(err: std::io::Error) -> Self {
+        SerializeErr::Io(IoError { inner: err })
+    }
+}
+
+/// The error of the writer behind a `WriteSerializer`.
+///
+/// It can only be created with the `std` feature, but `SerializeErr::Io` exists without it too, so
+/// matching on `SerializeErr` works the same whichever features are enabled.
+pub struct IoError {
+    #[cfg(feature = "std")]
+    inner: std::io::Error,
+    #[cfg(not(feature = "std"))]
+    never: core::convert::Infallible,
+}
+
+#[cfg(feature = "std")]
+impl IoError {
+    pub fn kind(&self) -> std::io::ErrorKind {
+        self.inner.kind()
+    }
+
+    pub fn get_ref(&self) -> &std::io::Error {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> std::io::Error {
+        self.inner
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for IoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.inner, f)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl fmt::Display for IoError {
+    fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.never {}
+    }
+}
+
+impl fmt::Debug for IoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <dyn fmt::Display>::fmt(self, f)
+    }
+}
+
 pub type SerializeResult = Result<(), SerializeErr>;
 
 pub trait Serialize: Sized {
     fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult;
+
+    /// The exact number of bytes `mc_serialize` writes, so they can go straight into a buffer of
+    /// the right size, or after a length prefix, without serializing twice.
+    ///
+    /// By default this runs `mc_serialize` against a `LenSerializer`, which counts without copying
+    /// anything. Types whose length is cheaper to work out than to write override it. If
+    /// `mc_serialize` fails, this is the length up to the failure.
+    fn serialized_len(&self) -> usize {
+        let mut counter = LenSerializer::default();
+        let _ = self.mc_serialize(&mut counter);
+        counter.len()
+    }
 }
 
 pub trait Serializer: Sized {
@@ -45,3 +117,199 @@ pub trait Serializer: Sized {
         other.mc_serialize(self)
     }
 }
+
+/// A `Serializer` which only counts the bytes written to it, see `Serialize::serialized_len`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LenSerializer {
+    len: usize,
+}
+
+impl LenSerializer {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl Serializer for LenSerializer {
+    fn serialize_bytes(&mut self, data: &[u8]) -> SerializeResult {
+        self.len += data.len();
+        Ok(())
+    }
+
+    fn serialize_byte(&mut self, _: u8) -> SerializeResult {
+        self.len += 1;
+        Ok(())
+    }
+
+    // nested values get the chance to skip serializing with their own serialized_len
+    fn serialize_other<S: Serialize>(&mut self, other: &S) -> SerializeResult {
+        self.len += other.serialized_len();
+        Ok(())
+    }
+}
+
+/// A `Serializer` writing straight into a `std::io::Write`, such as a `TcpStream`.
+///
+/// Every value becomes at least one call to `write_all`, so unbuffered writers should be wrapped
+/// in a `BufWriter` first.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct WriteSerializer<W> {
+    inner: W,
+    written: usize,
+}
+
+#[cfg(feature = "std")]
+impl<W> WriteSerializer<W> where W: std::io::Write {
+    pub fn new(inner: W) -> Self {
+        Self { inner, written: 0 }
+    }
+
+    /// The number of bytes written so far.
+    pub fn written(&self) -> usize {
+        self.written
+    }
+
+    pub fn flush(&mut self) -> SerializeResult {
+        Ok(self.inner.flush()?)
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W> Serializer for WriteSerializer<W> where W: std::io::Write {
+    fn serialize_bytes(&mut self, data: &[u8]) -> SerializeResult {
+        self.inner.write_all(data)?;
+        self.written += data.len();
+        Ok(())
+    }
+}
+
+/// A `Serializer` filling a fixed buffer, which fails with `SerializeErr::BufferFull` instead of
+/// growing it. Nothing of a write that doesn't fit is copied into the buffer.
+///
+/// `Serialize::serialized_len` tells up front how big the buffer has to be.
+#[derive(Debug)]
+pub struct SliceSerializer<'a> {
+    buf: &'a mut [u8],
+    position: usize,
+}
+
+impl<'a> SliceSerializer<'a> {
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, position: 0 }
+    }
+
+    /// The number of bytes written so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.buf.len() - self.position
+    }
+
+    pub fn written(&self) -> &[u8] {
+        &self.buf[..self.position]
+    }
+
+    pub fn into_written(self) -> &'a mut [u8] {
+        &mut self.buf[..self.position]
+    }
+}
+
+impl Serializer for SliceSerializer<'_> {
+    fn serialize_bytes(&mut self, data: &[u8]) -> SerializeResult {
+        let remaining = self.remaining();
+        if data.len() > remaining {
+            return Err(SerializeErr::BufferFull { needed: data.len(), remaining });
+        }
+
+        let end = self.position + data.len();
+        self.buf[self.position..end].copy_from_slice(data);
+        self.position = end;
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::types::VarInt;
+    use alloc::{vec, vec::Vec};
+    use std::io;
+
+    #[test]
+    fn test_slice_serializer() {
+        let mut buf = [0u8; 4];
+        let mut out = SliceSerializer::new(&mut buf);
+        out.serialize_other(&VarInt(300)).unwrap();
+        assert_eq!(out.written(), &[0xAC, 0x02]);
+        assert_eq!(out.remaining(), 2);
+
+        assert!(matches!(
+            out.serialize_other(&0x01020304u32),
+            Err(SerializeErr::BufferFull { needed: 4, remaining: 2 })
+        ));
+        assert_eq!(out.position(), 2);
+
+        out.serialize_byte(7).unwrap();
+        out.serialize_bytes(&[8]).unwrap();
+        assert!(matches!(out.serialize_byte(9), Err(SerializeErr::BufferFull { needed: 1, remaining: 0 })));
+        assert_eq!(out.into_written(), &[0xAC, 0x02, 7, 8]);
+    }
+
+    #[test]
+    fn test_slice_serializer_sized_by_serialized_len() {
+        let value = alloc::string::String::from("hello");
+        let mut buf = vec![0u8; value.serialized_len()];
+        let mut out = SliceSerializer::new(buf.as_mut_slice());
+        out.serialize_other(&value).unwrap();
+        assert_eq!(out.remaining(), 0);
+        assert_eq!(buf, b"\x05hello");
+    }
+
+    struct FailingWriter;
+
+    impl io::Write for FailingWriter {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_write_serializer() {
+        let mut out = WriteSerializer::new(Vec::new());
+        out.serialize_other(&VarInt(300)).unwrap();
+        out.serialize_other(&1u16).unwrap();
+        out.flush().unwrap();
+        assert_eq!(out.written(), 4);
+        assert_eq!(out.into_inner(), vec![0xAC, 0x02, 0x00, 0x01]);
+
+        let mut out = WriteSerializer::new(FailingWriter);
+        assert!(matches!(
+            out.serialize_other(&VarInt(1)),
+            Err(SerializeErr::Io(err)) if err.kind() == io::ErrorKind::BrokenPipe
+        ));
+        assert_eq!(out.written(), 0);
+    }
+}
@@ -5,8 +5,9 @@ use crate::{
     SerializeErr, SerializeResult,
 };
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use alloc::{string::String, fmt, vec::Vec, borrow::ToOwned};
+use alloc::{string::String, fmt, vec::Vec, borrow::ToOwned, collections::BTreeMap};
 use alloc::format;
+use serde_json::Value;
 
 #[cfg(all(test, feature = "std"))]
 use crate::protocol::TestRandom;
@@ -18,6 +19,25 @@ pub struct StatusSpec {
     pub description: Chat,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub favicon: Option<StatusFaviconSpec>,
+    // sent by Forge servers up to 1.12 (FML1)
+    #[serde(rename = "modinfo", skip_serializing_if = "Option::is_none")]
+    pub mod_info: Option<StatusModInfoSpec>,
+    // sent by Forge servers since 1.13 (FML2)
+    #[serde(rename = "forgeData", skip_serializing_if = "Option::is_none")]
+    pub forge_data: Option<StatusForgeDataSpec>,
+    #[serde(rename = "enforcesSecureChat", skip_serializing_if = "Option::is_none")]
+    pub enforces_secure_chat: Option<bool>,
+    #[serde(rename = "previewsChat", skip_serializing_if = "Option::is_none")]
+    pub previews_chat: Option<bool>,
+    // any other keys, kept so a proxy can pass a response through unchanged
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, Value>,
+}
+
+impl StatusSpec {
+    pub fn builder() -> StatusBuilder {
+        StatusBuilder::default()
+    }
 }
 
 impl McSerialize for StatusSpec {
@@ -58,6 +78,11 @@ impl TestRandom for StatusSpec {
             },
             favicon: None,
             description: Chat::test_gen_random(),
+            mod_info: None,
+            forge_data: None,
+            enforces_secure_chat: None,
+            previews_chat: None,
+            extra: BTreeMap::default(),
         }
     }
 }
@@ -83,20 +108,282 @@ pub struct StatusPlayerSampleSpec {
     pub id: UUID4,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StatusModInfoSpec {
+    // optional, so a server leaving it out still gets the rest of its status parsed
+    #[serde(rename = "type", default)]
+    pub kind: String,
+    #[serde(rename = "modList", default = "Vec::default")]
+    pub mod_list: Vec<StatusModInfoModSpec>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StatusModInfoModSpec {
+    #[serde(rename = "modid")]
+    pub mod_id: String,
+    pub version: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StatusForgeDataSpec {
+    #[serde(default = "Vec::default")]
+    pub channels: Vec<StatusForgeChannelSpec>,
+    #[serde(default = "Vec::default")]
+    pub mods: Vec<StatusForgeModSpec>,
+    #[serde(rename = "fmlNetworkVersion", default)]
+    pub fml_network_version: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncated: Option<bool>,
+    // newer versions of forge pack the lists into an encoded string "d" instead
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StatusForgeChannelSpec {
+    pub res: String,
+    pub version: String,
+    pub required: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StatusForgeModSpec {
+    #[serde(rename = "modId")]
+    pub mod_id: String,
+    #[serde(rename = "modmarker")]
+    pub mod_marker: String,
+}
+
+// Builds a StatusSpec for a server to send. Anything not set is left out of the response, except
+// players and description, which default to 0/0 and an empty text component.
+#[derive(Clone, Debug, Default)]
+pub struct StatusBuilder {
+    version: Option<StatusVersionSpec>,
+    online: i32,
+    max: i32,
+    sample: Vec<StatusPlayerSampleSpec>,
+    description: Option<Chat>,
+    favicon: Option<StatusFaviconSpec>,
+    mod_info: Option<StatusModInfoSpec>,
+    forge_data: Option<StatusForgeDataSpec>,
+    enforces_secure_chat: Option<bool>,
+    previews_chat: Option<bool>,
+    extra: BTreeMap<String, Value>,
+}
+
+impl StatusBuilder {
+    pub fn version(mut self, name: &str, protocol: i32) -> Self {
+        self.version = Some(StatusVersionSpec {
+            name: name.to_owned(),
+            protocol,
+        });
+        self
+    }
+
+    pub fn players(mut self, online: i32, max: i32) -> Self {
+        self.online = online;
+        self.max = max;
+        self
+    }
+
+    pub fn sample_player(mut self, name: &str, id: UUID4) -> Self {
+        self.sample.push(StatusPlayerSampleSpec {
+            name: name.to_owned(),
+            id,
+        });
+        self
+    }
+
+    pub fn description(mut self, description: Chat) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    // a description written with legacy section sign color codes
+    pub fn motd(self, motd: &str) -> Self {
+        self.description(Chat::from_traditional(motd, false))
+    }
+
+    pub fn favicon(mut self, favicon: StatusFaviconSpec) -> Self {
+        self.favicon = Some(favicon);
+        self
+    }
+
+    pub fn mod_info(mut self, mod_info: StatusModInfoSpec) -> Self {
+        self.mod_info = Some(mod_info);
+        self
+    }
+
+    pub fn forge_data(mut self, forge_data: StatusForgeDataSpec) -> Self {
+        self.forge_data = Some(forge_data);
+        self
+    }
+
+    pub fn enforces_secure_chat(mut self, enforces_secure_chat: bool) -> Self {
+        self.enforces_secure_chat = Some(enforces_secure_chat);
+        self
+    }
+
+    pub fn previews_chat(mut self, previews_chat: bool) -> Self {
+        self.previews_chat = Some(previews_chat);
+        self
+    }
+
+    pub fn extra(mut self, key: &str, value: Value) -> Self {
+        self.extra.insert(key.to_owned(), value);
+        self
+    }
+
+    pub fn build(self) -> StatusSpec {
+        StatusSpec {
+            version: self.version,
+            players: StatusPlayersSpec {
+                max: self.max,
+                online: self.online,
+                sample: self.sample,
+            },
+            description: self.description.unwrap_or_else(move || Chat::from_text("")),
+            favicon: self.favicon,
+            mod_info: self.mod_info,
+            forge_data: self.forge_data,
+            enforces_secure_chat: self.enforces_secure_chat,
+            previews_chat: self.previews_chat,
+            extra: self.extra,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct StatusFaviconSpec {
     pub content_type: String,
     pub data: Vec<u8>,
 }
 
+pub const FAVICON_SIZE: u32 = 64;
+pub const PNG_CONTENT_TYPE: &str = "image/png";
+pub const PNG_DATA_URI_PREFIX: &str = "data:image/png;base64,";
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+const PNG_IHDR_LENGTH: u32 = 13;
+
+pub enum FaviconErr {
+    NotPngDataUri,
+    BadBase64(String),
+    NotPng,
+    MissingHeader,
+    BadSize { width: u32, height: u32 },
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+}
+
+impl fmt::Display for FaviconErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use FaviconErr::*;
+        match self {
+            NotPngDataUri => f.write_fmt(format_args!("favicon does not start with {}", PNG_DATA_URI_PREFIX)),
+            BadBase64(err) => f.write_fmt(format_args!("bad base64 in favicon: {}", err)),
+            NotPng => f.write_str("favicon is not a png"),
+            MissingHeader => f.write_str("favicon png has no IHDR header"),
+            BadSize { width, height } => f.write_fmt(format_args!(
+                "favicon is {}x{}, expected {}x{}",
+                width, height, FAVICON_SIZE, FAVICON_SIZE
+            )),
+            #[cfg(feature = "std")]
+            Io(err) => f.write_fmt(format_args!("failed to read favicon: {}", err)),
+        }
+    }
+}
+
+impl fmt::Debug for FaviconErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <dyn fmt::Display>::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FaviconErr {}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for FaviconErr {
+    fn from(err: std::io::Error) -> Self {
+        FaviconErr::Io(err)
+    }
+}
+
+pub type FaviconResult<T> = Result<T, FaviconErr>;
+
+pub fn strip_png_data_uri_prefix(uri: &str) -> Option<&str> {
+    uri.strip_prefix(PNG_DATA_URI_PREFIX)
+}
+
+pub fn add_png_data_uri_prefix(data_base64: &str) -> String {
+    format!("{}{}", PNG_DATA_URI_PREFIX, data_base64)
+}
+
+// reads width and height from the IHDR chunk, which the png spec requires to come first
+pub fn png_dimensions(data: &[u8]) -> FaviconResult<(u32, u32)> {
+    if data.len() < PNG_SIGNATURE.len() || data[..PNG_SIGNATURE.len()] != PNG_SIGNATURE {
+        return Err(FaviconErr::NotPng);
+    }
+
+    let chunk = &data[PNG_SIGNATURE.len()..];
+    if chunk.len() < 16 || &chunk[4..8] != b"IHDR" {
+        return Err(FaviconErr::MissingHeader);
+    }
+
+    let read_u32 = move |at: usize| u32::from_be_bytes([chunk[at], chunk[at + 1], chunk[at + 2], chunk[at + 3]]);
+    if read_u32(0) != PNG_IHDR_LENGTH {
+        return Err(FaviconErr::MissingHeader);
+    }
+
+    Ok((read_u32(8), read_u32(12)))
+}
+
+impl StatusFaviconSpec {
+    /// Wraps the bytes of a png file, which must be 64x64 as the vanilla client requires.
+    pub fn from_png(data: Vec<u8>) -> FaviconResult<Self> {
+        let out = Self {
+            content_type: PNG_CONTENT_TYPE.to_owned(),
+            data,
+        };
+        out.validate()?;
+        Ok(out)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn from_png_file<P: AsRef<std::path::Path>>(path: P) -> FaviconResult<Self> {
+        Self::from_png(std::fs::read(path)?)
+    }
+
+    /// Parses a `data:image/png;base64,` uri as found in a status response, and validates it.
+    pub fn from_data_uri(uri: &str) -> FaviconResult<Self> {
+        let data_base64 = strip_png_data_uri_prefix(uri).ok_or(FaviconErr::NotPngDataUri)?;
+        let data = base64::decode(data_base64.replace('\n', ""))
+            .map_err(move |err| FaviconErr::BadBase64(format!("{:?}", err)))?;
+        Self::from_png(data)
+    }
+
+    pub fn to_data_uri(&self) -> String {
+        format!("data:{};base64,{}", self.content_type, base64::encode(self.data.as_slice()))
+    }
+
+    pub fn validate(&self) -> FaviconResult<()> {
+        if self.content_type != PNG_CONTENT_TYPE {
+            return Err(FaviconErr::NotPng);
+        }
+
+        match png_dimensions(self.data.as_slice())? {
+            (FAVICON_SIZE, FAVICON_SIZE) => Ok(()),
+            (width, height) => Err(FaviconErr::BadSize { width, height }),
+        }
+    }
+}
+
 impl Serialize for StatusFaviconSpec {
     fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
     where
         S: Serializer,
     {
-        let data_base64 = base64::encode(self.data.as_slice());
-        let content = alloc::format!("data:{};base64,{}", self.content_type, data_base64);
-        serializer.serialize_str(content.as_str())
+        serializer.serialize_str(self.to_data_uri().as_str())
     }
 }
 
@@ -188,4 +475,145 @@ fn str_take<'a, E, V>(
     } else {
         Ok(target.split_at(n))
     }
-}
\ No newline at end of file
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forge_fml1() {
+        let json = r#"{"version":{"name":"1.12.2","protocol":340},"players":{"max":20,"online":0},"description":{"text":"A Minecraft Server"},"modinfo":{"type":"FML","modList":[{"modid":"minecraft","version":"1.12.2"},{"modid":"forge","version":"14.23.5.2854"}]}}"#;
+        let status: StatusSpec = serde_json::from_str(json).expect("parses");
+        let mod_info = status.mod_info.as_ref().expect("has modinfo");
+        assert_eq!(mod_info.kind, "FML");
+        assert_eq!(mod_info.mod_list[1].mod_id, "forge");
+        assert_eq!(mod_info.mod_list[1].version, "14.23.5.2854");
+        assert!(status.extra.is_empty());
+        assert_eq!(serde_json::from_str::<Value>(&serde_json::to_string(&status).unwrap()).unwrap(), serde_json::from_str::<Value>(json).unwrap());
+    }
+
+    #[test]
+    fn test_forge_fml2() {
+        let json = r#"{"version":{"name":"1.16.5","protocol":754},"players":{"max":20,"online":1},"description":{"text":"modded"},"forgeData":{"channels":[{"res":"forge:tier_sorting","version":"1.0","required":false}],"mods":[{"modId":"forge","modmarker":"ANY"}],"fmlNetworkVersion":2,"truncated":false}}"#;
+        let status: StatusSpec = serde_json::from_str(json).expect("parses");
+        let forge_data = status.forge_data.as_ref().expect("has forgeData");
+        assert_eq!(forge_data.fml_network_version, 2);
+        assert_eq!(forge_data.channels[0].res, "forge:tier_sorting");
+        assert!(!forge_data.channels[0].required);
+        assert_eq!(forge_data.mods[0].mod_marker, "ANY");
+        assert_eq!(forge_data.truncated, Some(false));
+        assert_eq!(serde_json::from_str::<Value>(&serde_json::to_string(&status).unwrap()).unwrap(), serde_json::from_str::<Value>(json).unwrap());
+    }
+
+    #[test]
+    fn test_forge_missing_keys() {
+        let json = r#"{"version":{"name":"1.12.2","protocol":340},"players":{"max":20,"online":0},"description":"modded","modinfo":{"modList":[{"modid":"forge","version":"14.23.5.2854"}]}}"#;
+        let status: StatusSpec = serde_json::from_str(json).expect("parses without modinfo.type");
+        let mod_info = status.mod_info.as_ref().expect("has modinfo");
+        assert_eq!(mod_info.kind, "");
+        assert_eq!(mod_info.mod_list[0].mod_id, "forge");
+
+        let json = r#"{"version":{"name":"1.16.5","protocol":754},"players":{"max":20,"online":0},"description":"modded","forgeData":{"mods":[{"modId":"forge","modmarker":"ANY"}]}}"#;
+        let status: StatusSpec = serde_json::from_str(json).expect("parses without forgeData.fmlNetworkVersion");
+        let forge_data = status.forge_data.as_ref().expect("has forgeData");
+        assert_eq!(forge_data.fml_network_version, 0);
+        assert_eq!(forge_data.mods[0].mod_marker, "ANY");
+    }
+
+    #[test]
+    fn test_unknown_keys_round_trip() {
+        let json = r#"{"version":{"name":"1.19","protocol":759},"players":{"max":20,"online":1},"description":"plain","enforcesSecureChat":true,"previewsChat":false,"preventsChatReports":true,"forgeData":{"fmlNetworkVersion":3,"d":"\u0000abc"}}"#;
+        let status: StatusSpec = serde_json::from_str(json).expect("parses");
+        assert_eq!(status.enforces_secure_chat, Some(true));
+        assert_eq!(status.previews_chat, Some(false));
+        assert_eq!(status.extra.get("preventsChatReports"), Some(&Value::Bool(true)));
+        let forge_data = status.forge_data.as_ref().unwrap();
+        assert!(forge_data.mods.is_empty());
+        assert!(forge_data.extra.contains_key("d"));
+
+        let round_trip: StatusSpec = serde_json::from_str(&serde_json::to_string(&status).unwrap()).unwrap();
+        assert_eq!(round_trip, status);
+    }
+
+    #[test]
+    fn test_builder() {
+        let id = UUID4::from(0x1234u128);
+        let status = StatusSpec::builder()
+            .version("1.16.5", 754)
+            .players(1, 20)
+            .sample_player("Notch", id)
+            .motd("hello")
+            .enforces_secure_chat(false)
+            .extra("custom", Value::from(5))
+            .build();
+
+        assert_eq!(status.version, Some(StatusVersionSpec { name: "1.16.5".to_owned(), protocol: 754 }));
+        assert_eq!(status.players.online, 1);
+        assert_eq!(status.players.max, 20);
+        assert_eq!(status.players.sample[0].id, id);
+        assert_eq!(status.description.to_traditional().as_deref(), Some("hello"));
+        assert_eq!(status.mod_info, None);
+
+        let json: Value = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["enforcesSecureChat"], Value::Bool(false));
+        assert_eq!(json["custom"], Value::from(5));
+        assert!(json.get("previewsChat").is_none());
+        assert!(json.get("forgeData").is_none());
+
+        let empty = StatusSpec::builder().build();
+        assert_eq!(empty.version, None);
+        assert_eq!(empty.description, Chat::from_text(""));
+    }
+
+    fn png_header(width: u32, height: u32) -> Vec<u8> {
+        let mut out = PNG_SIGNATURE.to_vec();
+        out.extend_from_slice(&PNG_IHDR_LENGTH.to_be_bytes());
+        out.extend_from_slice(b"IHDR");
+        out.extend_from_slice(&width.to_be_bytes());
+        out.extend_from_slice(&height.to_be_bytes());
+        // bit depth, color type, compression, filter, interlace, then the chunk crc
+        out.extend_from_slice(&[8, 6, 0, 0, 0, 0, 0, 0, 0]);
+        out
+    }
+
+    #[test]
+    fn test_favicon_from_png() {
+        let favicon = StatusFaviconSpec::from_png(png_header(64, 64)).expect("valid favicon");
+        assert_eq!(favicon.content_type, PNG_CONTENT_TYPE);
+
+        let uri = favicon.to_data_uri();
+        assert!(uri.starts_with(PNG_DATA_URI_PREFIX));
+        assert_eq!(add_png_data_uri_prefix(strip_png_data_uri_prefix(&uri).unwrap()), uri);
+        assert_eq!(StatusFaviconSpec::from_data_uri(&uri).expect("parses"), favicon);
+        assert_eq!(serde_json::to_value(&favicon).unwrap(), Value::String(uri));
+    }
+
+    #[test]
+    fn test_favicon_errors() {
+        assert!(matches!(StatusFaviconSpec::from_png(png_header(128, 64)), Err(FaviconErr::BadSize { width: 128, height: 64 })));
+        assert!(matches!(StatusFaviconSpec::from_png(b"GIF89a".to_vec()), Err(FaviconErr::NotPng)));
+        assert!(matches!(StatusFaviconSpec::from_png(PNG_SIGNATURE.to_vec()), Err(FaviconErr::MissingHeader)));
+
+        let mut wrong_chunk = png_header(64, 64);
+        wrong_chunk[12..16].copy_from_slice(b"IDAT");
+        assert!(matches!(StatusFaviconSpec::from_png(wrong_chunk), Err(FaviconErr::MissingHeader)));
+
+        assert!(matches!(StatusFaviconSpec::from_data_uri("data:image/jpeg;base64,AAAA"), Err(FaviconErr::NotPngDataUri)));
+        assert!(matches!(StatusFaviconSpec::from_data_uri("data:image/png;base64,!!!"), Err(FaviconErr::BadBase64(_))));
+
+        let jpeg = StatusFaviconSpec { content_type: "image/jpeg".to_owned(), data: png_header(64, 64) };
+        assert!(matches!(jpeg.validate(), Err(FaviconErr::NotPng)));
+    }
+
+    #[test]
+    fn test_favicon_from_file() {
+        let path = std::env::temp_dir().join(format!("mcproto-favicon-{}.png", std::process::id()));
+        std::fs::write(&path, png_header(64, 64)).unwrap();
+        let favicon = StatusFaviconSpec::from_png_file(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(favicon.expect("reads file").data, png_header(64, 64));
+
+        assert!(matches!(StatusFaviconSpec::from_png_file(path), Err(FaviconErr::Io(_))));
+    }
+}
@@ -13,6 +13,7 @@ macro_rules! packet_test_cases {
                 let mut out = crate::types::BytesSerializer::default();
                 packet.mc_serialize_body(&mut out).expect("serialize succeeds");
                 let bytes = out.into_bytes();
+                assert_eq!(packet.serialized_body_len(), bytes.len(), "serialized_body_len() == serialize(packet).len()");
 
                 let raw_packet = $rawnam::create(packet.id(), bytes.as_slice()).expect("valid id");
                 let deserialized = match raw_packet.deserialize() {
@@ -23,7 +24,26 @@ macro_rules! packet_test_cases {
                     Ok(out) => out
                 };
                 assert_eq!(packet, deserialized, "deserialize(serialize(packet)) == packet");
-                assert_eq!(packet.clone(), deserialized.clone(), "deserialized.clone() == packet.clone()")
+                assert_eq!(packet.clone(), deserialized.clone(), "deserialized.clone() == packet.clone()");
+
+                #[cfg(feature = "compression")]
+                {
+                    let compression = crate::compression::Compression::with_threshold(64);
+                    let mut out = crate::types::BytesSerializer::default();
+                    compression.write_packet(&packet, &mut out).expect("compressed write succeeds");
+                    let bytes = out.into_bytes();
+                    let payload = compression.read_frame(bytes.as_slice())
+                        .expect("valid compressed frame")
+                        .expect("complete compressed frame")
+                        .value;
+                    let id = packet.id();
+                    let raw_packet: $rawnam = crate::frame::RawFrame::parse(payload.as_ref(), id.state, id.direction)
+                        .expect("valid payload")
+                        .packet()
+                        .expect("valid id");
+                    assert_eq!(packet, raw_packet.deserialize().expect("deserialize compressed succeeds"),
+                        "decompress(compress(packet)) == packet");
+                }
             }
         }
 
@@ -0,0 +1,1156 @@
+use crate::nbt::{NamedTag, Tag};
+use crate::protocol::{HasPacketId, Id};
+use crate::types::{BytesSerializer, ChunkPosition, CountedArray, NamedNbtTag, VarInt};
+use crate::uuid::UUID4;
+use crate::v1_15_2::{self as v578, Packet578};
+use crate::v1_16_3::{self as v753, Packet753};
+use crate::{Deserialize, DeserializeErr, Deserialized, Serialize, SerializeErr, Serializer};
+use alloc::{borrow::ToOwned, boxed::Box, fmt, string::String, vec, vec::Vec};
+
+pub enum TranslateErr {
+    Untranslatable(Id),
+    Unrepresentable(Id, &'static str),
+    SerializeFailed(Id, SerializeErr),
+    DeserializeFailed(Id, DeserializeErr),
+}
+
+impl fmt::Display for TranslateErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use TranslateErr::*;
+        match self {
+            Untranslatable(id) => f.write_fmt(format_args!(
+                "packet {:?} has no equivalent in the target version",
+                id
+            )),
+            Unrepresentable(id, reason) => {
+                f.write_fmt(format_args!("cannot translate packet {:?}: {}", id, reason))
+            }
+            SerializeFailed(id, err) => {
+                f.write_fmt(format_args!("failed to re-encode packet {:?}: {:?}", id, err))
+            }
+            DeserializeFailed(id, err) => f.write_fmt(format_args!(
+                "packet {:?} does not decode in the target version: {:?}",
+                id, err
+            )),
+        }
+    }
+}
+
+impl fmt::Debug for TranslateErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <dyn fmt::Display>::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TranslateErr {}
+
+pub type TranslateResult<T> = Result<T, TranslateErr>;
+
+/// Translates packets between protocol 578 (1.15.2) and protocol 753 (1.16.3).
+///
+/// Packets which are encoded the same way in both versions are translated by re-encoding them,
+/// and the packets which changed are rewritten field by field. Registry ids (block states, items,
+/// entity types, sounds, ...) are copied as they are, so callers who need them remapped have to
+/// do that on top of this.
+///
+/// The dimension codec is sent in every `PlayJoinGame` translated to 753, and is where the
+/// dimension types of `PlayJoinGame` and `PlayRespawn` are looked up. The default is a minimal
+/// codec with the vanilla dimension types and biome ids.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Translator {
+    dimension_codec: NamedNbtTag,
+}
+
+impl Default for Translator {
+    fn default() -> Self {
+        Self::with_dimension_codec(default_dimension_codec())
+    }
+}
+
+impl Translator {
+    pub fn with_dimension_codec(dimension_codec: NamedNbtTag) -> Self {
+        Self { dimension_codec }
+    }
+
+    pub fn dimension_codec(&self) -> &NamedNbtTag {
+        &self.dimension_codec
+    }
+
+    pub fn to_578(&self, packet: Packet753) -> TranslateResult<Packet578> {
+        if let Some(out) = same_shape_to_578(&packet) {
+            return out;
+        }
+
+        let id = packet.id();
+        Ok(match packet {
+            Packet753::LoginSuccess(body) => Packet578::LoginSuccess(v578::LoginSuccessSpec {
+                uuid_string: body.uuid.hex(),
+                username: body.username,
+            }),
+            Packet753::PlayServerChatMessage(body) => {
+                Packet578::PlayServerChatMessage(v578::PlayServerChatMessageSpec {
+                    message: body.message,
+                    position: reencode(id, &body.position)?,
+                })
+            }
+            Packet753::PlayUpdateLight(body) => Packet578::PlayUpdateLight(v578::PlayUpdateLightSpec {
+                chunk: body.chunk,
+                update: reencode(id, &body.update)?,
+            }),
+            Packet753::PlayJoinGame(body) => Packet578::PlayJoinGame(v578::PlayJoinGameSpec {
+                entity_id: body.entity_id,
+                gamemode: reencode(id, &body.gamemode)?,
+                dimension: dimension_578(&body.dimension, body.world_name.as_str()),
+                hashed_seed: body.hashed_seed,
+                max_players: body.max_players.0.max(0).min(u8::MAX as i32) as u8,
+                level_type: level_type_578(body.is_debug, body.is_flat),
+                view_distance: body.view_distance,
+                reduced_debug_info: body.reduced_debug_info,
+                enable_respawn_screen: body.enable_respawn_screen,
+            }),
+            Packet753::PlayOpenWindow(body) => Packet578::PlayOpenWindow(v578::PlayOpenWindowSpec {
+                id: body.id,
+                kind: reencode(id, &body.kind)?,
+                title: reencode(id, &body.title)?,
+            }),
+            Packet753::PlayUnlockRecipes(body) => {
+                Packet578::PlayUnlockRecipes(v578::PlayUnlockRecipesSpec {
+                    action: reencode(id, &body.action)?,
+                    crafting_book_open: body.crafting_book_open,
+                    crafting_book_active: body.crafting_book_active,
+                    smelting_book_open: body.smelting_book_open,
+                    smelting_book_active: body.smelting_book_active,
+                    recipe_ids: body.recipe_ids,
+                    other_recipe_ids: body.other_recipe_ids,
+                })
+            }
+            Packet753::PlayRespawn(body) => Packet578::PlayRespawn(v578::PlayRespawnSpec {
+                dimension: dimension_578(&body.dimension, body.world_name.as_str()),
+                hashed_seed: body.hashed_seed,
+                gamemode: reencode(id, &body.gamemode)?,
+                level_type: level_type_578(body.is_debug, body.is_flat),
+            }),
+            Packet753::PlayMultiBlockChange(body) => {
+                let section = body.chunk;
+                let changes = body
+                    .blocks
+                    .iter()
+                    .map(|record| {
+                        let y = section.y * 16 + (record.rel_position.y as i32);
+                        if !(0..=(u8::MAX as i32)).contains(&y) {
+                            return Err(TranslateErr::Unrepresentable(id, "block change is outside of the 1.15 world height"));
+                        }
+                        if record.block_id > (i32::MAX as u64) {
+                            return Err(TranslateErr::Unrepresentable(id, "block state id does not fit in a VarInt"));
+                        }
+
+                        Ok(v578::MultiBlockChangeRecord {
+                            horizontal_position: v578::BlockChangeHorizontalPosition {
+                                rel_x: record.rel_position.x as u8,
+                                rel_z: record.rel_position.z as u8,
+                            },
+                            y_coordinate: y as u8,
+                            block_id: VarInt(record.block_id as i32),
+                        })
+                    })
+                    .collect::<TranslateResult<Vec<_>>>()?;
+
+                Packet578::PlayMultiBlockChange(v578::PlayMultiBlockChangeSpec {
+                    chunk: ChunkPosition { x: section.x, z: section.z },
+                    changes: changes.into(),
+                })
+            }
+            Packet753::PlayEntityEquipment(body) => {
+                if body.equipment.len() != 1 {
+                    return Err(TranslateErr::Unrepresentable(id, "1.15 sends one equipment slot per packet"));
+                }
+                let entry = &body.equipment[0];
+                Packet578::PlayEntityEquipment(v578::PlayEntityEquiptmentSpec {
+                    entity_id: body.entity_id,
+                    slot: reencode(id, &entry.slot)?,
+                    item: reencode(id, &entry.item)?,
+                })
+            }
+            Packet753::PlayChunkData(body) => Packet578::PlayChunkData(v578::PlayChunkDataWrapper {
+                data: chunk_data_578(id, body.data)?,
+            }),
+            Packet753::PlayInteractEntity(body) => {
+                Packet578::PlayInteractEntity(v578::PlayInteractEntitySpec {
+                    entity_id: body.entity_id,
+                    kind: reencode(id, &body.kind)?,
+                })
+            }
+            Packet753::PlayClientPlayerAbilities(body) => {
+                Packet578::PlayClientPlayerAbilities(v578::PlayClientPlayerAbilitiesSpec {
+                    flags: reencode(id, &body.flags)?,
+                    flying_speed: DEFAULT_FLYING_SPEED,
+                    walking_speed: DEFAULT_WALKING_SPEED,
+                })
+            }
+            _ => return Err(TranslateErr::Untranslatable(id)),
+        })
+    }
+
+    pub fn to_753(&self, packet: Packet578) -> TranslateResult<Packet753> {
+        if let Some(out) = same_shape_to_753(&packet) {
+            return out;
+        }
+
+        let id = packet.id();
+        Ok(match packet {
+            Packet578::LoginSuccess(body) => Packet753::LoginSuccess(v753::LoginSuccessSpec {
+                uuid: UUID4::parse(body.uuid_string.as_str())
+                    .ok_or(TranslateErr::Unrepresentable(id, "uuid is not a valid uuid string"))?,
+                username: body.username,
+            }),
+            Packet578::PlayServerChatMessage(body) => {
+                Packet753::PlayServerChatMessage(v753::PlayServerChatMessageSpec {
+                    message: body.message,
+                    position: reencode(id, &body.position)?,
+                    // the nil uuid marks messages which weren't sent by a player
+                    sender: UUID4::from(0u128),
+                })
+            }
+            Packet578::PlayUpdateLight(body) => Packet753::PlayUpdateLight(v753::PlayUpdateLightSpec {
+                chunk: body.chunk,
+                trust_edges: true,
+                update: reencode(id, &body.update)?,
+            }),
+            Packet578::PlayJoinGame(body) => {
+                let world_name = dimension_name(&body.dimension);
+                Packet753::PlayJoinGame(v753::PlayJoinGameSpec {
+                    entity_id: body.entity_id,
+                    is_hardcore: false,
+                    gamemode: reencode(id, &body.gamemode)?,
+                    previous_gamemode: v753::PreviousGameMode::NoPrevious,
+                    worlds: dimension_type_names(&self.dimension_codec)
+                        .map(ToOwned::to_owned)
+                        .collect::<Vec<_>>()
+                        .into(),
+                    dimension_codec: self.dimension_codec.clone(),
+                    dimension: self.dimension_type(id, world_name)?,
+                    world_name: world_name.to_owned(),
+                    hashed_seed: body.hashed_seed,
+                    max_players: VarInt(body.max_players as i32),
+                    view_distance: body.view_distance,
+                    reduced_debug_info: body.reduced_debug_info,
+                    enable_respawn_screen: body.enable_respawn_screen,
+                    is_debug: body.level_type == LEVEL_TYPE_DEBUG,
+                    is_flat: body.level_type == LEVEL_TYPE_FLAT,
+                })
+            }
+            Packet578::PlayOpenWindow(body) => Packet753::PlayOpenWindow(v753::PlayOpenWindowSpec {
+                id: body.id,
+                kind: reencode(id, &body.kind)?,
+                title: reencode(id, &body.title)?,
+            }),
+            Packet578::PlayUnlockRecipes(body) => {
+                Packet753::PlayUnlockRecipes(v753::PlayUnlockRecipesSpec {
+                    action: reencode(id, &body.action)?,
+                    crafting_book_open: body.crafting_book_open,
+                    crafting_book_active: body.crafting_book_active,
+                    smelting_book_open: body.smelting_book_open,
+                    smelting_book_active: body.smelting_book_active,
+                    blast_furnace_recipe_book_open: false,
+                    blast_furnace_recipe_book_active: false,
+                    smoke_recipe_book_open: false,
+                    smoke_recipe_book_active: false,
+                    recipe_ids: body.recipe_ids,
+                    other_recipe_ids: body.other_recipe_ids,
+                })
+            }
+            Packet578::PlayRespawn(body) => {
+                let world_name = dimension_name(&body.dimension);
+                let gamemode: v753::GameMode = reencode(id, &body.gamemode)?;
+                Packet753::PlayRespawn(v753::PlayRespawnSpec {
+                    dimension: self.dimension_type(id, world_name)?,
+                    world_name: world_name.to_owned(),
+                    hashed_seed: body.hashed_seed,
+                    previous_gamemode: gamemode.clone(),
+                    gamemode,
+                    is_debug: body.level_type == LEVEL_TYPE_DEBUG,
+                    is_flat: body.level_type == LEVEL_TYPE_FLAT,
+                    copy_metadata: false,
+                })
+            }
+            Packet578::PlayMultiBlockChange(body) => {
+                let section_y = body.changes.first().map(|record| record.y_coordinate >> 4).unwrap_or(0);
+                let blocks = body
+                    .changes
+                    .iter()
+                    .map(|record| {
+                        if record.y_coordinate >> 4 != section_y {
+                            return Err(TranslateErr::Unrepresentable(id, "1.16 block changes must be in a single chunk section"));
+                        }
+                        if record.block_id.0 < 0 {
+                            return Err(TranslateErr::Unrepresentable(id, "block state id is negative"));
+                        }
+
+                        Ok(v753::MultiBlockChangeRecord {
+                            block_id: record.block_id.0 as u64,
+                            rel_position: (
+                                record.horizontal_position.rel_x as i8,
+                                (record.y_coordinate & 0xF) as i8,
+                                record.horizontal_position.rel_z as i8,
+                            ).into(),
+                        })
+                    })
+                    .collect::<TranslateResult<Vec<_>>>()?;
+
+                Packet753::PlayMultiBlockChange(v753::PlayMultiBlockChangeSpec {
+                    chunk: v753::ChunkSectionPosition {
+                        x: body.chunk.x,
+                        y: section_y as i32,
+                        z: body.chunk.z,
+                    },
+                    not_trust_edges: false,
+                    blocks: blocks.into(),
+                })
+            }
+            Packet578::PlayEntityEquipment(body) => {
+                Packet753::PlayEntityEquipment(v753::PlayEntityEquiptmentSpec {
+                    entity_id: body.entity_id,
+                    equipment: vec![v753::EntityEquipmentEntry {
+                        slot: reencode(id, &body.slot)?,
+                        item: reencode(id, &body.item)?,
+                    }].into(),
+                })
+            }
+            Packet578::PlayChunkData(body) => Packet753::PlayChunkData(v753::PlayChunkDataWrapper {
+                data: chunk_data_753(id, body.data)?,
+            }),
+            Packet578::PlayInteractEntity(body) => {
+                Packet753::PlayInteractEntity(v753::PlayInteractEntitySpec {
+                    entity_id: body.entity_id,
+                    kind: reencode(id, &body.kind)?,
+                    sneaking: false,
+                })
+            }
+            Packet578::PlayClientPlayerAbilities(body) => {
+                Packet753::PlayClientPlayerAbilities(v753::PlayClientPlayerAbilitiesSpec {
+                    flags: reencode(id, &body.flags)?,
+                })
+            }
+            _ => return Err(TranslateErr::Untranslatable(id)),
+        })
+    }
+
+    // the element of the dimension type registry entry called `name`
+    fn dimension_type(&self, id: Id, name: &str) -> TranslateResult<NamedNbtTag> {
+        dimension_type_entries(&self.dimension_codec)
+            .find(|entry| compound_string(entry, "name") == Some(name))
+            .and_then(|entry| compound_get(entry, "element"))
+            .map(|element| NamedNbtTag { root: element.clone().with_name("") })
+            .ok_or(TranslateErr::Unrepresentable(id, "dimension type is missing from the dimension codec"))
+    }
+}
+
+// serializes with one version and deserializes with the other
+fn reencode<A, B>(id: Id, from: &A) -> TranslateResult<B> where A: Serialize, B: Deserialize {
+    let mut out = BytesSerializer::default();
+    out.serialize_other(from).map_err(move |err| TranslateErr::SerializeFailed(id, err))?;
+    let bytes = out.into_bytes();
+    let Deserialized { value, data: rest } = B::mc_deserialize(bytes.as_slice())
+        .map_err(move |err| TranslateErr::DeserializeFailed(id, err))?;
+    if !rest.is_empty() {
+        return Err(TranslateErr::Unrepresentable(id, "re-encoded value has trailing bytes"));
+    }
+
+    Ok(value)
+}
+
+macro_rules! same_shape_packets {
+    ($($nam: ident),*) => {
+        fn same_shape_to_578(packet: &Packet753) -> Option<TranslateResult<Packet578>> {
+            let id = packet.id();
+            match packet {
+                $(Packet753::$nam(body) => Some(reencode(id, body).map(Packet578::$nam)),)*
+                _ => None,
+            }
+        }
+
+        fn same_shape_to_753(packet: &Packet578) -> Option<TranslateResult<Packet753>> {
+            let id = packet.id();
+            match packet {
+                $(Packet578::$nam(body) => Some(reencode(id, body).map(Packet753::$nam)),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+// packets with the same fields in both versions. some of them can still fail to translate, for
+// example a smithing recipe in PlayDeclareRecipes doesn't decode in 1.15
+same_shape_packets!(
+    Handshake, StatusRequest, StatusPing, StatusResponse, StatusPong, LoginDisconnect,
+    LoginEncryptionRequest, LoginSetCompression, LoginPluginRequest, LoginStart,
+    LoginEncryptionResponse, LoginPluginResponse, PlaySpawnEntity, PlaySpawnExperienceOrb,
+    PlaySpawnLivingEntity, PlaySpawnPainting, PlaySpawnPlayer, PlayEntityAnimation, PlayStatistics,
+    PlayAcknowledgePlayerDigging, PlayBlockBreakAnimation, PlayBlockEntityData, PlayBlockAction,
+    PlayBlockChange, PlayBossBar, PlayServerDifficulty, PlayTabComplete, PlayDeclareCommands,
+    PlayServerWindowConfirmation, PlayServerCloseWindow, PlayWindowItems, PlayWindowProperty,
+    PlaySetSlot, PlaySetCooldown, PlayServerPluginMessage, PlayNamedSoundEffect, PlayDisconnect,
+    PlayEntityStatus, PlayExplosion, PlayUnloadChunk, PlayChangeGameState, PlayOpenHorseWindow,
+    PlayServerKeepAlive, PlayEffect, PlayParticle, PlayMapData, PlayTradeList, PlayEntityPosition,
+    PlayEntityPositionAndRotation, PlayEntityRotation, PlayEntityMovement, PlayServerVehicleMove,
+    PlayOpenBook, PlayOpenSignEditor, PlayCraftRecipeResponse, PlayServerPlayerAbilities,
+    PlayCombatEvent, PlayPlayerInfo, PlayFacePlayer, PlayServerPlayerPositionAndLook,
+    PlayDestroyEntities, PlayRemoveEntityEffect, PlayResourcePackSend, PlayEntityHeadLook,
+    PlaySelectAdvancementTab, PlayWorldBorder, PlayCamera, PlayServerHeldItemChange,
+    PlayUpdateViewPosition, PlayUpdateViewDistance, PlaySpawnPosition, PlayDisplayScoreboard,
+    PlayEntityMetadata, PlayAttachEntity, PlayEntityVelocity, PlaySetExperience, PlayUpdatehealth,
+    PlayScoreboardObjective, PlaySetPassengers, PlayTeams, PlayUpdateScore, PlayTimeUpdate,
+    PlayTitle, PlayEntitySoundEffect, PlaySoundEffect, PlayStopSound,
+    PlayerPlayerListHeaderAndFooter, PlayNbtQueryResponse, PlayCollectItem, PlayEntityTeleport,
+    PlayAdvancements, PlayEntityProperties, PlayEntityEffect, PlayDeclareRecipes, PlayTags,
+    PlayTeleportConfirm, PlayQueryBlockNbt, PlayQueryEntityNbt, PlaySetDifficulty,
+    PlayClientChatMessage, PlayClientStatus, PlayClientSettings, PlayClientTabComplete,
+    PlayClientWindowConfirmation, PlayClickWindowButton, PlayClickWindow, PlayClientCloseWindow,
+    PlayClientPluginMessage, PlayEditBook, PlayClientKeepAlive, PlayLockDifficulty,
+    PlayPlayerPosition, PlayClientPlayerPositionAndRotation, PlayPlayerRotation,
+    PlayPlayerMovement, PlayClientVehicleMove, PlaySteerBoat, PlayPickItem, PlayCraftRecipeRequest,
+    PlayPlayerDigging, PlayEntityAction, PlaySteerVehicle, PlayNameItem, PlayResourcePackStatus,
+    PlayAdvancementTab, PlaySelectTrade, PlaySetBeaconEffect, PlayClientHeldItemChange,
+    PlayUpdateCommandBlock, PlayUpdateCommandBlockMinecart, PlayUpdateJigsawBlock,
+    PlayCreativeInventoryAction, PlayUpdateStructureBlock, PlayUpdateSign, PlayClientAnimation,
+    PlaySpectate, PlayBlockPlacement, PlayUseItem
+);
+
+const DEFAULT_FLYING_SPEED: f32 = 0.05;
+const DEFAULT_WALKING_SPEED: f32 = 0.1;
+
+const LEVEL_TYPE_DEFAULT: &str = "default";
+const LEVEL_TYPE_FLAT: &str = "flat";
+const LEVEL_TYPE_DEBUG: &str = "debug_all_block_states";
+
+const OVERWORLD: &str = "minecraft:overworld";
+const THE_NETHER: &str = "minecraft:the_nether";
+const THE_END: &str = "minecraft:the_end";
+
+fn level_type_578(is_debug: bool, is_flat: bool) -> String {
+    if is_debug {
+        LEVEL_TYPE_DEBUG
+    } else if is_flat {
+        LEVEL_TYPE_FLAT
+    } else {
+        LEVEL_TYPE_DEFAULT
+    }.to_owned()
+}
+
+fn dimension_name(dimension: &v578::Dimension) -> &'static str {
+    use v578::Dimension::*;
+    match dimension {
+        Nether => THE_NETHER,
+        Overworld => OVERWORLD,
+        End => THE_END,
+    }
+}
+
+// 1.15 only knows the vanilla dimensions, so anything else is shown like the overworld
+fn dimension_578(dimension: &NamedNbtTag, world_name: &str) -> v578::Dimension {
+    let effects = compound_string(&dimension.root.payload, "effects").unwrap_or(world_name);
+    match effects {
+        THE_NETHER => v578::Dimension::Nether,
+        THE_END => v578::Dimension::End,
+        _ => v578::Dimension::Overworld,
+    }
+}
+
+fn compound_get<'a>(tag: &'a Tag, name: &str) -> Option<&'a Tag> {
+    match tag {
+        Tag::Compound(entries) => entries.iter().find(|entry| entry.name == name).map(|entry| &entry.payload),
+        _ => None,
+    }
+}
+
+fn compound_string<'a>(tag: &'a Tag, name: &str) -> Option<&'a str> {
+    match compound_get(tag, name)? {
+        Tag::String(value) => Some(value.as_str()),
+        _ => None,
+    }
+}
+
+fn dimension_type_entries(codec: &NamedNbtTag) -> impl Iterator<Item = &Tag> {
+    let entries = compound_get(&codec.root.payload, "minecraft:dimension_type")
+        .and_then(|registry| compound_get(registry, "value"));
+    let entries: &[Tag] = match entries {
+        Some(Tag::List(entries)) => entries.as_slice(),
+        _ => &[],
+    };
+    entries.iter()
+}
+
+fn dimension_type_names(codec: &NamedNbtTag) -> impl Iterator<Item = &str> {
+    dimension_type_entries(codec).filter_map(|entry| compound_string(entry, "name"))
+}
+
+fn compound(entries: Vec<NamedTag>) -> Tag {
+    Tag::Compound(entries)
+}
+
+fn string(value: &str) -> Tag {
+    Tag::String(value.to_owned())
+}
+
+fn flag(value: bool) -> Tag {
+    Tag::Byte(value as i8)
+}
+
+struct DimensionType {
+    name: &'static str,
+    piglin_safe: bool,
+    natural: bool,
+    ambient_light: f32,
+    fixed_time: Option<i64>,
+    infiniburn: &'static str,
+    respawn_anchor_works: bool,
+    has_skylight: bool,
+    bed_works: bool,
+    has_raids: bool,
+    logical_height: i32,
+    coordinate_scale: f64,
+    ultrawarm: bool,
+    has_ceiling: bool,
+}
+
+// the vanilla 1.16.3 values
+const DIMENSION_TYPES: [DimensionType; 3] = [
+    DimensionType {
+        name: OVERWORLD,
+        piglin_safe: false,
+        natural: true,
+        ambient_light: 0.0,
+        fixed_time: None,
+        infiniburn: "minecraft:infiniburn_overworld",
+        respawn_anchor_works: false,
+        has_skylight: true,
+        bed_works: true,
+        has_raids: true,
+        logical_height: 256,
+        coordinate_scale: 1.0,
+        ultrawarm: false,
+        has_ceiling: false,
+    },
+    DimensionType {
+        name: THE_NETHER,
+        piglin_safe: true,
+        natural: false,
+        ambient_light: 0.1,
+        fixed_time: Some(18000),
+        infiniburn: "minecraft:infiniburn_nether",
+        respawn_anchor_works: true,
+        has_skylight: false,
+        bed_works: false,
+        has_raids: false,
+        logical_height: 128,
+        coordinate_scale: 8.0,
+        ultrawarm: true,
+        has_ceiling: true,
+    },
+    DimensionType {
+        name: THE_END,
+        piglin_safe: false,
+        natural: false,
+        ambient_light: 0.0,
+        fixed_time: Some(6000),
+        infiniburn: "minecraft:infiniburn_end",
+        respawn_anchor_works: false,
+        has_skylight: false,
+        bed_works: false,
+        has_raids: true,
+        logical_height: 256,
+        coordinate_scale: 1.0,
+        ultrawarm: false,
+        has_ceiling: false,
+    },
+];
+
+impl DimensionType {
+    fn element(&self) -> Tag {
+        let mut out = vec![
+            flag(self.piglin_safe).with_name("piglin_safe"),
+            flag(self.natural).with_name("natural"),
+            Tag::Float(self.ambient_light).with_name("ambient_light"),
+            string(self.infiniburn).with_name("infiniburn"),
+            flag(self.respawn_anchor_works).with_name("respawn_anchor_works"),
+            flag(self.has_skylight).with_name("has_skylight"),
+            flag(self.bed_works).with_name("bed_works"),
+            string(self.name).with_name("effects"),
+            flag(self.has_raids).with_name("has_raids"),
+            Tag::Int(self.logical_height).with_name("logical_height"),
+            Tag::Double(self.coordinate_scale).with_name("coordinate_scale"),
+            flag(self.ultrawarm).with_name("ultrawarm"),
+            flag(self.has_ceiling).with_name("has_ceiling"),
+        ];
+        if let Some(time) = self.fixed_time {
+            out.push(Tag::Long(time).with_name("fixed_time"));
+        }
+        compound(out)
+    }
+}
+
+// every biome which exists in 1.15, by its numeric id (which 1.16 kept)
+const BIOMES: [(i32, &str); 74] = [
+    (0, "ocean"), (1, "plains"), (2, "desert"), (3, "mountains"), (4, "forest"), (5, "taiga"),
+    (6, "swamp"), (7, "river"), (8, "nether_wastes"), (9, "the_end"), (10, "frozen_ocean"),
+    (11, "frozen_river"), (12, "snowy_tundra"), (13, "snowy_mountains"), (14, "mushroom_fields"),
+    (15, "mushroom_field_shore"), (16, "beach"), (17, "desert_hills"), (18, "wooded_hills"),
+    (19, "taiga_hills"), (20, "mountain_edge"), (21, "jungle"), (22, "jungle_hills"),
+    (23, "jungle_edge"), (24, "deep_ocean"), (25, "stone_shore"), (26, "snowy_beach"),
+    (27, "birch_forest"), (28, "birch_forest_hills"), (29, "dark_forest"), (30, "snowy_taiga"),
+    (31, "snowy_taiga_hills"), (32, "giant_tree_taiga"), (33, "giant_tree_taiga_hills"),
+    (34, "wooded_mountains"), (35, "savanna"), (36, "savanna_plateau"), (37, "badlands"),
+    (38, "wooded_badlands_plateau"), (39, "badlands_plateau"), (40, "small_end_islands"),
+    (41, "end_midlands"), (42, "end_highlands"), (43, "end_barrens"), (44, "warm_ocean"),
+    (45, "lukewarm_ocean"), (46, "cold_ocean"), (47, "deep_warm_ocean"),
+    (48, "deep_lukewarm_ocean"), (49, "deep_cold_ocean"), (50, "deep_frozen_ocean"),
+    (127, "the_void"), (129, "sunflower_plains"), (130, "desert_lakes"),
+    (131, "gravelly_mountains"), (132, "flower_forest"), (133, "taiga_mountains"),
+    (134, "swamp_hills"), (140, "ice_spikes"), (149, "modified_jungle"),
+    (151, "modified_jungle_edge"), (155, "tall_birch_forest"), (156, "tall_birch_hills"),
+    (157, "dark_forest_hills"), (158, "snowy_taiga_mountains"), (160, "giant_spruce_taiga"),
+    (161, "giant_spruce_taiga_hills"), (162, "modified_gravelly_mountains"),
+    (163, "shattered_savanna"), (164, "shattered_savanna_plateau"), (165, "eroded_badlands"),
+    (166, "modified_wooded_badlands_plateau"), (167, "modified_badlands_plateau"),
+    (168, "bamboo_jungle"),
+];
+
+// the client only needs the biome ids to exist, so every biome looks like plains
+fn biome_element() -> Tag {
+    compound(vec![
+        string("rain").with_name("precipitation"),
+        compound(vec![
+            Tag::Int(7907327).with_name("sky_color"),
+            Tag::Int(329011).with_name("water_fog_color"),
+            Tag::Int(12638463).with_name("fog_color"),
+            Tag::Int(4159204).with_name("water_color"),
+        ]).with_name("effects"),
+        Tag::Float(0.125).with_name("depth"),
+        Tag::Float(0.8).with_name("temperature"),
+        Tag::Float(0.05).with_name("scale"),
+        Tag::Float(0.4).with_name("downfall"),
+        string("plains").with_name("category"),
+    ])
+}
+
+fn registry(name: &str, entries: Vec<Tag>) -> NamedTag {
+    compound(vec![
+        string(name).with_name("type"),
+        Tag::List(entries).with_name("value"),
+    ]).with_name(name)
+}
+
+fn registry_entry(name: &str, id: i32, element: Tag) -> Tag {
+    compound(vec![
+        string(name).with_name("name"),
+        Tag::Int(id).with_name("id"),
+        element.with_name("element"),
+    ])
+}
+
+/// A dimension codec with the three vanilla dimension types, and every biome id used by 1.15.
+pub fn default_dimension_codec() -> NamedNbtTag {
+    let dimension_types = DIMENSION_TYPES
+        .iter()
+        .enumerate()
+        .map(|(id, dimension)| registry_entry(dimension.name, id as i32, dimension.element()))
+        .collect();
+
+    let biomes = BIOMES
+        .iter()
+        .map(|(id, name)| {
+            let mut full_name = String::from("minecraft:");
+            full_name.push_str(name);
+            registry_entry(full_name.as_str(), *id, biome_element())
+        })
+        .collect();
+
+    NamedNbtTag {
+        root: compound(vec![
+            registry("minecraft:dimension_type", dimension_types),
+            registry("minecraft:worldgen/biome", biomes),
+        ]).with_name(""),
+    }
+}
+
+const SECTION_VOLUME: usize = 4096;
+const BIOMES_VOLUME: usize = 1024;
+const MAX_PALETTE_BITS: u8 = 8;
+const GLOBAL_PALETTE_BITS_578: u8 = 14;
+const GLOBAL_PALETTE_BITS_753: u8 = 15;
+
+// 1.15 packs block states back to back, so one may span two longs. 1.16 only puts as many block
+// states into a long as fit whole.
+#[derive(Clone, Copy)]
+enum Packing {
+    Spanning,
+    Aligned,
+}
+
+impl Packing {
+    // the long index and bit offset of entry `index`
+    fn locate(self, index: usize, bits: usize) -> (usize, usize) {
+        match self {
+            Packing::Spanning => ((index * bits) / 64, (index * bits) % 64),
+            Packing::Aligned => {
+                let per_long = 64 / bits;
+                (index / per_long, (index % per_long) * bits)
+            }
+        }
+    }
+
+    fn longs(self, bits: usize) -> usize {
+        match self {
+            Packing::Spanning => (SECTION_VOLUME * bits).div_ceil(64),
+            Packing::Aligned => {
+                let per_long = 64 / bits;
+                SECTION_VOLUME.div_ceil(per_long)
+            }
+        }
+    }
+
+    fn unpack(self, longs: &[i64], bits: usize) -> Option<Vec<u64>> {
+        let mask = (1u64 << bits) - 1;
+        (0..SECTION_VOLUME)
+            .map(|index| {
+                let (at, offset) = self.locate(index, bits);
+                let mut value = (*longs.get(at)? as u64) >> offset;
+                if offset + bits > 64 {
+                    value |= (*longs.get(at + 1)? as u64) << (64 - offset);
+                }
+                Some(value & mask)
+            })
+            .collect()
+    }
+
+    fn pack(self, values: &[u64], bits: usize) -> Vec<i64> {
+        let mut out = vec![0u64; self.longs(bits)];
+        for (index, value) in values.iter().enumerate() {
+            let (at, offset) = self.locate(index, bits);
+            out[at] |= value << offset;
+            if offset + bits > 64 {
+                out[at + 1] |= value >> (64 - offset);
+            }
+        }
+        out.into_iter().map(|long| long as i64).collect()
+    }
+}
+
+fn repack_sections(
+    id: Id,
+    primary_bit_mask: VarInt,
+    data: &[u8],
+    from: Packing,
+    to: Packing,
+    global_palette_bits: u8,
+) -> TranslateResult<Vec<u8>> {
+    let deserialize_failed = move |err| TranslateErr::DeserializeFailed(id, err);
+    let serialize_failed = move |err| TranslateErr::SerializeFailed(id, err);
+
+    let mut out = BytesSerializer::with_capacity(data.len() + 512);
+    let mut data = data;
+    for _ in 0..(primary_bit_mask.0 as u32).count_ones() {
+        let Deserialized { value: block_count, data: rest } = i16::mc_deserialize(data).map_err(deserialize_failed)?;
+        let Deserialized { value: bits, data: rest } = u8::mc_deserialize(rest).map_err(deserialize_failed)?;
+        if bits == 0 || bits > 32 {
+            return Err(TranslateErr::Unrepresentable(id, "chunk section has a bad bits per block"));
+        }
+
+        let (palette, rest) = if bits <= MAX_PALETTE_BITS {
+            let Deserialized { value, data } = <CountedArray<VarInt, VarInt>>::mc_deserialize(rest).map_err(deserialize_failed)?;
+            (Some(value), data)
+        } else {
+            (None, rest)
+        };
+        let Deserialized { value: longs, data: rest } = <CountedArray<i64, VarInt>>::mc_deserialize(rest).map_err(deserialize_failed)?;
+        data = rest;
+
+        let values = from.unpack(&longs, bits as usize)
+            .ok_or(TranslateErr::Unrepresentable(id, "chunk section has too little block data"))?;
+        let bits = if palette.is_some() { bits } else { global_palette_bits };
+        if values.iter().any(|value| value >> bits != 0) {
+            return Err(TranslateErr::Unrepresentable(id, "block state id does not fit the global palette"));
+        }
+
+        out.serialize_other(&block_count).map_err(serialize_failed)?;
+        out.serialize_byte(bits).map_err(serialize_failed)?;
+        if let Some(palette) = palette {
+            out.serialize_other(&palette).map_err(serialize_failed)?;
+        }
+        let longs: CountedArray<i64, VarInt> = to.pack(values.as_slice(), bits as usize).into();
+        out.serialize_other(&longs).map_err(serialize_failed)?;
+    }
+
+    out.serialize_bytes(data).map_err(serialize_failed)?;
+    Ok(out.into_bytes())
+}
+
+fn chunk_data_578(id: Id, chunk: v753::ChunkData) -> TranslateResult<v578::ChunkData> {
+    let biomes = match chunk.biomes {
+        Some(biomes) => {
+            if biomes.len() != BIOMES_VOLUME {
+                return Err(TranslateErr::Unrepresentable(id, "1.15 chunks have exactly 1024 biomes"));
+            }
+            let mut out = Box::new([0i32; BIOMES_VOLUME]);
+            for (to, from) in out.iter_mut().zip(biomes.iter()) {
+                *to = from.0;
+            }
+            Some(out)
+        }
+        None => None,
+    };
+
+    let data = repack_sections(
+        id,
+        chunk.primary_bit_mask,
+        chunk.data.as_slice(),
+        Packing::Aligned,
+        Packing::Spanning,
+        GLOBAL_PALETTE_BITS_578,
+    )?;
+
+    Ok(v578::ChunkData {
+        position: chunk.position,
+        primary_bit_mask: chunk.primary_bit_mask,
+        heightmaps: chunk.heightmaps,
+        biomes,
+        data: data.into(),
+        block_entities: chunk.block_entities,
+    })
+}
+
+fn chunk_data_753(id: Id, chunk: v578::ChunkData) -> TranslateResult<v753::ChunkData> {
+    let biomes = chunk.biomes.map(|biomes| biomes.iter().map(|biome| VarInt(*biome)).collect::<Vec<_>>().into());
+    let data = repack_sections(
+        id,
+        chunk.primary_bit_mask,
+        chunk.data.as_slice(),
+        Packing::Spanning,
+        Packing::Aligned,
+        GLOBAL_PALETTE_BITS_753,
+    )?;
+
+    Ok(v753::ChunkData {
+        position: chunk.position,
+        primary_bit_mask: chunk.primary_bit_mask,
+        heightmaps: chunk.heightmaps,
+        biomes,
+        data: data.into(),
+        block_entities: chunk.block_entities,
+    })
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::protocol::{HasPacketBody, Packet, TestRandom};
+
+    fn translator() -> Translator {
+        Translator::default()
+    }
+
+    fn round_trip_753(packet: Packet753) {
+        let translated = translator().to_578(packet.clone()).expect("translates to 578");
+        assert_eq!(translator().to_753(translated).expect("translates back to 753"), packet);
+    }
+
+    fn round_trip_578(packet: Packet578) {
+        let translated = translator().to_753(packet.clone()).expect("translates to 753");
+        assert_eq!(translator().to_578(translated).expect("translates back to 578"), packet);
+    }
+
+    #[test]
+    fn test_same_shape_round_trip() {
+        for _ in 0..16 {
+            round_trip_753(Packet753::LoginStart(v753::LoginStartSpec::test_gen_random()));
+            round_trip_753(Packet753::PlayBlockChange(v753::PlayBlockChangeSpec::test_gen_random()));
+            round_trip_753(Packet753::PlaySpawnEntity(v753::PlaySpawnEntitySpec::test_gen_random()));
+            round_trip_753(Packet753::PlayEntityMetadata(v753::PlayEntityMetadataSpec::test_gen_random()));
+            round_trip_753(Packet753::PlayClientChatMessage(v753::PlayClientChatMessageSpec::test_gen_random()));
+            round_trip_578(Packet578::PlayWindowItems(v578::PlayWindowItemsSpec::test_gen_random()));
+            round_trip_578(Packet578::PlayTeams(v578::PlayTeamsSpec::test_gen_random()));
+            round_trip_578(Packet578::PlayPlayerPosition(v578::PlayPlayerPositionSpec::test_gen_random()));
+        }
+    }
+
+    #[test]
+    fn test_packets_only_in_one_version() {
+        let packet = Packet753::PlaySetRecipeBookState(v753::PlaySetRecipeBookStateSpec::test_gen_random());
+        let id = packet.id();
+        assert!(matches!(translator().to_578(packet), Err(TranslateErr::Untranslatable(at)) if at == id));
+
+        let packet = Packet578::PlaySpawnWeatherEntity(v578::PlaySpawnWeatherEntitySpec::test_gen_random());
+        assert!(matches!(translator().to_753(packet), Err(TranslateErr::Untranslatable(_))));
+    }
+
+    #[test]
+    fn test_same_shape_with_new_values() {
+        let empty = v753::RecipeIngredient { items: Vec::new().into() };
+        let packet = Packet753::PlayDeclareRecipes(v753::PlayDeclareRecipesSpec {
+            recipes: vec![v753::RecipeSpec {
+                recipe: v753::Recipe::Smithing(v753::RecipeSmithingSpec {
+                    base: empty.clone(),
+                    addition: empty,
+                    result: None,
+                }),
+                id: "minecraft:netherite_sword_smithing".to_owned(),
+            }].into(),
+        });
+        assert!(matches!(translator().to_578(packet), Err(TranslateErr::DeserializeFailed(_, _))));
+    }
+
+    #[test]
+    fn test_login_success() {
+        let uuid = UUID4::from(0x069a79f4_44e9_4726_a5be_fca90e38aaf5u128);
+        let packet = Packet753::LoginSuccess(v753::LoginSuccessSpec { uuid, username: "Notch".to_owned() });
+        match translator().to_578(packet.clone()).unwrap() {
+            Packet578::LoginSuccess(body) => assert_eq!(body.uuid_string, "069a79f4-44e9-4726-a5be-fca90e38aaf5"),
+            other => panic!("expected login success, got {:?}", other),
+        }
+        round_trip_753(packet);
+
+        let bad = Packet578::LoginSuccess(v578::LoginSuccessSpec {
+            uuid_string: "not a uuid".to_owned(),
+            username: "Notch".to_owned(),
+        });
+        assert!(matches!(translator().to_753(bad), Err(TranslateErr::Unrepresentable(_, _))));
+    }
+
+    #[test]
+    fn test_chat_sender() {
+        let packet = Packet578::PlayServerChatMessage(v578::PlayServerChatMessageSpec {
+            message: crate::types::Chat::from_text("hello"),
+            position: v578::ChatPosition::ChatBox,
+        });
+        match translator().to_753(packet.clone()).unwrap() {
+            Packet753::PlayServerChatMessage(body) => assert_eq!(body.sender, UUID4::from(0u128)),
+            other => panic!("expected chat message, got {:?}", other),
+        }
+        round_trip_578(packet);
+    }
+
+    fn join_game_578(dimension: v578::Dimension, level_type: &str) -> Packet578 {
+        Packet578::PlayJoinGame(v578::PlayJoinGameSpec {
+            entity_id: 42,
+            gamemode: v578::GameMode::Creative,
+            dimension,
+            hashed_seed: 1234,
+            max_players: 20,
+            level_type: level_type.to_owned(),
+            view_distance: VarInt(10),
+            reduced_debug_info: false,
+            enable_respawn_screen: true,
+        })
+    }
+
+    #[test]
+    fn test_join_game() {
+        let translated = translator().to_753(join_game_578(v578::Dimension::Nether, LEVEL_TYPE_FLAT)).unwrap();
+        match &translated {
+            Packet753::PlayJoinGame(body) => {
+                assert_eq!(body.world_name, THE_NETHER);
+                assert_eq!(compound_string(&body.dimension.root.payload, "effects"), Some(THE_NETHER));
+                assert_eq!(&body.dimension_codec, translator().dimension_codec());
+                assert_eq!(body.worlds.len(), 3);
+                assert!(body.is_flat);
+                assert!(!body.is_debug);
+            }
+            other => panic!("expected join game, got {:?}", other),
+        }
+
+        // the codec survives being written and read
+        let mut out = BytesSerializer::default();
+        translated.mc_serialize_body(&mut out).unwrap();
+        let bytes = out.into_bytes();
+        assert_eq!(Packet753::mc_deserialize_body(translated.id(), bytes.as_slice()).unwrap(), translated);
+
+        round_trip_578(join_game_578(v578::Dimension::Nether, LEVEL_TYPE_FLAT));
+        round_trip_578(join_game_578(v578::Dimension::End, LEVEL_TYPE_DEFAULT));
+        round_trip_578(join_game_578(v578::Dimension::Overworld, LEVEL_TYPE_DEBUG));
+    }
+
+    #[test]
+    fn test_missing_dimension_type() {
+        let translator = Translator::with_dimension_codec(NamedNbtTag {
+            root: compound(Vec::new()).with_name(""),
+        });
+        assert!(matches!(
+            translator.to_753(join_game_578(v578::Dimension::Overworld, LEVEL_TYPE_DEFAULT)),
+            Err(TranslateErr::Unrepresentable(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_respawn() {
+        let packet = Packet578::PlayRespawn(v578::PlayRespawnSpec {
+            dimension: v578::Dimension::End,
+            hashed_seed: -5,
+            gamemode: v578::GameMode::Survival,
+            level_type: LEVEL_TYPE_DEFAULT.to_owned(),
+        });
+        match translator().to_753(packet.clone()).unwrap() {
+            Packet753::PlayRespawn(body) => {
+                assert_eq!(body.world_name, THE_END);
+                assert_eq!(body.previous_gamemode, v753::GameMode::Survival);
+            }
+            other => panic!("expected respawn, got {:?}", other),
+        }
+        round_trip_578(packet);
+    }
+
+    fn record_578(x: u8, y: u8, z: u8, block_id: i32) -> v578::MultiBlockChangeRecord {
+        v578::MultiBlockChangeRecord {
+            horizontal_position: v578::BlockChangeHorizontalPosition { rel_x: x, rel_z: z },
+            y_coordinate: y,
+            block_id: VarInt(block_id),
+        }
+    }
+
+    #[test]
+    fn test_multi_block_change() {
+        let packet = Packet578::PlayMultiBlockChange(v578::PlayMultiBlockChangeSpec {
+            chunk: ChunkPosition { x: -3, z: 7 },
+            changes: vec![record_578(1, 70, 2, 9), record_578(15, 79, 0, 1)].into(),
+        });
+        match translator().to_753(packet.clone()).unwrap() {
+            Packet753::PlayMultiBlockChange(body) => {
+                assert_eq!((body.chunk.x, body.chunk.y, body.chunk.z), (-3, 4, 7));
+                assert_eq!(body.blocks[0].block_id, 9);
+                assert_eq!(body.blocks[1].rel_position.y, 15);
+            }
+            other => panic!("expected multi block change, got {:?}", other),
+        }
+        round_trip_578(packet);
+
+        let spans_sections = Packet578::PlayMultiBlockChange(v578::PlayMultiBlockChangeSpec {
+            chunk: ChunkPosition { x: 0, z: 0 },
+            changes: vec![record_578(0, 15, 0, 1), record_578(0, 16, 0, 1)].into(),
+        });
+        assert!(matches!(translator().to_753(spans_sections), Err(TranslateErr::Unrepresentable(_, _))));
+
+        let too_high = Packet753::PlayMultiBlockChange(v753::PlayMultiBlockChangeSpec {
+            chunk: v753::ChunkSectionPosition { x: 0, y: 16, z: 0 },
+            not_trust_edges: false,
+            blocks: vec![v753::MultiBlockChangeRecord { block_id: 1, rel_position: (0, 0, 0).into() }].into(),
+        });
+        assert!(matches!(translator().to_578(too_high), Err(TranslateErr::Unrepresentable(_, _))));
+    }
+
+    #[test]
+    fn test_entity_equipment() {
+        let packet = Packet578::PlayEntityEquipment(v578::PlayEntityEquiptmentSpec {
+            entity_id: VarInt(3),
+            slot: v578::EquipmentSlot::ArmorHelmet,
+            item: None,
+        });
+        round_trip_578(packet);
+
+        let several = Packet753::PlayEntityEquipment(v753::PlayEntityEquiptmentSpec {
+            entity_id: VarInt(3),
+            equipment: vec![
+                v753::EntityEquipmentEntry { slot: v753::EquipmentSlot::MainHand, item: None },
+                v753::EntityEquipmentEntry { slot: v753::EquipmentSlot::OffHand, item: None },
+            ].into(),
+        });
+        assert!(matches!(translator().to_578(several), Err(TranslateErr::Unrepresentable(_, _))));
+    }
+
+    // one paletted section, and one section using the global palette
+    fn sections(packing: Packing, global_palette_bits: u8) -> (Vec<u8>, Vec<u64>, Vec<u64>) {
+        let paletted: Vec<u64> = (0..SECTION_VOLUME as u64).map(|i| (i * 7) % 20).collect();
+        let direct: Vec<u64> = (0..SECTION_VOLUME as u64).map(|i| (i * 31) % 10000).collect();
+
+        let mut out = BytesSerializer::default();
+        out.serialize_other(&4096i16).unwrap();
+        out.serialize_byte(5).unwrap();
+        let palette: CountedArray<VarInt, VarInt> = (0..20).map(|i| VarInt(i * 100)).collect::<Vec<_>>().into();
+        out.serialize_other(&palette).unwrap();
+        let longs: CountedArray<i64, VarInt> = packing.pack(paletted.as_slice(), 5).into();
+        out.serialize_other(&longs).unwrap();
+
+        out.serialize_other(&4096i16).unwrap();
+        out.serialize_byte(global_palette_bits).unwrap();
+        let longs: CountedArray<i64, VarInt> = packing.pack(direct.as_slice(), global_palette_bits as usize).into();
+        out.serialize_other(&longs).unwrap();
+
+        (out.into_bytes(), paletted, direct)
+    }
+
+    fn chunk_578() -> Packet578 {
+        let (data, _, _) = sections(Packing::Spanning, GLOBAL_PALETTE_BITS_578);
+        let mut biomes = Box::new([0i32; BIOMES_VOLUME]);
+        for (i, biome) in biomes.iter_mut().enumerate() {
+            *biome = (i % 50) as i32;
+        }
+
+        Packet578::PlayChunkData(v578::PlayChunkDataWrapper {
+            data: v578::ChunkData {
+                position: ChunkPosition { x: 4, z: -9 },
+                primary_bit_mask: VarInt(0b1000_0001),
+                heightmaps: NamedNbtTag { root: compound(Vec::new()).with_name("") },
+                biomes: Some(biomes),
+                data: data.into(),
+                block_entities: Vec::new(),
+            },
+        })
+    }
+
+    #[test]
+    fn test_packing() {
+        let values: Vec<u64> = (0..SECTION_VOLUME as u64).map(|i| i % 4096).collect();
+        for packing in &[Packing::Spanning, Packing::Aligned] {
+            for bits in 4..=15 {
+                let masked: Vec<u64> = values.iter().map(|v| v & ((1 << bits) - 1)).collect();
+                let packed = packing.pack(masked.as_slice(), bits);
+                assert_eq!(packed.len(), packing.longs(bits));
+                assert_eq!(packing.unpack(packed.as_slice(), bits).unwrap(), masked);
+            }
+        }
+
+        // 12 entries of 5 bits fit in a long, so 1.16 needs more longs than 1.15
+        assert_eq!(Packing::Spanning.longs(5), 320);
+        assert_eq!(Packing::Aligned.longs(5), 342);
+    }
+
+    #[test]
+    fn test_chunk_data() {
+        let packet = chunk_578();
+        let translated = translator().to_753(packet.clone()).unwrap();
+        let (expected, _, _) = sections(Packing::Aligned, GLOBAL_PALETTE_BITS_753);
+        match &translated {
+            Packet753::PlayChunkData(body) => {
+                assert_eq!(body.data.data.as_slice(), expected.as_slice());
+                let biomes = body.data.biomes.as_ref().expect("full chunk");
+                assert_eq!(biomes.len(), BIOMES_VOLUME);
+                assert_eq!(biomes[49], VarInt(49));
+            }
+            other => panic!("expected chunk data, got {:?}", other),
+        }
+
+        assert_eq!(translator().to_578(translated).unwrap(), packet);
+    }
+
+    #[test]
+    fn test_chunk_data_global_palette_overflow() {
+        let mut out = BytesSerializer::default();
+        out.serialize_other(&1i16).unwrap();
+        out.serialize_byte(GLOBAL_PALETTE_BITS_753).unwrap();
+        let mut values = vec![0u64; SECTION_VOLUME];
+        values[100] = 1 << GLOBAL_PALETTE_BITS_578;
+        let longs: CountedArray<i64, VarInt> = Packing::Aligned.pack(values.as_slice(), 15).into();
+        out.serialize_other(&longs).unwrap();
+
+        let packet = Packet753::PlayChunkData(v753::PlayChunkDataWrapper {
+            data: v753::ChunkData {
+                position: ChunkPosition { x: 0, z: 0 },
+                primary_bit_mask: VarInt(1),
+                heightmaps: NamedNbtTag { root: compound(Vec::new()).with_name("") },
+                biomes: None,
+                data: out.into_bytes().into(),
+                block_entities: Vec::new(),
+            },
+        });
+        assert!(matches!(translator().to_578(packet), Err(TranslateErr::Unrepresentable(_, _))));
+    }
+}
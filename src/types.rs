@@ -16,6 +16,10 @@ impl Serialize for bool {
     fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
         to.serialize_byte(if *self { 1 } else { 0 })
     }
+
+    fn serialized_len(&self) -> usize {
+        1
+    }
 }
 
 impl Deserialize for bool {
@@ -42,6 +46,10 @@ macro_rules! def_primitive {
                 let data = ProtoByteOrder::$write(*self);
                 to.serialize_bytes(&data)
             }
+
+            fn serialized_len(&self) -> usize {
+                core::mem::size_of::<$nam>()
+            }
         }
 
         impl Deserialize for $nam {
@@ -102,6 +110,17 @@ macro_rules! def_varnum {
 
                 to.serialize_bytes(&out[..byte_idx])
             }
+
+            fn serialized_len(&self) -> usize {
+                let mut v: $working_type = self.0 as $working_type;
+                let mut len = 1;
+                while v > 0x7F {
+                    v >>= 7;
+                    len += 1;
+                }
+
+                len
+            }
         }
 
         impl Deserialize for $nam {
@@ -183,17 +202,83 @@ impl Serialize for String {
         to.serialize_other(&VarInt(self.len() as i32))?;
         to.serialize_bytes(self.as_bytes())
     }
+
+    fn serialized_len(&self) -> usize {
+        self.as_str().serialized_len()
+    }
 }
 
 impl Deserialize for String {
     fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        deserialize_string_limited("string", crate::limits::current().max_string_chars, data)
+    }
+}
+
+pub(crate) fn deserialize_string_limited<'a>(what: &'static str, max_chars: usize, data: &'a [u8]) -> DeserializeResult<'a, String> {
+    take_string_bytes(what, max_chars, data)?.try_map(move |taken| {
+        let out = String::from_utf8(taken.to_vec()).map_err(DeserializeErr::BadStringEncoding)?;
+        crate::limits::check_chars(what, out.as_str(), max_chars)?;
+        Ok(out)
+    })
+}
+
+fn take_string_bytes<'a>(what: &'static str, max_chars: usize, data: &'a [u8]) -> DeserializeResult<'a, &'a [u8]> {
+    VarInt::mc_deserialize(data)?.and_then(move |length, rest| {
+        if length.0 < 0 {
+            Err(DeserializeErr::NegativeLength(length))
+        } else {
+            crate::limits::check_string_bytes(what, length.0 as usize, max_chars)?;
+            take(length.0 as usize, rest)
+        }
+    })
+}
+
+// BORROWED STRING AND BYTES
+impl Serialize for &'_ str {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        to.serialize_other(&VarInt(self.len() as i32))?;
+        to.serialize_bytes(self.as_bytes())
+    }
+
+    fn serialized_len(&self) -> usize {
+        VarInt(self.len() as i32).serialized_len() + self.len()
+    }
+}
+
+impl<'a> BorrowDeserialize<'a> for &'a str {
+    fn mc_deserialize_borrowed(data: &'a [u8]) -> DeserializeResult<'a, Self> {
+        deserialize_str_limited("string", crate::limits::current().max_string_chars, data)
+    }
+}
+
+pub(crate) fn deserialize_str_limited<'a>(what: &'static str, max_chars: usize, data: &'a [u8]) -> DeserializeResult<'a, &'a str> {
+    take_string_bytes(what, max_chars, data)?.try_map(move |taken| {
+        let out = core::str::from_utf8(taken).map_err(DeserializeErr::BadStrEncoding)?;
+        crate::limits::check_chars(what, out, max_chars)?;
+        Ok(out)
+    })
+}
+
+// the same as a CountedArray<u8, VarInt>
+impl Serialize for &'_ [u8] {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        to.serialize_other(&VarInt(self.len() as i32))?;
+        to.serialize_bytes(self)
+    }
+
+    fn serialized_len(&self) -> usize {
+        VarInt(self.len() as i32).serialized_len() + self.len()
+    }
+}
+
+impl<'a> BorrowDeserialize<'a> for &'a [u8] {
+    fn mc_deserialize_borrowed(data: &'a [u8]) -> DeserializeResult<'a, Self> {
         VarInt::mc_deserialize(data)?.and_then(move |length, rest| {
             if length.0 < 0 {
                 Err(DeserializeErr::NegativeLength(length))
             } else {
-                take(length.0 as usize, rest)?.try_map(move |taken| {
-                    String::from_utf8(taken.to_vec()).map_err(DeserializeErr::BadStringEncoding)
-                })
+                crate::limits::current().check_array_length(length.0 as usize)?;
+                take(length.0 as usize, rest)
             }
         })
     }
@@ -296,6 +381,59 @@ impl TestRandom for IntPosition {
     }
 }
 
+// position, as packed before 1.14 (x << 38 | y << 26 | z)
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub struct LegacyIntPosition {
+    pub x: i32,
+    pub y: i16,
+    pub z: i32,
+}
+
+impl Serialize for LegacyIntPosition {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        let x_raw = (self.x as u64) & 0x3FFFFFF;
+        let y_raw = (self.y as u64) & 0xFFF;
+        let z_raw = (self.z as u64) & 0x3FFFFFF;
+
+        let data = ProtoByteOrder::write_ulong((x_raw << 38) | (y_raw << 26) | z_raw);
+        to.serialize_bytes(&data)
+    }
+}
+
+impl Deserialize for LegacyIntPosition {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: raw, data } = ProtoByteOrder::read_ulong(data)?;
+        // shift each field to the top of an i64, then back down, to sign extend it
+        let raw = raw as i64;
+        let x = (raw >> 38) as i32;
+        let y = ((raw << 26) >> 52) as i16;
+        let z = ((raw << 38) >> 38) as i32;
+
+        Deserialized::ok(LegacyIntPosition { x, y, z }, data)
+    }
+}
+
+impl From<IntPosition> for LegacyIntPosition {
+    fn from(other: IntPosition) -> Self {
+        let IntPosition { x, y, z } = other;
+        Self { x, y, z }
+    }
+}
+
+impl From<LegacyIntPosition> for IntPosition {
+    fn from(other: LegacyIntPosition) -> Self {
+        let LegacyIntPosition { x, y, z } = other;
+        Self { x, y, z }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for LegacyIntPosition {
+    fn test_gen_random() -> Self {
+        IntPosition::test_gen_random().into()
+    }
+}
+
 // angle
 #[derive(Copy, Clone, PartialEq, Hash, Debug)]
 pub struct Angle {
@@ -734,15 +872,21 @@ impl<E, C> Serialize for CountedArray<E, C> where E: Serialize, C: ArrayCounter
         }
         Ok(())
     }
+
+    fn serialized_len(&self) -> usize {
+        C::from_count(self.data.len()).serialized_len() + self.data.iter().map(Serialize::serialized_len).sum::<usize>()
+    }
 }
 
 impl<E, C> Deserialize for CountedArray<E, C> where E: Deserialize, C: ArrayCounter {
     fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let start = data;
         let Deserialized { value: count, mut data } = C::mc_deserialize(data)?;
         let count = count.as_count();
-        let mut elems = Vec::with_capacity(count);
-        for _ in 0..count {
-            let Deserialized { value: elem, data: rest } = E::mc_deserialize(data)?;
+        let mut elems = Vec::with_capacity(crate::limits::array_capacity(count, data)?);
+        for index in 0..count {
+            let Deserialized { value: elem, data: rest } = E::mc_deserialize(data)
+                .map_err(|err| err.at_index(index, start.len() - data.len()))?;
             data = rest;
             elems.push(elem);
         }
@@ -847,6 +991,10 @@ impl Serialize for RemainingBytes {
     fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
         to.serialize_bytes(self.data.as_slice())
     }
+
+    fn serialized_len(&self) -> usize {
+        self.data.len()
+    }
 }
 
 impl Deserialize for RemainingBytes {
@@ -899,6 +1047,145 @@ impl TestRandom for RemainingBytes {
     }
 }
 
+// RemainingBytes without the copy, for decoding with BorrowDeserialize
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RemainingBytesRef<'a> {
+    pub data: &'a [u8],
+}
+
+impl Serialize for RemainingBytesRef<'_> {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        to.serialize_bytes(self.data)
+    }
+
+    fn serialized_len(&self) -> usize {
+        self.data.len()
+    }
+}
+
+impl<'a> BorrowDeserialize<'a> for RemainingBytesRef<'a> {
+    fn mc_deserialize_borrowed(data: &'a [u8]) -> DeserializeResult<'a, Self> {
+        Deserialized::ok(RemainingBytesRef { data }, &[])
+    }
+}
+
+impl<'a> From<&'a [u8]> for RemainingBytesRef<'a> {
+    fn from(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+}
+
+impl From<RemainingBytesRef<'_>> for RemainingBytes {
+    fn from(other: RemainingBytesRef<'_>) -> Self {
+        Self { data: other.data.to_vec() }
+    }
+}
+
+impl<'a> core::ops::Deref for RemainingBytesRef<'a> {
+    type Target = &'a [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+// a growable bit set sent as a VarInt-counted array of longs, bit n lives in word n / 64
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BitSet {
+    pub words: Vec<i64>,
+}
+
+impl BitSet {
+    pub fn from_words(words: Vec<i64>) -> Self {
+        Self { words }
+    }
+
+    pub fn get(&self, index: usize) -> bool {
+        self.words
+            .get(index / 64)
+            .map(move |word| (*word as u64) & (1 << (index % 64)) != 0)
+            .unwrap_or(false)
+    }
+
+    pub fn set(&mut self, index: usize, value: bool) {
+        let word_idx = index / 64;
+        if word_idx >= self.words.len() {
+            if !value {
+                return;
+            }
+            self.words.resize(word_idx + 1, 0);
+        }
+
+        let bit = 1u64 << (index % 64);
+        let word = self.words[word_idx] as u64;
+        self.words[word_idx] = (if value { word | bit } else { word & !bit }) as i64;
+    }
+
+    // number of bits that fit in the backing words (not the number of set bits)
+    pub fn bit_capacity(&self) -> usize {
+        self.words.len() * 64
+    }
+
+    pub fn highest_set_bit(&self) -> Option<usize> {
+        self.words.iter()
+            .enumerate()
+            .rev()
+            .find(move |(_, word)| **word != 0)
+            .map(move |(idx, word)| idx * 64 + 63 - (*word as u64).leading_zeros() as usize)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(move |word| *word == 0)
+    }
+}
+
+impl Serialize for BitSet {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        to.serialize_other(&VarInt(self.words.len() as i32))?;
+        for word in &self.words {
+            to.serialize_other(word)?;
+        }
+        Ok(())
+    }
+}
+
+impl Deserialize for BitSet {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: count, mut data } = VarInt::mc_deserialize(data)?;
+        if count.0 < 0 {
+            return Err(DeserializeErr::NegativeLength(count));
+        }
+
+        let count = count.0 as usize;
+        crate::limits::current().check_array_length(count)?;
+        if data.len() / 8 < count {
+            return Err(DeserializeErr::Eof);
+        }
+
+        let mut words = Vec::with_capacity(count);
+        for _ in 0..count {
+            let Deserialized { value: word, data: rest } = i64::mc_deserialize(data)?;
+            data = rest;
+            words.push(word);
+        }
+
+        Deserialized::ok(Self { words }, data)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for BitSet {
+    fn test_gen_random() -> Self {
+        let count: usize = rand::random::<usize>() % 4;
+        let mut words = Vec::with_capacity(count);
+        for _ in 0..count {
+            words.push(rand::random());
+        }
+
+        Self { words }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1065,6 +1352,18 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_legacy_position() {
+        test_type(LegacyIntPosition { x: 0, y: 0, z: 0 });
+        test_type(LegacyIntPosition { x: -12312, y: 32, z: 321312 });
+        test_type(LegacyIntPosition { x: 33554431, y: 2047, z: -33554432 });
+        test_type(LegacyIntPosition { x: -33554432, y: -2048, z: 33554431 });
+
+        let mut out = BytesSerializer::default();
+        LegacyIntPosition { x: 1, y: 2, z: 3 }.mc_serialize(&mut out).unwrap();
+        assert_eq!(out.into_bytes(), ((1u64 << 38) | (2 << 26) | 3).to_be_bytes().to_vec());
+    }
+
     #[cfg(feature = "std")]
     #[test]
     fn test_uuid() {
@@ -1073,6 +1372,161 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_bit_set() {
+        test_type(BitSet::default());
+        test_type(BitSet::from_words(alloc::vec![0x0F, -1]));
+
+        let mut bits = BitSet::default();
+        bits.set(0, true);
+        bits.set(70, true);
+        bits.set(200, false);
+        assert_eq!(bits.words, alloc::vec![1, 1 << 6]);
+        assert!(bits.get(0) && bits.get(70));
+        assert!(!bits.get(1) && !bits.get(200));
+        bits.set(0, false);
+        assert_eq!(bits.words, alloc::vec![0, 1 << 6]);
+        assert!(!bits.is_empty());
+        assert_eq!(bits.bit_capacity(), 128);
+        assert_eq!(bits.highest_set_bit(), Some(70));
+        assert_eq!(BitSet::from_words(alloc::vec![-1, 0]).highest_set_bit(), Some(63));
+        assert_eq!(BitSet::from_words(alloc::vec![0, 0]).highest_set_bit(), None);
+
+        let limits = crate::limits::DeserializeLimits { max_array_length: 1, ..Default::default() };
+        let mut out = BytesSerializer::default();
+        bits.mc_serialize(&mut out).unwrap();
+        let bytes = out.into_bytes();
+        assert!(matches!(
+            limits.scope(|| BitSet::mc_deserialize(bytes.as_slice())),
+            Err(DeserializeErr::LimitExceeded { what: "array", length: 2, max: 1 })
+        ));
+
+        let mut out = BytesSerializer::default();
+        VarInt(i32::MAX).mc_serialize(&mut out).unwrap();
+        assert!(matches!(
+            BitSet::mc_deserialize(out.into_bytes().as_slice()),
+            Err(DeserializeErr::LimitExceeded { what: "array", .. })
+        ));
+
+        let mut out = BytesSerializer::default();
+        bits.mc_serialize(&mut out).unwrap();
+        assert_eq!(out.into_bytes(), alloc::vec![2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x40]);
+    }
+
+    #[test]
+    fn test_string_limits() {
+        let mut out = BytesSerializer::default();
+        "a".repeat(crate::limits::MAX_STRING_CHARS).mc_serialize(&mut out).unwrap();
+        let max = out.into_bytes();
+        assert!(String::mc_deserialize(max.as_slice()).is_ok());
+
+        let mut out = BytesSerializer::default();
+        "a".repeat(crate::limits::MAX_STRING_CHARS + 1).mc_serialize(&mut out).unwrap();
+        let too_long = out.into_bytes();
+        assert!(matches!(
+            String::mc_deserialize(too_long.as_slice()),
+            Err(DeserializeErr::LimitExceeded { what: "string", length: 32768, max: 32767 })
+        ));
+
+        // rejected from the prefix alone, before waiting for the rest of the data
+        let mut out = BytesSerializer::default();
+        VarInt(i32::MAX).mc_serialize(&mut out).unwrap();
+        assert!(matches!(
+            String::mc_deserialize(out.into_bytes().as_slice()),
+            Err(DeserializeErr::LimitExceeded { what: "string", .. })
+        ));
+
+        let limits = crate::limits::DeserializeLimits { max_string_chars: 4, ..Default::default() };
+        assert!(matches!(
+            limits.scope(|| String::mc_deserialize(max.as_slice())),
+            Err(DeserializeErr::LimitExceeded { what: "string", length: 32767, max: 12 })
+        ));
+        assert!(crate::limits::DeserializeLimits::UNLIMITED.scope(|| String::mc_deserialize(too_long.as_slice())).is_ok());
+    }
+
+    #[test]
+    fn test_serialized_len() {
+        fn check<T: Serialize>(value: T) {
+            let mut out = BytesSerializer::default();
+            value.mc_serialize(&mut out).unwrap();
+            assert_eq!(value.serialized_len(), out.into_bytes().len());
+        }
+
+        for v in &[0, 1, 127, 128, 16383, 16384, 2097151, 2097152, i32::MAX, -1, i32::MIN] {
+            check(VarInt(*v));
+            check(VarLong(*v as i64));
+        }
+        check(VarLong(i64::MAX));
+        check(VarLong(i64::MIN));
+        check(true);
+        check(0u128);
+        check(1.5f32);
+        check("a".repeat(200));
+        check("é");
+        check(&[1u8, 2][..]);
+        check(RemainingBytes { data: vec![0; 3] });
+        check(Some(VarInt(300)));
+        check(<CountedArray<String, VarInt>>::from(vec!["a".to_owned(), "b".to_owned()]));
+        assert_eq!(VarInt(-1).serialized_len(), 5);
+        assert_eq!(VarLong(-1).serialized_len(), 10);
+    }
+
+    #[test]
+    fn test_borrowed_str_and_bytes() {
+        let mut out = BytesSerializer::default();
+        "héllo".mc_serialize(&mut out).unwrap();
+        (&[1u8, 2, 3][..]).mc_serialize(&mut out).unwrap();
+        let data = out.into_bytes();
+
+        let Deserialized { value: text, data: rest } = <&str>::mc_deserialize_borrowed(data.as_slice()).unwrap();
+        assert_eq!(text, "héllo");
+        assert_eq!(text.as_ptr(), data[1..].as_ptr());
+        let Deserialized { value: bytes, data: rest } = <&[u8]>::mc_deserialize_borrowed(rest).unwrap();
+        assert_eq!(bytes, &[1, 2, 3]);
+        let Deserialized { value: remaining, data: rest } = RemainingBytesRef::mc_deserialize_borrowed(rest).unwrap();
+        assert!(remaining.is_empty());
+        assert!(rest.is_empty());
+
+        // the same bytes as the owned types
+        assert_eq!(String::mc_deserialize(data.as_slice()).unwrap().value, text);
+        assert!(matches!(<&str>::mc_deserialize_borrowed(&[0x02, 0xC3, 0x28]), Err(DeserializeErr::BadStrEncoding(_))));
+
+        let limits = crate::limits::DeserializeLimits { max_string_chars: 2, ..Default::default() };
+        assert!(matches!(
+            limits.scope(|| <&str>::mc_deserialize_borrowed(data.as_slice())),
+            Err(DeserializeErr::LimitExceeded { what: "string", length: 5, max: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_counted_array_limits() {
+        // claims 2^31 - 1 elements but has none, which must fail without reserving room for them
+        let mut out = BytesSerializer::default();
+        VarInt(i32::MAX).mc_serialize(&mut out).unwrap();
+        let hostile = out.into_bytes();
+        assert!(matches!(
+            <CountedArray<i64, VarInt>>::mc_deserialize(hostile.as_slice()),
+            Err(DeserializeErr::LimitExceeded { what: "array", .. })
+        ));
+
+        let limits = crate::limits::DeserializeLimits { max_array_length: usize::MAX, ..Default::default() };
+        assert!(matches!(
+            limits.scope(|| <CountedArray<i64, VarInt>>::mc_deserialize(hostile.as_slice())),
+            Err(err) if matches!(err.root(), DeserializeErr::Eof) && err.offset() == Some(5)
+        ));
+
+        let array: CountedArray<u8, VarInt> = alloc::vec![1, 2, 3].into();
+        test_type(array.clone());
+        let limits = crate::limits::DeserializeLimits { max_array_length: 2, ..Default::default() };
+        let mut out = BytesSerializer::default();
+        array.mc_serialize(&mut out).unwrap();
+        let bytes = out.into_bytes();
+        assert!(matches!(
+            limits.scope(|| <CountedArray<u8, VarInt>>::mc_deserialize(bytes.as_slice())),
+            Err(DeserializeErr::LimitExceeded { what: "array", length: 3, max: 2 })
+        ));
+    }
+
     #[test]
     fn test_angle() {
         test_type(Angle { value: 0 });
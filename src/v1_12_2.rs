@@ -0,0 +1,2643 @@
+use crate::{types::*, uuid::*, *};
+use alloc::{string::String, vec::Vec, borrow::ToOwned, boxed::Box};
+
+#[cfg(all(test, feature = "std"))]
+use crate::protocol::TestRandom;
+
+define_protocol!(340, Packet340, RawPacket340, RawPacket340Body, Packet340Kind => {
+    // handshaking
+    Handshake, 0x00, Handshaking, ServerBound => HandshakeSpec {
+        version: VarInt,
+        server_address: String,
+        server_port: u16,
+        next_state: HandshakeNextState
+    },
+
+    // status
+    StatusRequest, 0x00, Status, ServerBound => StatusRequestSpec {},
+    StatusPing, 0x01, Status, ServerBound => StatusPingSpec {
+        payload: i64
+    },
+    StatusResponse, 0x00, Status, ClientBound => StatusResponseSpec {
+        response: super::status::StatusSpec
+    },
+    StatusPong, 0x01, Status, ClientBound => StatusPongSpec {
+        payload: i64
+    },
+
+    // login
+    LoginDisconnect, 0x00, Login, ClientBound => LoginDisconnectSpec {
+        message: Chat
+    },
+    LoginEncryptionRequest, 0x01, Login, ClientBound => LoginEncryptionRequestSpec {
+        server_id: String,
+        public_key: CountedArray<u8, VarInt>,
+        verify_token: CountedArray<u8, VarInt>
+    },
+    LoginSuccess, 0x02, Login, ClientBound => LoginSuccessSpec {
+        uuid_string: String,
+        username: String
+    },
+    LoginSetCompression, 0x03, Login, ClientBound => LoginSetCompressionSpec {
+        threshold: VarInt
+    },
+    LoginStart, 0x00, Login, ServerBound => LoginStartSpec {
+        name: String
+    },
+    LoginEncryptionResponse, 0x01, Login, ServerBound => LoginEncryptionResponseSpec {
+        shared_secret: CountedArray<u8, VarInt>,
+        verify_token: CountedArray<u8, VarInt>
+    },
+
+    // play
+    // client bound
+    PlaySpawnEntity, 0x00, Play, ClientBound => PlaySpawnEntitySpec {
+        entity_id: VarInt,
+        object_uuid: UUID4,
+        entity_type: u8,
+        position: Vec3<f64>,
+        pitch: Angle,
+        yaw: Angle,
+        data: i32,
+        velocity: Vec3<i16>
+    },
+    PlaySpawnExperienceOrb, 0x01, Play, ClientBound => PlaySpawnExperienceOrbSpec {
+        entity_id: VarInt,
+        position: Vec3<f64>,
+        count: i16
+    },
+    PlaySpawnWeatherEntity, 0x02, Play, ClientBound => PlaySpawnWeatherEntitySpec {
+        entity_id: VarInt,
+        entity_type: u8,
+        position: Vec3<f64>
+    },
+    PlaySpawnLivingEntity, 0x03, Play, ClientBound => PlaySpawnLivingEntitySpec {
+        entity_id: VarInt,
+        entity_uuid: UUID4,
+        entity_type: VarInt,
+        location: EntityLocation<f64, Angle>,
+        head_pitch: Angle,
+        velocity: Vec3<i16>,
+        metadata: EntityMetadata
+    },
+    PlaySpawnPainting, 0x04, Play, ClientBound => PlaySpawnPaintingSpec {
+        entity_id: VarInt,
+        entity_uuid: UUID4,
+        title: String,
+        location: LegacyIntPosition,
+        direction: CardinalDirection
+    },
+    PlaySpawnPlayer, 0x05, Play, ClientBound => PlaySpawnPlayerSpec {
+        entity_id: VarInt,
+        uuid: UUID4,
+        location: EntityLocation<f64, Angle>,
+        metadata: EntityMetadata
+    },
+    PlayEntityAnimation, 0x06, Play, ClientBound => PlayEntityAnimationSpec {
+        entity_id: VarInt,
+        animation: EntityAnimationKind
+    },
+    PlayStatistics, 0x07, Play, ClientBound => PlayStatisticsSpec {
+        entries: CountedArray<Statistic, VarInt>
+    },
+    PlayBlockBreakAnimation, 0x08, Play, ClientBound => PlayBlockBreakAnimationSpec {
+        entity_id: VarInt,
+        location: LegacyIntPosition,
+        destroy_stage: i8
+    },
+    PlayBlockEntityData, 0x09, Play, ClientBound => PlayBlockEntityDataSpec {
+        location: LegacyIntPosition,
+        action: BlockEntityDataAction,
+        nbt_data: NamedNbtTag
+    },
+    PlayBlockAction, 0x0A, Play, ClientBound => PlayBlockActionSpec {
+        location: LegacyIntPosition,
+        action_id: u8,
+        action_payload: u8,
+        block_type: VarInt
+    },
+    PlayBlockChange, 0x0B, Play, ClientBound => PlayBlockChangeSpec {
+        location: LegacyIntPosition,
+        block_id: VarInt
+    },
+    PlayBossBar, 0x0C, Play, ClientBound => PlayBossBarSpec {
+        uuid: UUID4,
+        action: BossBarAction
+    },
+    PlayServerDifficulty, 0x0D, Play, ClientBound => PlayServerDifficultySpec {
+        difficulty: Difficulty
+    },
+    PlayTabComplete, 0x0E, Play, ClientBound => PlayTabCompleteSpec {
+        matches: CountedArray<String, VarInt>
+    },
+    PlayServerChatMessage, 0x0F, Play, ClientBound => PlayServerChatMessageSpec {
+        message: Chat,
+        position: ChatPosition
+    },
+    PlayMultiBlockChange, 0x10, Play, ClientBound => PlayMultiBlockChangeSpec {
+        chunk: ChunkPosition<i32>,
+        changes: CountedArray<MultiBlockChangeRecord, VarInt>
+    },
+    PlayServerWindowConfirmation, 0x11, Play, ClientBound => PlayServerWindowConfirmationSpec {
+        window_id: i8,
+        action_number: i16,
+        accepted: bool
+    },
+    PlayServerCloseWindow, 0x12, Play, ClientBound => PlayServerCloseWindowSpec {
+        window_id: u8
+    },
+    PlayOpenWindow, 0x13, Play, ClientBound => PlayOpenWindowSpec {
+        window_id: u8,
+        window: WindowSpec
+    },
+    PlayWindowItems, 0x14, Play, ClientBound => PlayWindowItemsSpec {
+        window_id: u8,
+        slots: CountedArray<Slot, i16>
+    },
+    PlayWindowProperty, 0x15, Play, ClientBound => PlayWindowPropertySpec {
+        window_id: u8,
+        property: i16,
+        value: i16
+    },
+    PlaySetSlot, 0x16, Play, ClientBound => PlaySetSlotSpec {
+        window_id: i8,
+        slot: i16,
+        slot_data: Slot
+    },
+    PlaySetCooldown, 0x17, Play, ClientBound => PlaySetCooldownSpec {
+        item_id: VarInt,
+        cooldown_ticks: VarInt
+    },
+    PlayServerPluginMessage, 0x18, Play, ClientBound => PlayServerPluginMessageSpec {
+        channel: String,
+        data: RemainingBytes
+    },
+    PlayNamedSoundEffect, 0x19, Play, ClientBound => PlayNamedSoundEffectSpec {
+        sound_name: String,
+        sound_category: SoundCategory,
+        position: Vec3<FixedInt>,
+        volume: f32,
+        pitch: f32
+    },
+    PlayDisconnect, 0x1A, Play, ClientBound => PlayDisconnectSpec {
+        reason: Chat
+    },
+    PlayEntityStatus, 0x1B, Play, ClientBound => PlayEntityStatusSpec {
+        entity_id: i32,
+        raw_status: u8
+    },
+    PlayExplosion, 0x1C, Play, ClientBound => PlayExplosionSpec {
+        position: Vec3<f32>,
+        strength: f32,
+        records: CountedArray<Vec3<i8>, i32>,
+        player_motion: Vec3<f32>
+    },
+    PlayUnloadChunk, 0x1D, Play, ClientBound => PlayUnloadChunkSpec {
+        position: ChunkPosition<i32>
+    },
+    PlayChangeGameState, 0x1E, Play, ClientBound => PlayChangeGameStateSpec {
+        reason: GameChangeReason
+    },
+    PlayServerKeepAlive, 0x1F, Play, ClientBound => PlayServerKeepAliveSpec {
+        id: i64
+    },
+    PlayChunkData, 0x20, Play, ClientBound => PlayChunkDataSpec {
+        position: ChunkPosition<i32>,
+        full_chunk: bool,
+        primary_bit_mask: VarInt,
+        data: CountedArray<u8, VarInt>,
+        block_entities: CountedArray<NamedNbtTag, VarInt>
+    },
+    PlayEffect, 0x21, Play, ClientBound => PlayEffectSpec {
+        effect_id: i32,
+        location: LegacyIntPosition,
+        data: i32,
+        disable_relative_volume: bool
+    },
+    PlayParticle, 0x22, Play, ClientBound => PlayParticleSpec {
+        particle: ParticleSpec
+    },
+    PlayJoinGame, 0x23, Play, ClientBound => PlayJoinGameSpec {
+        entity_id: i32,
+        gamemode: JoinGameMode,
+        dimension: Dimension,
+        difficulty: Difficulty,
+        max_players: u8,
+        level_type: String,
+        reduced_debug_info: bool
+    },
+    PlayMapData, 0x24, Play, ClientBound => PlayMapDataSpec {
+        map_id: VarInt,
+        scale: i8,
+        tracking_position: bool,
+        icons: CountedArray<MapIconSpec, VarInt>,
+        columns: MapColumns
+    },
+    PlayEntityMovement, 0x25, Play, ClientBound => PlayEntityMovementSpec {
+        entity_id: VarInt
+    },
+    PlayEntityPosition, 0x26, Play, ClientBound => PlayEntityPositionSpec {
+        entity_id: VarInt,
+        delta: Vec3<i16>,
+        on_ground: bool
+    },
+    PlayEntityPositionAndRotation, 0x27, Play, ClientBound => PlayEntityPositionAndRotationSpec {
+        entity_id: VarInt,
+        delta: EntityLocation<i16, Angle>,
+        on_ground: bool
+    },
+    PlayEntityRotation, 0x28, Play, ClientBound => PlayEntityRotationSpec {
+        entity_id: VarInt,
+        rotation: EntityRotation<Angle>,
+        on_ground: bool
+    },
+    PlayServerVehicleMove, 0x29, Play, ClientBound => PlayEntityVehicleMoveSpec {
+        location: EntityLocation<f64, f32>
+    },
+    PlayOpenSignEditor, 0x2A, Play, ClientBound => PlayOpenSignEditorSpec {
+        location: LegacyIntPosition
+    },
+    PlayCraftRecipeResponse, 0x2B, Play, ClientBound => PlayCraftRecipeResponseSpec {
+        window_id: i8,
+        recipe: VarInt
+    },
+    PlayServerPlayerAbilities, 0x2C, Play, ClientBound => PlayServerPlayerAbilitiesSpec {
+        flags: PlayerAbilityFlags,
+        flying_speed: f32,
+        field_of_view_modifier: f32
+    },
+    PlayCombatEvent, 0x2D, Play, ClientBound => PlayCombatEventSpec {
+        event: CombatEvent
+    },
+    PlayPlayerInfo, 0x2E, Play, ClientBound => PlayPlayerInfoSpec {
+        actions: PlayerInfoActionList
+    },
+    PlayServerPlayerPositionAndLook, 0x2F, Play, ClientBound => PlayServerPlayerPositionAndLookSpec {
+        location: EntityLocation<f64, f32>,
+        flags: PositionAndLookFlags,
+        teleport_id: VarInt
+    },
+    PlayUseBed, 0x30, Play, ClientBound => PlayUseBedSpec {
+        entity_id: VarInt,
+        location: LegacyIntPosition
+    },
+    PlayUnlockRecipes, 0x31, Play, ClientBound => PlayUnlockRecipesSpec {
+        action: RecipeUnlockAction
+    },
+    PlayDestroyEntities, 0x32, Play, ClientBound => PlayDestroyEntitiesSpec {
+        entity_ids: CountedArray<VarInt, VarInt>
+    },
+    PlayRemoveEntityEffect, 0x33, Play, ClientBound => PlayRemoveEntityEffectSpec {
+        entity_id: VarInt,
+        effect: EntityEffectKind
+    },
+    PlayResourcePackSend, 0x34, Play, ClientBound => PlayResourcePackSendSpec {
+        url: String,
+        hash: String
+    },
+    PlayRespawn, 0x35, Play, ClientBound => PlayRespawnSpec {
+        dimension: Dimension,
+        difficulty: Difficulty,
+        gamemode: GameMode,
+        level_type: String
+    },
+    PlayEntityHeadLook, 0x36, Play, ClientBound => PlayEntityHeadLookSpec {
+        entity_id: VarInt,
+        head_yaw: Angle
+    },
+    PlaySelectAdvancementTab, 0x37, Play, ClientBound => PlaySelectAdvancementTabSpec {
+        identifier: Option<String>
+    },
+    PlayWorldBorder, 0x38, Play, ClientBound => PlayWorldBorderSpec {
+        action: WorldBorderAction
+    },
+    PlayCamera, 0x39, Play, ClientBound => PlayCameraSpec {
+        camera_id: VarInt
+    },
+    PlayServerHeldItemChange, 0x3A, Play, ClientBound => PlayServerHeldItemChangeSpec {
+        slot: i8
+    },
+    PlayDisplayScoreboard, 0x3B, Play, ClientBound => PlayDisplayScoreboardSpec {
+        position: ScoreboardPosition,
+        score_name: String
+    },
+    PlayEntityMetadata, 0x3C, Play, ClientBound => PlayEntityMetadataSpec {
+        entity_id: VarInt,
+        metadata: EntityMetadata
+    },
+    PlayAttachEntity, 0x3D, Play, ClientBound => PlayAttachEntitySpec {
+        attached_entity_id: i32,
+        holding_entity_id: i32
+    },
+    PlayEntityVelocity, 0x3E, Play, ClientBound => PlayEntityVelocitySpec {
+        entity_id: VarInt,
+        velocity: Vec3<i16>
+    },
+    PlayEntityEquipment, 0x3F, Play, ClientBound => PlayEntityEquipmentSpec {
+        entity_id: VarInt,
+        slot: EquipmentSlot,
+        item: Slot
+    },
+    PlaySetExperience, 0x40, Play, ClientBound => PlaySetExperienceSpec {
+        experience_bar: f32,
+        level: VarInt,
+        total_experience: VarInt
+    },
+    PlayUpdateHealth, 0x41, Play, ClientBound => PlayUpdateHealthSpec {
+        health: f32,
+        food: VarInt,
+        saturation: f32
+    },
+    PlayScoreboardObjective, 0x42, Play, ClientBound => PlayScoreboardObjectiveSpec {
+        objective_name: String,
+        action: ScoreboardObjectiveAction
+    },
+    PlaySetPassengers, 0x43, Play, ClientBound => PlaySetPassengersSpec {
+        entity_id: VarInt,
+        passenger_entity_ids: CountedArray<VarInt, VarInt>
+    },
+    PlayTeams, 0x44, Play, ClientBound => PlayTeamsSpec {
+        team_name: String,
+        action: TeamAction
+    },
+    PlayUpdateScore, 0x45, Play, ClientBound => PlayUpdateScoreSpec {
+        entity_name: String,
+        update: UpdateScoreSpec
+    },
+    PlaySpawnPosition, 0x46, Play, ClientBound => PlaySpawnPositionSpec {
+        location: LegacyIntPosition
+    },
+    PlayTimeUpdate, 0x47, Play, ClientBound => PlayTimeUpdateSpec {
+        world_age: i64,
+        time_of_day: i64
+    },
+    PlayTitle, 0x48, Play, ClientBound => PlayTitleSpec {
+        action: TitleActionSpec
+    },
+    PlaySoundEffect, 0x49, Play, ClientBound => PlaySoundEffectSpec {
+        sound_id: VarInt,
+        sound_category: SoundCategory,
+        position: Vec3<FixedInt>,
+        volume: f32,
+        pitch: f32
+    },
+    PlayPlayerListHeaderAndFooter, 0x4A, Play, ClientBound => PlayPlayerListHeaderAndFooterSpec {
+        header: Chat,
+        footer: Chat
+    },
+    PlayCollectItem, 0x4B, Play, ClientBound => PlayCollectItemSpec {
+        collected_entity_id: VarInt,
+        collector_entity_id: VarInt,
+        pickup_item_count: VarInt
+    },
+    PlayEntityTeleport, 0x4C, Play, ClientBound => PlayEntityTeleportSpec {
+        entity_id: VarInt,
+        location: EntityLocation<f64, Angle>,
+        on_ground: bool
+    },
+    PlayAdvancements, 0x4D, Play, ClientBound => PlayAdvancementsSpec {
+        reset: bool,
+        mappings: CountedArray<AdvancementMappingEntrySpec, VarInt>,
+        identifiers: CountedArray<String, VarInt>,
+        progress: CountedArray<AdvancementProgressEntrySpec, VarInt>
+    },
+    PlayEntityProperties, 0x4E, Play, ClientBound => PlayEntityPropertiesSpec {
+        entity_id: VarInt,
+        properties: CountedArray<EntityPropertySpec, i32>
+    },
+    PlayEntityEffect, 0x4F, Play, ClientBound => PlayEntityEffectSpec {
+        entity_id: VarInt,
+        effect_id: EntityEffectKind,
+        amplifier: i8,
+        duration_ticks: VarInt,
+        flags: EntityEffectFlags
+    },
+
+    // play server bound
+    PlayTeleportConfirm, 0x00, Play, ServerBound => PlayTeleportConfirmSpec {
+        teleport_id: VarInt
+    },
+    PlayClientTabComplete, 0x01, Play, ServerBound => PlayClientTabCompleteSpec {
+        text: String,
+        assume_command: bool,
+        looked_at_block: Option<LegacyIntPosition>
+    },
+    PlayClientChatMessage, 0x02, Play, ServerBound => PlayClientChatMessageSpec {
+        message: String
+    },
+    PlayClientStatus, 0x03, Play, ServerBound => PlayClientStatusSpec {
+        action: ClientStatusAction
+    },
+    PlayClientSettings, 0x04, Play, ServerBound => PlayClientSettingsSpec {
+        locale: String,
+        view_distance: i8,
+        chat_mode: ClientChatMode,
+        chat_colors: bool,
+        displayed_skin_parts: ClientDisplayedSkinParts,
+        main_hand: ClientMainHand
+    },
+    PlayClientWindowConfirmation, 0x05, Play, ServerBound => PlayClientWindowConfirmationSpec {
+        window_id: i8,
+        action_num: i16,
+        accepted: bool
+    },
+    PlayEnchantItem, 0x06, Play, ServerBound => PlayEnchantItemSpec {
+        window_id: i8,
+        enchantment: i8
+    },
+    PlayClickWindow, 0x07, Play, ServerBound => PlayClickWindowSpec {
+        window_id: u8,
+        slot: i16,
+        button: i8,
+        action_number: i16,
+        mode: InventoryOperationMode,
+        clicked_item: Slot
+    },
+    PlayClientCloseWindow, 0x08, Play, ServerBound => PlayClientCloseWindowSpec {
+        window_id: u8
+    },
+    PlayClientPluginMessage, 0x09, Play, ServerBound => PlayClientPluginMessageSpec {
+        channel: String,
+        data: RemainingBytes
+    },
+    PlayInteractEntity, 0x0A, Play, ServerBound => PlayInteractEntitySpec {
+        entity_id: VarInt,
+        kind: InteractKind
+    },
+    PlayClientKeepAlive, 0x0B, Play, ServerBound => PlayClientKeepAliveSpec {
+        id: i64
+    },
+    PlayPlayerMovement, 0x0C, Play, ServerBound => PlayPlayerMovementSpec {
+        on_ground: bool
+    },
+    PlayPlayerPosition, 0x0D, Play, ServerBound => PlayPlayerPositionSpec {
+        feet_position: Vec3<f64>,
+        on_ground: bool
+    },
+    PlayClientPlayerPositionAndRotation, 0x0E, Play, ServerBound => PlayClientPlayerPositionAndRotationSpec {
+        feet_location: EntityLocation<f64, f32>,
+        on_ground: bool
+    },
+    PlayPlayerRotation, 0x0F, Play, ServerBound => PlayPlayerRotationSpec {
+        rotation: EntityRotation<f32>,
+        on_ground: bool
+    },
+    PlayClientVehicleMove, 0x10, Play, ServerBound => PlayClientVehicleMoveSpec {
+        location: EntityLocation<f64, f32>
+    },
+    PlaySteerBoat, 0x11, Play, ServerBound => PlaySteerBoatSpec {
+        left_paddle_turning: bool,
+        right_paddle_turning: bool
+    },
+    PlayCraftRecipeRequest, 0x12, Play, ServerBound => PlayCraftRecipeRequestSpec {
+        window_id: i8,
+        recipe: VarInt,
+        make_all: bool
+    },
+    PlayClientPlayerAbilities, 0x13, Play, ServerBound => PlayClientPlayerAbilitiesSpec {
+        flags: ClientPlayerAbilities,
+        flying_speed: f32,
+        walking_speed: f32
+    },
+    PlayPlayerDigging, 0x14, Play, ServerBound => PlayPlayerDiggingSpec {
+        status: PlayerDiggingStatus,
+        location: LegacyIntPosition,
+        face: DiggingFace
+    },
+    PlayEntityAction, 0x15, Play, ServerBound => PlayEntityActionSpec {
+        entity_id: VarInt,
+        action: EntityActionKind,
+        jump_boost: VarInt
+    },
+    PlaySteerVehicle, 0x16, Play, ServerBound => PlaySteerVehicleSpec {
+        sideways: f32,
+        forward: f32,
+        flags: SteerVehicleFlags
+    },
+    PlayCraftingBookData, 0x17, Play, ServerBound => PlayCraftingBookDataSpec {
+        data: CraftingBookData
+    },
+    PlayResourcePackStatus, 0x18, Play, ServerBound => PlayResourcePackStatusSpec {
+        status: ResourcePackStatus
+    },
+    PlayAdvancementTab, 0x19, Play, ServerBound => PlayAdvancementTabSpec {
+        action: AdvancementTabAction
+    },
+    PlayClientHeldItemChange, 0x1A, Play, ServerBound => PlayClientHeldItemChangeSpec {
+        slot: i16
+    },
+    PlayCreativeInventoryAction, 0x1B, Play, ServerBound => PlayCreativeInventoryActionSpec {
+        slot: i16,
+        clicked_item: Slot
+    },
+    PlayUpdateSign, 0x1C, Play, ServerBound => PlayUpdateSignSpec {
+        location: LegacyIntPosition,
+        line1: String,
+        line2: String,
+        line3: String,
+        line4: String
+    },
+    PlayClientAnimation, 0x1D, Play, ServerBound => PlayClientAnimationSpec {
+        hand: Hand
+    },
+    PlaySpectate, 0x1E, Play, ServerBound => PlaySpectateSpec {
+        target: UUID4
+    },
+    PlayBlockPlacement, 0x1F, Play, ServerBound => PlayBlockPlacementSpec {
+        location: LegacyIntPosition,
+        face: BlockFace,
+        hand: Hand,
+        cursor_position: Vec3<f32>
+    },
+    PlayUseItem, 0x20, Play, ServerBound => PlayUseItemSpec {
+        hand: Hand
+    }
+});
+
+impl crate::protocol::HasCompressionThreshold for Packet340 {
+    fn compression_threshold(&self) -> Option<i32> {
+        match self {
+            Packet340::LoginSetCompression(body) => Some(body.threshold.0),
+            _ => None,
+        }
+    }
+}
+
+impl crate::protocol::HasNextState for Packet340 {
+    fn next_state(&self) -> Option<crate::protocol::State> {
+        use crate::protocol::State;
+        match self {
+            Packet340::Handshake(body) => Some(match body.next_state {
+                HandshakeNextState::Status => State::Status,
+                HandshakeNextState::Login => State::Login,
+            }),
+            Packet340::LoginSuccess(_) => Some(State::Play),
+            _ => None,
+        }
+    }
+}
+
+impl crate::limits::HasFieldLimits for Packet340 {
+    fn check_field_limits(&self, limits: &crate::limits::DeserializeLimits) -> Result<(), DeserializeErr> {
+        match self {
+            Packet340::LoginStart(body) => limits.check_username(body.name.as_str()),
+            Packet340::LoginSuccess(body) => limits.check_username(body.username.as_str()),
+            _ => Ok(()),
+        }
+    }
+}
+
+// helper types
+
+// handshake enum
+proto_byte_enum!(HandshakeNextState,
+    0x01 :: Status,
+    0x02 :: Login
+);
+
+proto_byte_enum!(CardinalDirection,
+    0x00 :: South,
+    0x01 :: West,
+    0x02 :: North,
+    0x03 :: East
+);
+
+proto_byte_enum!(EntityAnimationKind,
+    0x00 :: SwingMainArm,
+    0x01 :: TakeDamage,
+    0x02 :: LeaveBed,
+    0x03 :: SwingOffHand,
+    0x04 :: CriticalEffect,
+    0x05 :: MagicCriticalEffect
+);
+
+proto_struct!(Statistic {
+    name: String,
+    value: VarInt
+});
+
+proto_byte_enum!(BlockEntityDataAction,
+    0x01 :: SetMobSpawnerData,
+    0x02 :: SetCommandBlockText,
+    0x03 :: SetBeaconLevelAndPower,
+    0x04 :: SetMobHeadRotationAndSkin,
+    0x05 :: SetFlowerPotFlower,
+    0x06 :: SetBannerColorAndPatterns,
+    0x07 :: SetStructureTileEntityData,
+    0x08 :: SetEndGatewayDestination,
+    0x09 :: SetSignText,
+    0x0A :: DeclareShulkerBox,
+    0x0B :: SetBedColor
+);
+
+proto_byte_enum!(Difficulty,
+    0x00 :: Peaceful,
+    0x01 :: Easy,
+    0x02 :: Normal,
+    0x03 :: Hard
+);
+
+proto_byte_enum!(ChatPosition,
+    0x00 :: ChatBox,
+    0x01 :: SystemMessage,
+    0x02 :: Hotbar
+);
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct BlockChangeHorizontalPosition {
+    pub rel_x: u8,
+    pub rel_z: u8,
+}
+
+impl Serialize for BlockChangeHorizontalPosition {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        to.serialize_byte((self.rel_x & 0xF) << 4 | (self.rel_z & 0xF))
+    }
+}
+
+impl Deserialize for BlockChangeHorizontalPosition {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        Ok(
+            u8::mc_deserialize(data)?.map(move |b| BlockChangeHorizontalPosition {
+                rel_x: (b >> 4) & 0xF,
+                rel_z: b & 0xF,
+            }),
+        )
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for BlockChangeHorizontalPosition {
+    fn test_gen_random() -> Self {
+        BlockChangeHorizontalPosition {
+            rel_x: rand::random::<u8>() % 16,
+            rel_z: rand::random::<u8>() % 16,
+        }
+    }
+}
+
+proto_struct!(MultiBlockChangeRecord {
+    horizontal_position: BlockChangeHorizontalPosition,
+    y_coordinate: u8,
+    block_id: VarInt
+});
+
+proto_varint_enum!(BossBarAction,
+    0x00 :: Add(BossBarAddSpec),
+    0x01 :: Remove,
+    0x02 :: UpdateHealth(BossBarUpdateHealthSpec),
+    0x03 :: UpdateTitle(BossBarUpdateTitleSpec),
+    0x04 :: UpdateStyle(BossBarUpdateStyleSpec),
+    0x05 :: UpdateFlags(BossBarUpdateFlagsSpec)
+);
+
+proto_varint_enum!(BossBarColor,
+    0x00 :: Pink,
+    0x01 :: Blue,
+    0x02 :: Red,
+    0x03 :: Green,
+    0x04 :: Yellow,
+    0x05 :: Purple,
+    0x06 :: White
+);
+
+proto_varint_enum!(BossBarDivision,
+    0x00 :: NoDivision,
+    0x01 :: SixNotches,
+    0x02 :: TenNotches,
+    0x03 :: TwelveNotches,
+    0x04 :: TwentyNotches
+);
+
+proto_byte_flag!(BossBarFlags,
+    0x01 :: is_darken_sky set_darken_sky,
+    0x02 :: is_dragon_bar set_dragon_bar
+);
+
+proto_struct!(BossBarAddSpec {
+    title: Chat,
+    health: f32,
+    color: BossBarColor,
+    division: BossBarDivision,
+    flags: BossBarFlags
+});
+
+proto_struct!(BossBarUpdateHealthSpec { health: f32 });
+
+proto_struct!(BossBarUpdateTitleSpec { title: Chat });
+
+proto_struct!(BossBarUpdateStyleSpec {
+    color: BossBarColor,
+    dividers: BossBarDivision
+});
+
+proto_struct!(BossBarUpdateFlagsSpec {
+    flags: BossBarFlags
+});
+
+// "EntityHorse" is the only window type followed by an entity id
+const HORSE_WINDOW_TYPE: &str = "EntityHorse";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum WindowKind {
+    Container(String),
+    Horse(i32),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WindowSpec {
+    pub kind: WindowKind,
+    pub title: Chat,
+    pub number_of_slots: u8,
+}
+
+impl Serialize for WindowSpec {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        use WindowKind::*;
+        match &self.kind {
+            Container(name) => to.serialize_other(name)?,
+            Horse(_) => to.serialize_other(&HORSE_WINDOW_TYPE.to_owned())?,
+        }
+        to.serialize_other(&self.title)?;
+        to.serialize_other(&self.number_of_slots)?;
+        if let Horse(entity_id) = &self.kind {
+            to.serialize_other(entity_id)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Deserialize for WindowSpec {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: kind, data } = String::mc_deserialize(data)?;
+        let Deserialized { value: title, data } = Chat::mc_deserialize(data)?;
+        let Deserialized { value: number_of_slots, data } = u8::mc_deserialize(data)?;
+        let Deserialized { value: kind, data } = if kind == HORSE_WINDOW_TYPE {
+            i32::mc_deserialize(data)?.map(WindowKind::Horse)
+        } else {
+            Deserialized { value: WindowKind::Container(kind), data }
+        };
+
+        Deserialized::ok(Self {
+            kind,
+            title,
+            number_of_slots,
+        }, data)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for WindowSpec {
+    fn test_gen_random() -> Self {
+        let kind = if rand::random::<bool>() {
+            WindowKind::Horse(i32::test_gen_random())
+        } else {
+            WindowKind::Container("minecraft:chest".to_owned())
+        };
+
+        Self {
+            kind,
+            title: Chat::test_gen_random(),
+            number_of_slots: u8::test_gen_random(),
+        }
+    }
+}
+
+proto_varint_enum!(SoundCategory,
+    0x00 :: Master,
+    0x01 :: Music,
+    0x02 :: Records,
+    0x03 :: Weather,
+    0x04 :: Block,
+    0x05 :: Hostile,
+    0x06 :: Neutral,
+    0x07 :: Player,
+    0x08 :: Ambient,
+    0x09 :: Voice
+);
+
+proto_byte_enum!(GameMode,
+    0x00 :: Survival,
+    0x01 :: Creative,
+    0x02 :: Adventure,
+    0x03 :: Spectator
+);
+
+// the hardcore flag is packed into the game mode byte of PlayJoinGame
+#[derive(Clone, Debug, PartialEq)]
+pub struct JoinGameMode {
+    pub mode: GameMode,
+    pub hardcore: bool,
+}
+
+impl Serialize for JoinGameMode {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        let hardcore = if self.hardcore { 0x08 } else { 0x00 };
+        to.serialize_byte(self.mode.id() | hardcore)
+    }
+}
+
+impl Deserialize for JoinGameMode {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: raw, data } = u8::mc_deserialize(data)?;
+        let hardcore = raw & 0x08 != 0;
+        Ok(GameMode::deserialize_with_id(raw & 0x07, data)?.map(move |mode| Self { mode, hardcore }))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for JoinGameMode {
+    fn test_gen_random() -> Self {
+        Self {
+            mode: GameMode::test_gen_random(),
+            hardcore: rand::random(),
+        }
+    }
+}
+
+proto_byte_enum!(WinGameAction,
+    0x00 :: Respawn,
+    0x01 :: RollCreditsAndRespawn
+);
+
+proto_byte_enum!(DemoEvent,
+    0x00 :: ShowWelcomeScreen,
+    0x65 :: TellMovementControls,
+    0x66 :: TellJumpControl,
+    0x67 :: TellInventoryControl
+);
+
+proto_int_enum!(Dimension,
+    -0x01 :: Nether,
+     0x00 :: Overworld,
+     0x01 :: End
+);
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GameChangeReason {
+    InvalidBed,
+    EndRaining,
+    BeginRaining,
+    ChangeGameMode(GameMode),
+    ExitEnd(WinGameAction),
+    Demo(DemoEvent),
+    ArrowHitPlayer,
+    FadeValue(f32),
+    FadeTime(f32),
+    ElderGuardianMobAppearance,
+}
+
+impl Serialize for GameChangeReason {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        use GameChangeReason::*;
+        to.serialize_byte(match self {
+            InvalidBed => 0x00,
+            EndRaining => 0x01,
+            BeginRaining => 0x02,
+            ChangeGameMode(_) => 0x03,
+            ExitEnd(_) => 0x04,
+            Demo(_) => 0x05,
+            ArrowHitPlayer => 0x06,
+            FadeValue(_) => 0x07,
+            FadeTime(_) => 0x08,
+            ElderGuardianMobAppearance => 0x0A,
+        })?;
+
+        let value = match self {
+            ChangeGameMode(body) => body.id() as f32,
+            ExitEnd(body) => body.id() as f32,
+            Demo(body) => body.id() as f32,
+            FadeValue(body) => *body,
+            FadeTime(body) => *body,
+            _ => 0 as f32,
+        };
+        to.serialize_other(&value)
+    }
+}
+
+impl Deserialize for GameChangeReason {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: reason_id, data } = u8::mc_deserialize(data)?;
+        let Deserialized { value, data } = f32::mc_deserialize(data)?;
+        use GameChangeReason::*;
+        match reason_id {
+            0x00 => Deserialized::ok(InvalidBed, data),
+            0x01 => Deserialized::ok(EndRaining, data),
+            0x02 => Deserialized::ok(BeginRaining, data),
+            0x03 => Ok(GameMode::deserialize_with_id(value as u8, data)?.map(ChangeGameMode)),
+            0x04 => Ok(WinGameAction::deserialize_with_id(value as u8, data)?.map(ExitEnd)),
+            0x05 => Ok(DemoEvent::deserialize_with_id(value as u8, data)?.map(Demo)),
+            0x06 => Deserialized::ok(ArrowHitPlayer, data),
+            0x07 => Deserialized::ok(FadeValue(value), data),
+            0x08 => Deserialized::ok(FadeTime(value), data),
+            0x0A => Deserialized::ok(ElderGuardianMobAppearance, data),
+            other => Err(DeserializeErr::CannotUnderstandValue(alloc::format!(
+                "invalid game change reason id {}",
+                other
+            ))),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for GameChangeReason {
+    fn test_gen_random() -> Self {
+        use GameChangeReason::*;
+        match rand::random::<u8>() % 5 {
+            0 => BeginRaining,
+            1 => ChangeGameMode(GameMode::test_gen_random()),
+            2 => ExitEnd(WinGameAction::test_gen_random()),
+            3 => FadeValue(f32::test_gen_random()),
+            _ => ElderGuardianMobAppearance,
+        }
+    }
+}
+
+// particles which are followed by some VarInt arguments, with the number of arguments
+const PARTICLE_ARGUMENTS: [(i32, usize); 4] = [
+    (36, 2), // iconcrack
+    (37, 1), // blockcrack
+    (38, 1), // blockdust
+    (46, 1), // fallingdust
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParticleSpec {
+    pub particle_id: i32,
+    pub long_distance: bool,
+    pub position: Vec3<f32>,
+    pub offset: Vec3<f32>,
+    pub particle_data: f32,
+    pub count: i32,
+    pub arguments: Vec<VarInt>,
+}
+
+impl ParticleSpec {
+    pub fn argument_count(particle_id: i32) -> usize {
+        PARTICLE_ARGUMENTS.iter()
+            .find(move |(id, _)| *id == particle_id)
+            .map(move |(_, count)| *count)
+            .unwrap_or(0)
+    }
+}
+
+impl Serialize for ParticleSpec {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        let expected = Self::argument_count(self.particle_id);
+        if self.arguments.len() != expected {
+            return Err(SerializeErr::CannotSerialize(alloc::format!(
+                "particle {} takes {} arguments, got {}",
+                self.particle_id, expected, self.arguments.len()
+            )));
+        }
+
+        to.serialize_other(&self.particle_id)?;
+        to.serialize_other(&self.long_distance)?;
+        to.serialize_other(&self.position)?;
+        to.serialize_other(&self.offset)?;
+        to.serialize_other(&self.particle_data)?;
+        to.serialize_other(&self.count)?;
+        for argument in &self.arguments {
+            to.serialize_other(argument)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Deserialize for ParticleSpec {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: particle_id, data } = i32::mc_deserialize(data)?;
+        let Deserialized { value: long_distance, data } = bool::mc_deserialize(data)?;
+        let Deserialized { value: position, data } = <Vec3<f32>>::mc_deserialize(data)?;
+        let Deserialized { value: offset, data } = <Vec3<f32>>::mc_deserialize(data)?;
+        let Deserialized { value: particle_data, data } = f32::mc_deserialize(data)?;
+        let Deserialized { value: count, mut data } = i32::mc_deserialize(data)?;
+        let n_arguments = Self::argument_count(particle_id);
+        let mut arguments = Vec::with_capacity(n_arguments);
+        for _ in 0..n_arguments {
+            let Deserialized { value: argument, data: rest } = VarInt::mc_deserialize(data)?;
+            data = rest;
+            arguments.push(argument);
+        }
+
+        Deserialized::ok(Self {
+            particle_id,
+            long_distance,
+            position,
+            offset,
+            particle_data,
+            count,
+            arguments,
+        }, data)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for ParticleSpec {
+    fn test_gen_random() -> Self {
+        let particle_id = (rand::random::<u8>() % 48) as i32;
+        let arguments = (0..Self::argument_count(particle_id))
+            .map(move |_| VarInt::test_gen_random())
+            .collect();
+
+        Self {
+            particle_id,
+            long_distance: rand::random(),
+            position: <Vec3<f32>>::test_gen_random(),
+            offset: <Vec3<f32>>::test_gen_random(),
+            particle_data: f32::test_gen_random(),
+            count: i32::test_gen_random(),
+            arguments,
+        }
+    }
+}
+
+proto_byte_enum!(MapIconType,
+    0x00 :: WhiteArrow,
+    0x01 :: GreenArrow,
+    0x02 :: RedArrow,
+    0x03 :: BlueArrow,
+    0x04 :: WhiteCross,
+    0x05 :: RedPointer,
+    0x06 :: WhiteCircle,
+    0x07 :: SmallWhiteCircle,
+    0x08 :: Mansion,
+    0x09 :: Temple
+);
+
+// the icon type and direction share a single byte
+#[derive(Clone, Debug, PartialEq)]
+pub struct MapIconSpec {
+    pub kind: MapIconType,
+    pub direction: u8,
+    pub position: TopDownPosition<i8>,
+}
+
+impl Serialize for MapIconSpec {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        to.serialize_byte(self.kind.id() << 4 | (self.direction & 0xF))?;
+        to.serialize_other(&self.position)
+    }
+}
+
+impl Deserialize for MapIconSpec {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: raw, data } = u8::mc_deserialize(data)?;
+        let Deserialized { value: kind, data } = MapIconType::deserialize_with_id(raw >> 4, data)?;
+        let direction = raw & 0xF;
+        Ok(<TopDownPosition<i8>>::mc_deserialize(data)?.map(move |position| Self {
+            kind,
+            direction,
+            position,
+        }))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for MapIconSpec {
+    fn test_gen_random() -> Self {
+        Self {
+            kind: MapIconType::test_gen_random(),
+            direction: rand::random::<u8>() % 16,
+            position: <TopDownPosition<i8>>::test_gen_random(),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum MapColumns {
+    NoUpdates,
+    Updated(MapColumnsSpec),
+}
+
+proto_struct!(MapColumnsSpec {
+    columns: u8,
+    rows: u8,
+    position: TopDownPosition<u8>,
+    data: CountedArray<u8, VarInt>
+});
+
+impl Serialize for MapColumns {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        use MapColumns::*;
+        match self {
+            NoUpdates => to.serialize_other(&0u8),
+            Updated(body) => to.serialize_other(body),
+        }
+    }
+}
+
+impl Deserialize for MapColumns {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: columns, data: rest } = u8::mc_deserialize(data)?;
+        use MapColumns::*;
+        match columns {
+            0x00 => Deserialized::ok(NoUpdates, rest),
+            _ => Ok(MapColumnsSpec::mc_deserialize(data)?.map(Updated)),
+        }
+    }
+}
+
+impl From<Option<MapColumnsSpec>> for MapColumns {
+    fn from(other: Option<MapColumnsSpec>) -> Self {
+        use MapColumns::*;
+        match other {
+            Some(body) if body.columns != 0 => Updated(body),
+            _ => NoUpdates,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for MapColumns {
+    fn test_gen_random() -> Self {
+        <Option<MapColumnsSpec>>::test_gen_random().into()
+    }
+}
+
+proto_varint_enum!(Hand,
+    0x00 :: MainHand,
+    0x01 :: OffHand
+);
+
+proto_byte_flag!(PlayerAbilityFlags,
+    0x01 :: is_invulnerable set_invulnerable,
+    0x02 :: is_flying set_flying,
+    0x04 :: is_flight_allowed set_flight_allowed,
+    0x08 :: is_instant_break set_instant_break
+);
+
+proto_varint_enum!(CombatEvent,
+    0x00 :: Enter,
+    0x01 :: End(CombatEndSpec),
+    0x02 :: EntityDead(CombatEntityDeadSpec)
+);
+
+proto_struct!(CombatEndSpec {
+    duration_ticks: VarInt,
+    entity_id: i32
+});
+
+proto_struct!(CombatEntityDeadSpec {
+    player_id: VarInt,
+    entity_id: i32,
+    message: Chat
+});
+
+proto_struct!(PlayerInfoAction<A> {
+    uuid: UUID4,
+    action: A
+});
+
+// the player list still sends game modes as VarInts
+proto_varint_enum!(PlayerInfoActionList,
+    0x00 :: Add(CountedArray<PlayerInfoAction<PlayerAddActionSpec>, VarInt>),
+    0x01 :: UpdateGameMode(CountedArray<PlayerInfoAction<VarInt>, VarInt>),
+    0x02 :: UpdateLatency(CountedArray<PlayerInfoAction<VarInt>, VarInt>),
+    0x03 :: UpdateDisplayName(CountedArray<PlayerInfoAction<Option<Chat>>, VarInt>),
+    0x04 :: Remove(CountedArray<UUID4, VarInt>)
+);
+
+proto_struct!(PlayerAddActionSpec {
+    name: String,
+    properties: CountedArray<PlayerAddProperty, VarInt>,
+    game_mode: VarInt,
+    ping_ms: VarInt,
+    display_name: Option<Chat>
+});
+
+proto_struct!(PlayerAddProperty {
+    name: String,
+    value: String,
+    signature: Option<String>
+});
+
+proto_byte_flag!(PositionAndLookFlags,
+    0x01 :: is_x_rel set_x_rel,
+    0x02 :: is_y_rel set_y_rel,
+    0x04 :: is_z_rel set_z_rel,
+    0x08 :: is_y_rotation_rel set_y_rotation_rel,
+    0x10 :: is_x_rotation_rel set_x_rotation_rel
+);
+
+proto_varint_enum!(RecipeUnlockAction,
+    0x00 :: Init(RecipeUnlockInitSpec),
+    0x01 :: Add(RecipeUnlockSpec),
+    0x02 :: Remove(RecipeUnlockSpec)
+);
+
+proto_struct!(RecipeUnlockInitSpec {
+    crafting_book_open: bool,
+    filtering_craftable: bool,
+    recipe_ids: CountedArray<VarInt, VarInt>,
+    displayed_recipe_ids: CountedArray<VarInt, VarInt>
+});
+
+proto_struct!(RecipeUnlockSpec {
+    crafting_book_open: bool,
+    filtering_craftable: bool,
+    recipe_ids: CountedArray<VarInt, VarInt>
+});
+
+proto_byte_enum!(EntityEffectKind,
+    0x01 :: Speed,
+    0x02 :: Slowness,
+    0x03 :: Haste,
+    0x04 :: MiningFatigue,
+    0x05 :: Strength,
+    0x06 :: InstantHealth,
+    0x07 :: InstantDamage,
+    0x08 :: JumpBoost,
+    0x09 :: Nausea,
+    0x0A :: Regeneration,
+    0x0B :: Resistance,
+    0x0C :: FireResistance,
+    0x0D :: WaterBreathing,
+    0x0E :: Invisibility,
+    0x0F :: Blindness,
+    0x10 :: NightVision,
+    0x11 :: Hunger,
+    0x12 :: Weakness,
+    0x13 :: Poison,
+    0x14 :: Wither,
+    0x15 :: HealthBoost,
+    0x16 :: Absorption,
+    0x17 :: Saturation,
+    0x18 :: Glowing,
+    0x19 :: Levetation,
+    0x1A :: Luck,
+    0x1B :: Unluck
+);
+
+proto_varint_enum!(WorldBorderAction,
+    0x00 :: SetSize(WorldBorderSetSizeSpec),
+    0x01 :: LerpSize(WorldBorderLerpSizeSpec),
+    0x02 :: SetCenter(TopDownPosition<f64>),
+    0x03 :: Initialize(WorldBorderInitializeSpec),
+    0x04 :: SetWarningTime(WorldBorderWarningTimeSpec),
+    0x05 :: SetWarningBlocks(WorldBorderWarningBlocksSpec)
+);
+
+proto_struct!(WorldBorderSetSizeSpec {
+    diameter: f64
+});
+
+proto_struct!(WorldBorderLerpSizeSpec {
+    old_diameter: f64,
+    new_diameter: f64,
+    speed: VarLong
+});
+
+proto_struct!(WorldBorderInitializeSpec {
+    position: TopDownPosition<f64>,
+    old_diameter: f64,
+    new_diameter: f64,
+    speed: VarLong,
+    portal_teleport_boundary: VarInt,
+    warning_time: VarInt,
+    warning_blocks: VarInt
+});
+
+proto_struct!(WorldBorderWarningTimeSpec {
+    warning_time: VarInt
+});
+
+proto_struct!(WorldBorderWarningBlocksSpec {
+    warning_blocks: VarInt
+});
+
+proto_byte_enum!(ScoreboardPosition,
+    0x00 :: List,
+    0x01 :: Sidebar,
+    0x02 :: BelowName,
+    0x03 :: TeamSpecific(i8)
+);
+
+proto_varint_enum!(EquipmentSlot,
+    0x00 :: MainHand,
+    0x01 :: OffHand,
+    0x02 :: ArmorBoots,
+    0x03 :: ArmorLeggings,
+    0x04 :: ArmorChestplate,
+    0x05 :: ArmorHelmet
+);
+
+proto_byte_enum!(ScoreboardObjectiveAction,
+    0x00 :: Create(ScoreboardObjectiveSpec),
+    0x01 :: Remove,
+    0x02 :: UpdateText(ScoreboardObjectiveSpec)
+);
+
+proto_str_enum!(ScoreboardObjectiveKind,
+    "integer" :: Integer,
+    "hearts" :: Hearts
+);
+
+proto_struct!(ScoreboardObjectiveSpec {
+    text: String,
+    kind: ScoreboardObjectiveKind
+});
+
+proto_byte_enum!(TeamAction,
+    0x00 :: Create(TeamActionCreateSpec),
+    0x01 :: Remove,
+    0x02 :: UpdateInfo(TeamActionUpdateInfoSpec),
+    0x03 :: AddPlayers(TeamActionPlayerList),
+    0x04 :: RemovePlayers(TeamActionPlayerList)
+);
+
+proto_str_enum!(TeamTagNameVisibility,
+    "always" :: Always,
+    "hideForOtherTeams" :: HideForOtherTeams,
+    "hideForOwnTeam" :: HideForOwnTeam,
+    "never" :: Never
+);
+
+proto_str_enum!(TeamCollisionRule,
+    "always" :: Always,
+    "pushOtherTeams" :: PushOtherTeams,
+    "pushOwnTeam" :: PushOwnTeam,
+    "never" :: Never
+);
+
+proto_struct!(TeamActionPlayerList {
+    entities: CountedArray<String, VarInt>
+});
+
+proto_struct!(TeamActionCreateSpec {
+    display_name: String,
+    prefix: String,
+    suffix: String,
+    friendly_flags: TeamFriendlyFlags,
+    tag_name_visibility: TeamTagNameVisibility,
+    collision_rule: TeamCollisionRule,
+    color: i8,
+    entities: CountedArray<String, VarInt>
+});
+
+proto_struct!(TeamActionUpdateInfoSpec {
+    display_name: String,
+    prefix: String,
+    suffix: String,
+    friendly_flags: TeamFriendlyFlags,
+    tag_name_visibility: TeamTagNameVisibility,
+    collision_rule: TeamCollisionRule,
+    color: i8
+});
+
+proto_byte_flag!(TeamFriendlyFlags,
+    0x01 :: allow_friendly_fire set_friendly_fire,
+    0x02 :: show_invisible_teammates set_show_invisible_teammates
+);
+
+proto_byte_enum!(UpdateScoreAction,
+    0x00 :: Upsert(VarInt),
+    0x01 :: Remove
+);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct UpdateScoreSpec {
+    pub objective_name: String,
+    pub action: UpdateScoreAction,
+}
+
+impl Serialize for UpdateScoreSpec {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        to.serialize_byte(self.action.id())?;
+        to.serialize_other(&self.objective_name)?;
+        self.action.serialize_body(to)?;
+
+        Ok(())
+    }
+}
+
+impl Deserialize for UpdateScoreSpec {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: action_id, data } = u8::mc_deserialize(data)?;
+        let Deserialized { value: objective_name, data } = String::mc_deserialize(data)?;
+
+        Ok(UpdateScoreAction::deserialize_with_id(action_id, data)?.map(move |action| {
+            Self {
+                objective_name,
+                action,
+            }
+        }))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for UpdateScoreSpec {
+    fn test_gen_random() -> Self {
+        Self {
+            objective_name: String::test_gen_random(),
+            action: UpdateScoreAction::test_gen_random(),
+        }
+    }
+}
+
+proto_varint_enum!(TitleActionSpec,
+    0x00 :: SetTitle(Chat),
+    0x01 :: SetSubtitle(Chat),
+    0x02 :: SetActionBar(Chat),
+    0x03 :: SetTimesAndDisplay(TitleTimesSpec),
+    0x04 :: Hide,
+    0x05 :: Reset
+);
+
+proto_struct!(TitleTimesSpec {
+    fade_in: i32,
+    stay: i32,
+    fade_out: i32
+});
+
+proto_struct!(AdvancementMappingEntrySpec {
+    key: String,
+    value: AdvancementSpec
+});
+
+proto_struct!(AdvancementSpec {
+    parent: Option<String>,
+    display: Option<AdvancementDisplaySpec>,
+    criteria: CountedArray<String, VarInt>,
+    requirements: CountedArray<CountedArray<String, VarInt>, VarInt>
+});
+
+proto_struct!(AdvancementDisplaySpec {
+    title: Chat,
+    description: Chat,
+    icon: Slot,
+    frame_type: AdvancementFrameType,
+    flags: AdvancementDisplayFlags,
+    position: Vec2<f32>
+});
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AdvancementDisplayFlags {
+    pub background_texture: Option<String>,
+    pub show_toast: bool,
+    pub hidden: bool,
+}
+
+impl Serialize for AdvancementDisplayFlags {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        let mut raw_flags: i32 = 0;
+        if self.background_texture.is_some() {
+            raw_flags |= 0x01;
+        }
+        if self.show_toast {
+            raw_flags |= 0x02;
+        }
+        if self.hidden {
+            raw_flags |= 0x04;
+        }
+
+        to.serialize_other(&raw_flags)?;
+        if let Some(texture) = &self.background_texture {
+            to.serialize_other(texture)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Deserialize for AdvancementDisplayFlags {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: raw_flags, data } = i32::mc_deserialize(data)?;
+        let has_background_texture = raw_flags & 0x01 != 0;
+        let show_toast = raw_flags & 0x02 != 0;
+        let hidden = raw_flags & 0x04 != 0;
+
+        Ok(if has_background_texture {
+            String::mc_deserialize(data)?.map(Some)
+        } else {
+            Deserialized { value: None, data }
+        }.map(move |background_texture| {
+            Self {
+                background_texture,
+                show_toast,
+                hidden,
+            }
+        }))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for AdvancementDisplayFlags {
+    fn test_gen_random() -> Self {
+        let background_texture = if rand::random::<bool>() {
+            Some(String::test_gen_random())
+        } else {
+            None
+        };
+
+        Self {
+            background_texture,
+            show_toast: rand::random(),
+            hidden: rand::random(),
+        }
+    }
+}
+
+proto_varint_enum!(AdvancementFrameType,
+    0x00 :: Task,
+    0x01 :: Challenge,
+    0x02 :: Goal
+);
+
+proto_struct!(AdvancementProgressEntrySpec {
+    key: String,
+    value: AdvancementProgressSpec
+});
+
+proto_struct!(AdvancementProgressSpec {
+    criteria: CountedArray<AdvancementCriteriaSpec, VarInt>
+});
+
+proto_struct!(AdvancementCriteriaSpec {
+    identifier: String,
+    progress: AdvancementCriterionProgressSpec
+});
+
+proto_struct!(AdvancementCriterionProgressSpec {
+    achieved_at: Option<i64>
+});
+
+proto_struct!(EntityPropertySpec {
+    key: String,
+    value: f64,
+    modifiers: CountedArray<EntityPropertyModifierSpec, VarInt>
+});
+
+proto_struct!(EntityPropertyModifierSpec {
+    uuid: UUID4,
+    amount: f64,
+    operation: EntityPropertyModifierOperation
+});
+
+proto_byte_enum!(EntityPropertyModifierOperation,
+    0x00 :: AddSubtractAmount,
+    0x01 :: AddSubtractAmountPercentOfCurrent,
+    0x02 :: MultiplyByAmountPercent
+);
+
+proto_byte_flag!(EntityEffectFlags,
+    0x01 :: is_ambient set_ambient,
+    0x02 :: is_show_particles set_show_particles
+);
+
+proto_varint_enum!(ClientStatusAction,
+    0x00 :: PerformRespawn,
+    0x01 :: RequestStats,
+    0x02 :: OpenInventory
+);
+
+proto_varint_enum!(ClientChatMode,
+    0x00 :: Enabled,
+    0x01 :: CommandsOnly,
+    0x02 :: Hidden
+);
+
+proto_varint_enum!(ClientMainHand,
+    0x00 :: Left,
+    0x01 :: Right
+);
+
+proto_byte_flag!(ClientDisplayedSkinParts,
+    0x01 :: is_cape_enabled set_cape_enabled,
+    0x02 :: is_jacket_enabled set_jacket_enabled,
+    0x04 :: is_left_sleeve_enabled set_left_sleeve_enabled,
+    0x08 :: is_right_sleeve_enabled set_right_sleeve_enabled,
+    0x10 :: is_left_pants_leg_enabled set_left_pants_leg_enabled,
+    0x20 :: is_right_pant_legs_enabled set_right_pant_legs_enabled,
+    0x40 :: is_hat_enabled set_hat_enabled
+);
+
+proto_varint_enum!(InventoryOperationMode,
+    0x00 :: MouseClick,
+    0x01 :: ShiftClick,
+    0x02 :: NumberClick,
+    0x03 :: MiddleClick,
+    0x04 :: DropClick,
+    0x05 :: Drag,
+    0x06 :: DoubleClick
+);
+
+proto_struct!(InteractAtSpec {
+    target_position: Vec3<f32>,
+    hand: Hand
+});
+
+proto_varint_enum!(InteractKind,
+    0x00 :: Interact(Hand),
+    0x01 :: Attack,
+    0x02 :: InteractAt(InteractAtSpec)
+);
+
+proto_byte_flag!(ClientPlayerAbilities,
+    0x01 :: is_creative set_creative,
+    0x02 :: is_flying set_flying,
+    0x04 :: is_fly_enabled set_fly_enabled,
+    0x08 :: is_damaged_disabled set_damaged_disabled
+);
+
+proto_varint_enum!(PlayerDiggingStatus,
+    0x00 :: Started,
+    0x01 :: Cancelled,
+    0x02 :: Finished,
+    0x03 :: DropStack,
+    0x04 :: DropItem,
+    0x05 :: ShootArrowOrFishEating,
+    0x06 :: SwapItemInHand
+);
+
+proto_byte_enum!(DiggingFace,
+    0x00 :: Bottom,
+    0x01 :: Top,
+    0x02 :: North,
+    0x03 :: South,
+    0x04 :: West,
+    0x05 :: East
+);
+
+proto_varint_enum!(BlockFace,
+    0x00 :: Bottom,
+    0x01 :: Top,
+    0x02 :: North,
+    0x03 :: South,
+    0x04 :: West,
+    0x05 :: East
+);
+
+proto_varint_enum!(EntityActionKind,
+    0x00 :: StartSneaking,
+    0x01 :: StopSneaking,
+    0x02 :: LeaveBed,
+    0x03 :: StartSprinting,
+    0x04 :: StopSprinting,
+    0x05 :: StartJumpWithHorse,
+    0x06 :: StopJumpWithHorse,
+    0x07 :: OpenHorseInventory,
+    0x08 :: StartFlyingWithElytra
+);
+
+proto_byte_flag!(SteerVehicleFlags,
+    0x01 :: is_jump set_jump,
+    0x02 :: is_unmount set_unmount
+);
+
+proto_varint_enum!(CraftingBookData,
+    0x00 :: Displayed(i32),
+    0x01 :: Status(CraftingBookStatusSpec)
+);
+
+proto_struct!(CraftingBookStatusSpec {
+    crafting_book_open: bool,
+    crafting_filter: bool
+});
+
+proto_varint_enum!(ResourcePackStatus,
+    0x00 :: Loaded,
+    0x01 :: Declined,
+    0x02 :: FailedDownload,
+    0x03 :: Accepted
+);
+
+proto_varint_enum!(AdvancementTabAction,
+    0x00 :: Opened(String),
+    0x01 :: Closed
+);
+
+proto_varint_enum!(EntityDirection,
+    0x00 :: Down,
+    0x01 :: Up,
+    0x02 :: North,
+    0x03 :: South,
+    0x04 :: West,
+    0x05 :: East
+);
+
+// items are still identified by a numeric id and a damage value before the flattening
+#[derive(Debug, PartialEq, Clone)]
+pub struct ItemStack {
+    pub item_id: i16,
+    pub item_count: i8,
+    pub damage: i16,
+    pub nbt: Option<nbt::NamedTag>,
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for ItemStack {
+    fn test_gen_random() -> Self {
+        Self {
+            item_id: (rand::random::<u16>() % 512) as i16,
+            item_count: i8::test_gen_random() % 65,
+            damage: i16::test_gen_random(),
+            nbt: <Option<nbt::NamedTag>>::test_gen_random(),
+        }
+    }
+}
+
+const EMPTY_SLOT_ID: i16 = -1;
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Slot(pub Option<ItemStack>);
+
+impl Serialize for Slot {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        match &self.0 {
+            None => to.serialize_other(&EMPTY_SLOT_ID),
+            Some(item) => {
+                to.serialize_other(&item.item_id)?;
+                to.serialize_other(&item.item_count)?;
+                to.serialize_other(&item.damage)?;
+                match item.nbt.as_ref() {
+                    Some(nbt) => to.serialize_bytes(nbt.bytes().as_slice()),
+                    None => to.serialize_byte(nbt::Tag::End.id()),
+                }
+            }
+        }
+    }
+}
+
+impl Deserialize for Slot {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: item_id, data } = i16::mc_deserialize(data)?;
+        if item_id == EMPTY_SLOT_ID {
+            return Deserialized::ok(Slot(None), data);
+        }
+
+        let Deserialized { value: item_count, data } = i8::mc_deserialize(data)?;
+        let Deserialized { value: damage, data } = i16::mc_deserialize(data)?;
+        if data.is_empty() {
+            return Err(DeserializeErr::Eof);
+        }
+
+        Ok(match data[0] {
+            0x00 => Deserialized {
+                value: None,
+                data: &data[1..],
+            },
+            _ => nbt::read_named_tag(data)?.map(Some),
+        }.map(move |nbt| Slot(Some(ItemStack {
+            item_id,
+            item_count,
+            damage,
+            nbt,
+        }))))
+    }
+}
+
+impl From<Option<ItemStack>> for Slot {
+    fn from(other: Option<ItemStack>) -> Self {
+        Slot(other)
+    }
+}
+
+impl From<ItemStack> for Slot {
+    fn from(other: ItemStack) -> Self {
+        Slot(Some(other))
+    }
+}
+
+impl From<Slot> for Option<ItemStack> {
+    fn from(other: Slot) -> Self {
+        other.0
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for Slot {
+    fn test_gen_random() -> Self {
+        Slot(<Option<ItemStack>>::test_gen_random())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct EntityMetadata {
+    pub fields: Vec<EntityMetadataField>
+}
+
+impl Serialize for EntityMetadata {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        for field in &self.fields {
+            to.serialize_byte(field.index)?;
+            to.serialize_other(&field.data)?;
+        }
+        to.serialize_byte(0xFF)
+    }
+}
+
+impl Deserialize for EntityMetadata {
+    fn mc_deserialize(mut data: &[u8]) -> DeserializeResult<'_, Self> {
+        let mut fields = Vec::new();
+        loop {
+            let Deserialized { value: index, data: rest } = u8::mc_deserialize(data)?;
+            data = rest;
+            if index == 0xFF {
+                break;
+            }
+
+            let Deserialized { value: field, data: rest } = EntityMetadataFieldData::mc_deserialize(data)?;
+            data = rest;
+            fields.push(EntityMetadataField{
+                index,
+                data: field,
+            });
+        }
+
+        Deserialized::ok(Self{
+            fields,
+        }, data)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for EntityMetadata {
+    fn test_gen_random() -> Self {
+        let n_fields = rand::random::<usize>() % 10;
+        let mut fields = Vec::with_capacity(n_fields);
+        for i in 0..n_fields {
+            fields.push(EntityMetadataField{
+                index: i as u8,
+                data: EntityMetadataFieldData::test_gen_random(),
+            });
+        }
+
+        Self {
+            fields,
+        }
+    }
+}
+
+impl EntityMetadata {
+    pub fn set(&mut self, index: u8, data: EntityMetadataFieldData) {
+        for field in &mut self.fields {
+            if field.index == index {
+                field.data = data;
+                return;
+            }
+        }
+
+        self.fields.push(EntityMetadataField{
+            index,
+            data,
+        })
+    }
+
+    pub fn remove(&mut self, index: u8) -> bool {
+        if let Some(i) = self.fields.iter().position(move |field| field.index == index) {
+            self.fields.remove(i);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn get(&self, index: u8) -> Option<&EntityMetadataFieldData> {
+        self.fields.iter()
+            .find(move |field| field.index == index)
+            .map(move |field| &field.data)
+    }
+
+    pub fn get_mut(&mut self, index: u8) -> Option<&mut EntityMetadataFieldData> {
+        self.fields.iter_mut()
+            .find(move |field| field.index == index)
+            .map(move |field| &mut field.data)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EntityMetadataField {
+    pub index: u8,
+    pub data: EntityMetadataFieldData
+}
+
+// the 1.12 type table has no optional chat, particle, villager data or pose entries
+proto_varint_enum!(EntityMetadataFieldData,
+    0x00 :: Byte(i8),
+    0x01 :: VarInt(VarInt),
+    0x02 :: Float(f32),
+    0x03 :: String(String),
+    0x04 :: Chat(Chat),
+    0x05 :: Slot(Slot),
+    0x06 :: Boolean(bool),
+    0x07 :: Rotation(Vec3<f32>),
+    0x08 :: Position(LegacyIntPosition),
+    0x09 :: OptPosition(Option<LegacyIntPosition>),
+    0x0A :: Direction(EntityDirection),
+    0x0B :: OptUUID(Option<UUID4>),
+    0x0C :: OptBlockId(VarInt),
+    0x0D :: NBT(NamedNbtTag)
+);
+
+pub const CHUNK_SECTIONS: usize = 16;
+pub const CHUNK_SECTION_BLOCKS: usize = 4096;
+pub const CHUNK_LIGHT_DATA_LENGTH: usize = 2048;
+pub const CHUNK_BIOMES_LENGTH: usize = 256;
+
+// bits per block used by the global palette, which sends an empty palette
+pub const GLOBAL_PALETTE_BITS_PER_BLOCK: u8 = 13;
+
+// one 16x16x16 section of the pre-1.13 chunk format
+#[derive(Clone, PartialEq, Debug)]
+pub struct ChunkSection {
+    pub bits_per_block: u8,
+    pub palette: CountedArray<VarInt, VarInt>,
+    pub data: CountedArray<i64, VarInt>,
+    pub block_light: Box<[u8; CHUNK_LIGHT_DATA_LENGTH]>,
+    pub sky_light: Option<Box<[u8; CHUNK_LIGHT_DATA_LENGTH]>>,
+}
+
+impl ChunkSection {
+    // sky light is only sent in dimensions which have a sky, which the section itself cannot tell
+    pub fn deserialize(has_sky_light: bool, data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: bits_per_block, data } = u8::mc_deserialize(data)?;
+        let Deserialized { value: palette, data } = <CountedArray<VarInt, VarInt>>::mc_deserialize(data)?;
+        let Deserialized { value: block_data, data } = <CountedArray<i64, VarInt>>::mc_deserialize(data)?;
+        let Deserialized { value: block_light, data } = read_light_data(data)?;
+        let Deserialized { value: sky_light, data } = if has_sky_light {
+            read_light_data(data)?.map(Some)
+        } else {
+            Deserialized { value: None, data }
+        };
+
+        Deserialized::ok(Self {
+            bits_per_block,
+            palette,
+            data: block_data,
+            block_light,
+            sky_light,
+        }, data)
+    }
+
+    pub fn is_global_palette(&self) -> bool {
+        self.palette.is_empty()
+    }
+
+    // block index is (y * 16 + z) * 16 + x, values may span two longs
+    pub fn get_raw(&self, x: usize, y: usize, z: usize) -> Option<u32> {
+        if x >= 16 || y >= 16 || z >= 16 || self.bits_per_block == 0 || self.bits_per_block > 32 {
+            return None;
+        }
+
+        let bits = self.bits_per_block as usize;
+        let index = (y * 16 + z) * 16 + x;
+        let start_bit = index * bits;
+        let start_long = start_bit / 64;
+        let end_long = (start_bit + bits - 1) / 64;
+        let offset = start_bit % 64;
+        let mask = (1u64 << bits) - 1;
+
+        let mut value = (*self.data.get(start_long)? as u64) >> offset;
+        if end_long != start_long {
+            value |= (*self.data.get(end_long)? as u64) << (64 - offset);
+        }
+
+        Some((value & mask) as u32)
+    }
+
+    pub fn get(&self, x: usize, y: usize, z: usize) -> Option<u32> {
+        let raw = self.get_raw(x, y, z)?;
+        if self.is_global_palette() {
+            Some(raw)
+        } else {
+            self.palette.get(raw as usize).map(move |id| id.0 as u32)
+        }
+    }
+}
+
+fn read_light_data(data: &[u8]) -> DeserializeResult<'_, Box<[u8; CHUNK_LIGHT_DATA_LENGTH]>> {
+    if data.len() < CHUNK_LIGHT_DATA_LENGTH {
+        return Err(DeserializeErr::Eof);
+    }
+
+    let (light, rest) = data.split_at(CHUNK_LIGHT_DATA_LENGTH);
+    let mut out = Box::new([0u8; CHUNK_LIGHT_DATA_LENGTH]);
+    out.copy_from_slice(light);
+    Deserialized::ok(out, rest)
+}
+
+impl Serialize for ChunkSection {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        to.serialize_other(&self.bits_per_block)?;
+        to.serialize_other(&self.palette)?;
+        to.serialize_other(&self.data)?;
+        to.serialize_bytes(&self.block_light[..])?;
+        if let Some(sky_light) = &self.sky_light {
+            to.serialize_bytes(&sky_light[..])?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for ChunkSection {
+    fn test_gen_random() -> Self {
+        let bits_per_block = 4 + rand::random::<u8>() % 5;
+        let n_longs = CHUNK_SECTION_BLOCKS * bits_per_block as usize / 64;
+        let palette: Vec<VarInt> = (0..(1 << bits_per_block)).map(move |_| VarInt::test_gen_random()).collect();
+        let data: Vec<i64> = (0..n_longs).map(move |_| i64::test_gen_random()).collect();
+        let mut block_light = Box::new([0u8; CHUNK_LIGHT_DATA_LENGTH]);
+        for elem in block_light.iter_mut() {
+            *elem = rand::random();
+        }
+
+        Self {
+            bits_per_block,
+            palette: palette.into(),
+            data: data.into(),
+            sky_light: Some(block_light.clone()),
+            block_light,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct ChunkColumn {
+    pub sections: [Option<ChunkSection>; CHUNK_SECTIONS],
+    pub biomes: Option<Box<[u8; CHUNK_BIOMES_LENGTH]>>,
+}
+
+impl ChunkColumn {
+    pub fn read(primary_bit_mask: i32, full_chunk: bool, has_sky_light: bool, mut data: &[u8]) -> Result<Self, DeserializeErr> {
+        let mut out = Self::default();
+        for (y, section) in out.sections.iter_mut().enumerate() {
+            if primary_bit_mask & (1 << y) != 0 {
+                let Deserialized { value, data: rest } = ChunkSection::deserialize(has_sky_light, data)?;
+                data = rest;
+                *section = Some(value);
+            }
+        }
+
+        if full_chunk {
+            if data.len() < CHUNK_BIOMES_LENGTH {
+                return Err(DeserializeErr::Eof);
+            }
+
+            let mut biomes = Box::new([0u8; CHUNK_BIOMES_LENGTH]);
+            biomes.copy_from_slice(&data[..CHUNK_BIOMES_LENGTH]);
+            out.biomes = Some(biomes);
+        }
+
+        Ok(out)
+    }
+
+    pub fn primary_bit_mask(&self) -> VarInt {
+        VarInt(self.sections.iter()
+            .enumerate()
+            .filter(move |(_, section)| section.is_some())
+            .fold(0, move |mask, (y, _)| mask | (1 << y)))
+    }
+
+    pub fn get(&self, x: usize, y: usize, z: usize) -> Option<u32> {
+        self.sections.get(y / 16)?.as_ref()?.get(x, y % 16, z)
+    }
+}
+
+impl Serialize for ChunkColumn {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        for section in self.sections.iter().flatten() {
+            to.serialize_other(section)?;
+        }
+
+        if let Some(biomes) = &self.biomes {
+            to.serialize_bytes(&biomes[..])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl PlayChunkDataSpec {
+    pub fn column(&self, has_sky_light: bool) -> Result<ChunkColumn, DeserializeErr> {
+        ChunkColumn::read(self.primary_bit_mask.0, self.full_chunk, has_sky_light, self.data.as_slice())
+    }
+
+    pub fn from_column(position: ChunkPosition<i32>, column: &ChunkColumn, block_entities: Vec<NamedNbtTag>) -> Result<Self, SerializeErr> {
+        let mut serializer = BytesSerializer::default();
+        serializer.serialize_other(column)?;
+
+        Ok(Self {
+            position,
+            full_chunk: column.biomes.is_some(),
+            primary_bit_mask: column.primary_bit_mask(),
+            data: serializer.into_bytes().into(),
+            block_entities: block_entities.into(),
+        })
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+pub mod tests {
+    use super::*;
+    use crate::packet_test_cases;
+    use crate::protocol::TestRandom;
+
+    packet_test_cases!(RawPacket340, Packet340, Handshake, HandshakeSpec,
+        test_handshake, bench_write_handshake, bench_read_handshake);
+
+    packet_test_cases!(RawPacket340, Packet340, StatusRequest, StatusRequestSpec,
+        test_status_request, bench_write_status_request, bench_read_status_request);
+
+    packet_test_cases!(RawPacket340, Packet340, StatusPing, StatusPingSpec,
+        test_status_ping, bench_write_status_ping, bench_read_status_ping);
+
+    packet_test_cases!(RawPacket340, Packet340, StatusResponse, StatusResponseSpec,
+        test_status_response, bench_write_status_response, bench_read_status_response);
+
+    packet_test_cases!(RawPacket340, Packet340, StatusPong, StatusPongSpec,
+        test_status_pong, bench_write_status_pong, bench_read_status_pong);
+
+    packet_test_cases!(RawPacket340, Packet340, LoginDisconnect, LoginDisconnectSpec,
+        test_login_disconnect, bench_write_login_disconnect, bench_read_login_disconnect);
+
+    packet_test_cases!(RawPacket340, Packet340, LoginEncryptionRequest, LoginEncryptionRequestSpec,
+        test_login_encryption_request, bench_write_login_encryption_request, bench_read_login_encryption_request);
+
+    packet_test_cases!(RawPacket340, Packet340, LoginSuccess, LoginSuccessSpec,
+        test_login_success, bench_write_login_success, bench_read_login_success);
+
+    packet_test_cases!(RawPacket340, Packet340, LoginSetCompression, LoginSetCompressionSpec,
+        test_login_set_compression, bench_write_login_set_compression, bench_read_login_set_compression);
+
+    packet_test_cases!(RawPacket340, Packet340, LoginStart, LoginStartSpec,
+        test_login_start, bench_write_login_start, bench_read_login_start);
+
+    packet_test_cases!(RawPacket340, Packet340, LoginEncryptionResponse, LoginEncryptionResponseSpec,
+        test_login_encryption_response, bench_write_login_encryption_response, bench_read_login_encryption_response);
+
+    packet_test_cases!(RawPacket340, Packet340, PlaySpawnEntity, PlaySpawnEntitySpec,
+        test_play_spawn_entity, bench_write_play_spawn_entity, bench_read_play_spawn_entity);
+
+    packet_test_cases!(RawPacket340, Packet340, PlaySpawnExperienceOrb, PlaySpawnExperienceOrbSpec,
+        test_play_spawn_experience_orb, bench_write_play_spawn_experience_orb, bench_read_play_spawn_experience_orb);
+
+    packet_test_cases!(RawPacket340, Packet340, PlaySpawnWeatherEntity, PlaySpawnWeatherEntitySpec,
+        test_play_spawn_weather_entity, bench_write_play_spawn_weather_entity, bench_read_play_spawn_weather_entity);
+
+    packet_test_cases!(RawPacket340, Packet340, PlaySpawnLivingEntity, PlaySpawnLivingEntitySpec,
+        test_play_spawn_living_entity, bench_write_play_spawn_living_entity, bench_read_play_spawn_living_entity);
+
+    packet_test_cases!(RawPacket340, Packet340, PlaySpawnPainting, PlaySpawnPaintingSpec,
+        test_play_spawn_painting, bench_write_play_spawn_painting, bench_read_play_spawn_painting);
+
+    packet_test_cases!(RawPacket340, Packet340, PlaySpawnPlayer, PlaySpawnPlayerSpec,
+        test_play_spawn_player, bench_write_play_spawn_player, bench_read_play_spawn_player);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayEntityAnimation, PlayEntityAnimationSpec,
+        test_play_entity_animation, bench_write_play_entity_animation, bench_read_play_entity_animation);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayStatistics, PlayStatisticsSpec,
+        test_play_statistics, bench_write_play_statistics, bench_read_play_statistics);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayBlockBreakAnimation, PlayBlockBreakAnimationSpec,
+        test_play_block_break_animation, bench_write_play_block_break_animation, bench_read_play_block_break_animation);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayBlockEntityData, PlayBlockEntityDataSpec,
+        test_play_block_entity_data, bench_write_play_block_entity_data, bench_read_play_block_entity_data);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayBlockAction, PlayBlockActionSpec,
+        test_play_block_action, bench_write_play_block_action, bench_read_play_block_action);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayBlockChange, PlayBlockChangeSpec,
+        test_play_block_change, bench_write_play_block_change, bench_read_play_block_change);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayBossBar, PlayBossBarSpec,
+        test_play_boss_bar, bench_write_play_boss_bar, bench_read_play_boss_bar);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayServerDifficulty, PlayServerDifficultySpec,
+        test_play_server_difficulty, bench_write_play_server_difficulty, bench_read_play_server_difficulty);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayTabComplete, PlayTabCompleteSpec,
+        test_play_tab_complete, bench_write_play_tab_complete, bench_read_play_tab_complete);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayServerChatMessage, PlayServerChatMessageSpec,
+        test_play_server_chat_message, bench_write_play_server_chat_message, bench_read_play_server_chat_message);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayMultiBlockChange, PlayMultiBlockChangeSpec,
+        test_play_multi_block_change, bench_write_play_multi_block_change, bench_read_play_multi_block_change);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayServerWindowConfirmation, PlayServerWindowConfirmationSpec,
+        test_play_server_window_confirmation, bench_write_play_server_window_confirmation, bench_read_play_server_window_confirmation);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayServerCloseWindow, PlayServerCloseWindowSpec,
+        test_play_server_close_window, bench_write_play_server_close_window, bench_read_play_server_close_window);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayOpenWindow, PlayOpenWindowSpec,
+        test_play_open_window, bench_write_play_open_window, bench_read_play_open_window);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayWindowItems, PlayWindowItemsSpec,
+        test_play_window_items, bench_write_play_window_items, bench_read_play_window_items);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayWindowProperty, PlayWindowPropertySpec,
+        test_play_window_property, bench_write_play_window_property, bench_read_play_window_property);
+
+    packet_test_cases!(RawPacket340, Packet340, PlaySetSlot, PlaySetSlotSpec,
+        test_play_set_slot, bench_write_play_set_slot, bench_read_play_set_slot);
+
+    packet_test_cases!(RawPacket340, Packet340, PlaySetCooldown, PlaySetCooldownSpec,
+        test_play_set_cooldown, bench_write_play_set_cooldown, bench_read_play_set_cooldown);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayServerPluginMessage, PlayServerPluginMessageSpec,
+        test_play_server_plugin_message, bench_write_play_server_plugin_message, bench_read_play_server_plugin_message);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayNamedSoundEffect, PlayNamedSoundEffectSpec,
+        test_play_named_sound_effect, bench_write_play_named_sound_effect, bench_read_play_named_sound_effect);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayDisconnect, PlayDisconnectSpec,
+        test_play_disconnect, bench_write_play_disconnect, bench_read_play_disconnect);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayEntityStatus, PlayEntityStatusSpec,
+        test_play_entity_status, bench_write_play_entity_status, bench_read_play_entity_status);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayExplosion, PlayExplosionSpec,
+        test_play_explosion, bench_write_play_explosion, bench_read_play_explosion);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayUnloadChunk, PlayUnloadChunkSpec,
+        test_play_unload_chunk, bench_write_play_unload_chunk, bench_read_play_unload_chunk);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayChangeGameState, PlayChangeGameStateSpec,
+        test_play_change_game_state, bench_write_play_change_game_state, bench_read_play_change_game_state);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayServerKeepAlive, PlayServerKeepAliveSpec,
+        test_play_server_keep_alive, bench_write_play_server_keep_alive, bench_read_play_server_keep_alive);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayChunkData, PlayChunkDataSpec,
+        test_play_chunk_data, bench_write_play_chunk_data, bench_read_play_chunk_data);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayEffect, PlayEffectSpec,
+        test_play_effect, bench_write_play_effect, bench_read_play_effect);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayParticle, PlayParticleSpec,
+        test_play_particle, bench_write_play_particle, bench_read_play_particle);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayJoinGame, PlayJoinGameSpec,
+        test_play_join_game, bench_write_play_join_game, bench_read_play_join_game);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayMapData, PlayMapDataSpec,
+        test_play_map_data, bench_write_play_map_data, bench_read_play_map_data);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayEntityMovement, PlayEntityMovementSpec,
+        test_play_entity_movement, bench_write_play_entity_movement, bench_read_play_entity_movement);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayEntityPosition, PlayEntityPositionSpec,
+        test_play_entity_position, bench_write_play_entity_position, bench_read_play_entity_position);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayEntityPositionAndRotation, PlayEntityPositionAndRotationSpec,
+        test_play_entity_position_and_rotation, bench_write_play_entity_position_and_rotation, bench_read_play_entity_position_and_rotation);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayEntityRotation, PlayEntityRotationSpec,
+        test_play_entity_rotation, bench_write_play_entity_rotation, bench_read_play_entity_rotation);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayServerVehicleMove, PlayEntityVehicleMoveSpec,
+        test_play_server_vehicle_move, bench_write_play_server_vehicle_move, bench_read_play_server_vehicle_move);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayOpenSignEditor, PlayOpenSignEditorSpec,
+        test_play_open_sign_editor, bench_write_play_open_sign_editor, bench_read_play_open_sign_editor);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayCraftRecipeResponse, PlayCraftRecipeResponseSpec,
+        test_play_craft_recipe_response, bench_write_play_craft_recipe_response, bench_read_play_craft_recipe_response);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayServerPlayerAbilities, PlayServerPlayerAbilitiesSpec,
+        test_play_server_player_abilities, bench_write_play_server_player_abilities, bench_read_play_server_player_abilities);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayCombatEvent, PlayCombatEventSpec,
+        test_play_combat_event, bench_write_play_combat_event, bench_read_play_combat_event);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayPlayerInfo, PlayPlayerInfoSpec,
+        test_play_player_info, bench_write_play_player_info, bench_read_play_player_info);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayServerPlayerPositionAndLook, PlayServerPlayerPositionAndLookSpec,
+        test_play_server_player_position_and_look, bench_write_play_server_player_position_and_look, bench_read_play_server_player_position_and_look);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayUseBed, PlayUseBedSpec,
+        test_play_use_bed, bench_write_play_use_bed, bench_read_play_use_bed);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayUnlockRecipes, PlayUnlockRecipesSpec,
+        test_play_unlock_recipes, bench_write_play_unlock_recipes, bench_read_play_unlock_recipes);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayDestroyEntities, PlayDestroyEntitiesSpec,
+        test_play_destroy_entities, bench_write_play_destroy_entities, bench_read_play_destroy_entities);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayRemoveEntityEffect, PlayRemoveEntityEffectSpec,
+        test_play_remove_entity_effect, bench_write_play_remove_entity_effect, bench_read_play_remove_entity_effect);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayResourcePackSend, PlayResourcePackSendSpec,
+        test_play_resource_pack_send, bench_write_play_resource_pack_send, bench_read_play_resource_pack_send);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayRespawn, PlayRespawnSpec,
+        test_play_respawn, bench_write_play_respawn, bench_read_play_respawn);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayEntityHeadLook, PlayEntityHeadLookSpec,
+        test_play_entity_head_look, bench_write_play_entity_head_look, bench_read_play_entity_head_look);
+
+    packet_test_cases!(RawPacket340, Packet340, PlaySelectAdvancementTab, PlaySelectAdvancementTabSpec,
+        test_play_select_advancement_tab, bench_write_play_select_advancement_tab, bench_read_play_select_advancement_tab);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayWorldBorder, PlayWorldBorderSpec,
+        test_play_world_border, bench_write_play_world_border, bench_read_play_world_border);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayCamera, PlayCameraSpec,
+        test_play_camera, bench_write_play_camera, bench_read_play_camera);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayServerHeldItemChange, PlayServerHeldItemChangeSpec,
+        test_play_server_held_item_change, bench_write_play_server_held_item_change, bench_read_play_server_held_item_change);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayDisplayScoreboard, PlayDisplayScoreboardSpec,
+        test_play_display_scoreboard, bench_write_play_display_scoreboard, bench_read_play_display_scoreboard);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayEntityMetadata, PlayEntityMetadataSpec,
+        test_play_entity_metadata, bench_write_play_entity_metadata, bench_read_play_entity_metadata);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayAttachEntity, PlayAttachEntitySpec,
+        test_play_attach_entity, bench_write_play_attach_entity, bench_read_play_attach_entity);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayEntityVelocity, PlayEntityVelocitySpec,
+        test_play_entity_velocity, bench_write_play_entity_velocity, bench_read_play_entity_velocity);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayEntityEquipment, PlayEntityEquipmentSpec,
+        test_play_entity_equipment, bench_write_play_entity_equipment, bench_read_play_entity_equipment);
+
+    packet_test_cases!(RawPacket340, Packet340, PlaySetExperience, PlaySetExperienceSpec,
+        test_play_set_experience, bench_write_play_set_experience, bench_read_play_set_experience);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayUpdateHealth, PlayUpdateHealthSpec,
+        test_play_update_health, bench_write_play_update_health, bench_read_play_update_health);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayScoreboardObjective, PlayScoreboardObjectiveSpec,
+        test_play_scoreboard_objective, bench_write_play_scoreboard_objective, bench_read_play_scoreboard_objective);
+
+    packet_test_cases!(RawPacket340, Packet340, PlaySetPassengers, PlaySetPassengersSpec,
+        test_play_set_passengers, bench_write_play_set_passengers, bench_read_play_set_passengers);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayTeams, PlayTeamsSpec,
+        test_play_teams, bench_write_play_teams, bench_read_play_teams);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayUpdateScore, PlayUpdateScoreSpec,
+        test_play_update_score, bench_write_play_update_score, bench_read_play_update_score);
+
+    packet_test_cases!(RawPacket340, Packet340, PlaySpawnPosition, PlaySpawnPositionSpec,
+        test_play_spawn_position, bench_write_play_spawn_position, bench_read_play_spawn_position);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayTimeUpdate, PlayTimeUpdateSpec,
+        test_play_time_update, bench_write_play_time_update, bench_read_play_time_update);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayTitle, PlayTitleSpec,
+        test_play_title, bench_write_play_title, bench_read_play_title);
+
+    packet_test_cases!(RawPacket340, Packet340, PlaySoundEffect, PlaySoundEffectSpec,
+        test_play_sound_effect, bench_write_play_sound_effect, bench_read_play_sound_effect);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayPlayerListHeaderAndFooter, PlayPlayerListHeaderAndFooterSpec,
+        test_play_player_list_header_and_footer, bench_write_play_player_list_header_and_footer, bench_read_play_player_list_header_and_footer);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayCollectItem, PlayCollectItemSpec,
+        test_play_collect_item, bench_write_play_collect_item, bench_read_play_collect_item);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayEntityTeleport, PlayEntityTeleportSpec,
+        test_play_entity_teleport, bench_write_play_entity_teleport, bench_read_play_entity_teleport);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayAdvancements, PlayAdvancementsSpec,
+        test_play_advancements, bench_write_play_advancements, bench_read_play_advancements);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayEntityProperties, PlayEntityPropertiesSpec,
+        test_play_entity_properties, bench_write_play_entity_properties, bench_read_play_entity_properties);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayEntityEffect, PlayEntityEffectSpec,
+        test_play_entity_effect, bench_write_play_entity_effect, bench_read_play_entity_effect);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayTeleportConfirm, PlayTeleportConfirmSpec,
+        test_play_teleport_confirm, bench_write_play_teleport_confirm, bench_read_play_teleport_confirm);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayClientTabComplete, PlayClientTabCompleteSpec,
+        test_play_client_tab_complete, bench_write_play_client_tab_complete, bench_read_play_client_tab_complete);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayClientChatMessage, PlayClientChatMessageSpec,
+        test_play_client_chat_message, bench_write_play_client_chat_message, bench_read_play_client_chat_message);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayClientStatus, PlayClientStatusSpec,
+        test_play_client_status, bench_write_play_client_status, bench_read_play_client_status);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayClientSettings, PlayClientSettingsSpec,
+        test_play_client_settings, bench_write_play_client_settings, bench_read_play_client_settings);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayClientWindowConfirmation, PlayClientWindowConfirmationSpec,
+        test_play_client_window_confirmation, bench_write_play_client_window_confirmation, bench_read_play_client_window_confirmation);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayEnchantItem, PlayEnchantItemSpec,
+        test_play_enchant_item, bench_write_play_enchant_item, bench_read_play_enchant_item);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayClickWindow, PlayClickWindowSpec,
+        test_play_click_window, bench_write_play_click_window, bench_read_play_click_window);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayClientCloseWindow, PlayClientCloseWindowSpec,
+        test_play_client_close_window, bench_write_play_client_close_window, bench_read_play_client_close_window);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayClientPluginMessage, PlayClientPluginMessageSpec,
+        test_play_client_plugin_message, bench_write_play_client_plugin_message, bench_read_play_client_plugin_message);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayInteractEntity, PlayInteractEntitySpec,
+        test_play_interact_entity, bench_write_play_interact_entity, bench_read_play_interact_entity);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayClientKeepAlive, PlayClientKeepAliveSpec,
+        test_play_client_keep_alive, bench_write_play_client_keep_alive, bench_read_play_client_keep_alive);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayPlayerMovement, PlayPlayerMovementSpec,
+        test_play_player_movement, bench_write_play_player_movement, bench_read_play_player_movement);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayPlayerPosition, PlayPlayerPositionSpec,
+        test_play_player_position, bench_write_play_player_position, bench_read_play_player_position);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayClientPlayerPositionAndRotation, PlayClientPlayerPositionAndRotationSpec,
+        test_play_client_player_position_and_rotation, bench_write_play_client_player_position_and_rotation, bench_read_play_client_player_position_and_rotation);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayPlayerRotation, PlayPlayerRotationSpec,
+        test_play_player_rotation, bench_write_play_player_rotation, bench_read_play_player_rotation);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayClientVehicleMove, PlayClientVehicleMoveSpec,
+        test_play_client_vehicle_move, bench_write_play_client_vehicle_move, bench_read_play_client_vehicle_move);
+
+    packet_test_cases!(RawPacket340, Packet340, PlaySteerBoat, PlaySteerBoatSpec,
+        test_play_steer_boat, bench_write_play_steer_boat, bench_read_play_steer_boat);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayCraftRecipeRequest, PlayCraftRecipeRequestSpec,
+        test_play_craft_recipe_request, bench_write_play_craft_recipe_request, bench_read_play_craft_recipe_request);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayClientPlayerAbilities, PlayClientPlayerAbilitiesSpec,
+        test_play_client_player_abilities, bench_write_play_client_player_abilities, bench_read_play_client_player_abilities);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayPlayerDigging, PlayPlayerDiggingSpec,
+        test_play_player_digging, bench_write_play_player_digging, bench_read_play_player_digging);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayEntityAction, PlayEntityActionSpec,
+        test_play_entity_action, bench_write_play_entity_action, bench_read_play_entity_action);
+
+    packet_test_cases!(RawPacket340, Packet340, PlaySteerVehicle, PlaySteerVehicleSpec,
+        test_play_steer_vehicle, bench_write_play_steer_vehicle, bench_read_play_steer_vehicle);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayCraftingBookData, PlayCraftingBookDataSpec,
+        test_play_crafting_book_data, bench_write_play_crafting_book_data, bench_read_play_crafting_book_data);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayResourcePackStatus, PlayResourcePackStatusSpec,
+        test_play_resource_pack_status, bench_write_play_resource_pack_status, bench_read_play_resource_pack_status);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayAdvancementTab, PlayAdvancementTabSpec,
+        test_play_advancement_tab, bench_write_play_advancement_tab, bench_read_play_advancement_tab);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayClientHeldItemChange, PlayClientHeldItemChangeSpec,
+        test_play_client_held_item_change, bench_write_play_client_held_item_change, bench_read_play_client_held_item_change);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayCreativeInventoryAction, PlayCreativeInventoryActionSpec,
+        test_play_creative_inventory_action, bench_write_play_creative_inventory_action, bench_read_play_creative_inventory_action);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayUpdateSign, PlayUpdateSignSpec,
+        test_play_update_sign, bench_write_play_update_sign, bench_read_play_update_sign);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayClientAnimation, PlayClientAnimationSpec,
+        test_play_client_animation, bench_write_play_client_animation, bench_read_play_client_animation);
+
+    packet_test_cases!(RawPacket340, Packet340, PlaySpectate, PlaySpectateSpec,
+        test_play_spectate, bench_write_play_spectate, bench_read_play_spectate);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayBlockPlacement, PlayBlockPlacementSpec,
+        test_play_block_placement, bench_write_play_block_placement, bench_read_play_block_placement);
+
+    packet_test_cases!(RawPacket340, Packet340, PlayUseItem, PlayUseItemSpec,
+        test_play_use_item, bench_write_play_use_item, bench_read_play_use_item);
+
+    fn serialize_bytes<T: Serialize>(value: &T) -> Vec<u8> {
+        let mut serializer = BytesSerializer::default();
+        value.mc_serialize(&mut serializer).expect("serializes");
+        serializer.into_bytes()
+    }
+
+    fn pack_blocks(values: &[u32], bits: usize) -> Vec<i64> {
+        let mut out = alloc::vec![0u64; values.len() * bits / 64];
+        for (index, value) in values.iter().enumerate() {
+            let start_bit = index * bits;
+            let offset = start_bit % 64;
+            out[start_bit / 64] |= (*value as u64) << offset;
+            if offset + bits > 64 {
+                out[(start_bit + bits - 1) / 64] |= (*value as u64) >> (64 - offset);
+            }
+        }
+
+        out.into_iter().map(move |v| v as i64).collect()
+    }
+
+    #[test]
+    fn test_empty_slot() {
+        assert_eq!(serialize_bytes(&Slot(None)), alloc::vec![0xFF, 0xFF]);
+        let Deserialized { value, data } = Slot::mc_deserialize(&[0xFF, 0xFF, 0x01]).expect("deserializes");
+        assert_eq!(value, Slot(None));
+        assert_eq!(data, &[0x01]);
+    }
+
+    #[test]
+    fn test_slot_with_damage() {
+        let slot: Slot = ItemStack {
+            item_id: 276,
+            item_count: 1,
+            damage: 12,
+            nbt: None,
+        }.into();
+
+        let bytes = serialize_bytes(&slot);
+        assert_eq!(bytes, alloc::vec![0x01, 0x14, 0x01, 0x00, 0x0C, 0x00]);
+        assert_eq!(Slot::mc_deserialize(bytes.as_slice()).expect("deserializes").value, slot);
+    }
+
+    #[test]
+    fn test_join_game_hardcore() {
+        let mode = JoinGameMode {
+            mode: GameMode::Adventure,
+            hardcore: true,
+        };
+
+        assert_eq!(serialize_bytes(&mode), alloc::vec![0x0A]);
+        assert_eq!(JoinGameMode::mc_deserialize(&[0x0A]).expect("deserializes").value, mode);
+    }
+
+    #[test]
+    fn test_metadata_type_ids() {
+        let mut metadata = EntityMetadata::default();
+        metadata.set(0, EntityMetadataFieldData::Byte(0x20));
+        metadata.set(6, EntityMetadataFieldData::Boolean(true));
+        metadata.set(7, EntityMetadataFieldData::Slot(Slot(None)));
+
+        let bytes = serialize_bytes(&metadata);
+        assert_eq!(bytes, alloc::vec![0x00, 0x00, 0x20, 0x06, 0x06, 0x01, 0x07, 0x05, 0xFF, 0xFF, 0xFF]);
+        assert_eq!(EntityMetadata::mc_deserialize(bytes.as_slice()).expect("deserializes").value, metadata);
+    }
+
+    #[test]
+    fn test_horse_window() {
+        let window = WindowSpec {
+            kind: WindowKind::Horse(42),
+            title: Chat::from_text("Horse"),
+            number_of_slots: 2,
+        };
+
+        let bytes = serialize_bytes(&window);
+        assert_eq!(&bytes[bytes.len() - 4..], &[0x00, 0x00, 0x00, 0x2A]);
+        assert_eq!(WindowSpec::mc_deserialize(bytes.as_slice()).expect("deserializes").value, window);
+    }
+
+    #[test]
+    fn test_particle_arguments() {
+        let mut particle = ParticleSpec::test_gen_random();
+        particle.particle_id = 37;
+        particle.arguments = alloc::vec![VarInt(1)];
+        let bytes = serialize_bytes(&particle);
+        assert_eq!(ParticleSpec::mc_deserialize(bytes.as_slice()).expect("deserializes").value, particle);
+
+        particle.arguments.clear();
+        assert!(particle.mc_serialize(&mut BytesSerializer::default()).is_err());
+    }
+
+    #[test]
+    fn test_chunk_section_get() {
+        let values: Vec<u32> = (0..CHUNK_SECTION_BLOCKS).map(move |i| (i % 31) as u32).collect();
+        let palette: Vec<VarInt> = (0..32).map(move |i| VarInt(i * 16)).collect();
+        let section = ChunkSection {
+            bits_per_block: 5,
+            palette: palette.into(),
+            data: pack_blocks(values.as_slice(), 5).into(),
+            block_light: Box::new([0u8; CHUNK_LIGHT_DATA_LENGTH]),
+            sky_light: None,
+        };
+
+        for (x, y, z) in [(0, 0, 0), (12, 0, 0), (15, 15, 15), (3, 7, 9)] {
+            let index = (y * 16 + z) * 16 + x;
+            assert_eq!(section.get_raw(x, y, z), Some(values[index]));
+            assert_eq!(section.get(x, y, z), Some(values[index] * 16));
+        }
+        assert_eq!(section.get(16, 0, 0), None);
+    }
+
+    #[test]
+    fn test_chunk_column_round_trip() {
+        let mut column = ChunkColumn::default();
+        column.sections[0] = Some(ChunkSection::test_gen_random());
+        column.sections[5] = Some(ChunkSection::test_gen_random());
+        column.biomes = Some(Box::new([1u8; CHUNK_BIOMES_LENGTH]));
+
+        let position = ChunkPosition { x: 3, z: -7 };
+        let spec = PlayChunkDataSpec::from_column(position, &column, Vec::new()).expect("serializes");
+        assert_eq!(spec.primary_bit_mask, VarInt(0b100001));
+        assert!(spec.full_chunk);
+        assert_eq!(spec.column(true).expect("deserializes"), column);
+    }
+
+    #[test]
+    fn test_spawn_position_uses_legacy_layout() {
+        let packet = PlaySpawnPositionSpec {
+            location: LegacyIntPosition { x: 1, y: 2, z: 3 },
+        };
+
+        assert_eq!(serialize_bytes(&packet), alloc::vec![0x00, 0x00, 0x00, 0x40, 0x08, 0x00, 0x00, 0x03]);
+    }
+}
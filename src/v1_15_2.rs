@@ -702,6 +702,39 @@ define_protocol!(578, Packet578, RawPacket578, RawPacket578Body, Packet578Kind =
     }
 });
 
+impl crate::protocol::HasCompressionThreshold for Packet578 {
+    fn compression_threshold(&self) -> Option<i32> {
+        match self {
+            Packet578::LoginSetCompression(body) => Some(body.threshold.0),
+            _ => None,
+        }
+    }
+}
+
+impl crate::protocol::HasNextState for Packet578 {
+    fn next_state(&self) -> Option<crate::protocol::State> {
+        use crate::protocol::State;
+        match self {
+            Packet578::Handshake(body) => Some(match body.next_state {
+                HandshakeNextState::Status => State::Status,
+                HandshakeNextState::Login => State::Login,
+            }),
+            Packet578::LoginSuccess(_) => Some(State::Play),
+            _ => None,
+        }
+    }
+}
+
+impl crate::limits::HasFieldLimits for Packet578 {
+    fn check_field_limits(&self, limits: &crate::limits::DeserializeLimits) -> Result<(), DeserializeErr> {
+        match self {
+            Packet578::LoginStart(body) => limits.check_username(body.name.as_str()),
+            Packet578::LoginSuccess(body) => limits.check_username(body.username.as_str()),
+            _ => Ok(()),
+        }
+    }
+}
+
 // helper types
 
 // handshake enum
@@ -2376,8 +2409,8 @@ impl Deserialize for RecipeCraftingShapedSpec {
         let Deserialized { value: height, data } = <VarInt>::mc_deserialize(data)?;
         let Deserialized { value: group, mut data } = <String>::mc_deserialize(data)?;
 
-        let ingredients_count = width.0 as usize * height.0 as usize;
-        let mut ingredients: Vec<RecipeIngredient> = Vec::with_capacity(ingredients_count);
+        let ingredients_count = (width.0 as usize).saturating_mul(height.0 as usize);
+        let mut ingredients: Vec<RecipeIngredient> = Vec::with_capacity(crate::limits::array_capacity(ingredients_count, data)?);
         for _ in 0..ingredients_count {
             let Deserialized { value: elem, data: rest } = RecipeIngredient::mc_deserialize(data)?;
             data = rest;
@@ -2500,7 +2533,7 @@ impl Deserialize for ChunkData {
         let Deserialized { value: chunk_data, data } = <CountedArray<u8, VarInt>>::mc_deserialize(data)?;
         let Deserialized { value: n_block_entities_raw, mut data } = VarInt::mc_deserialize(data)?;
         let n_block_entities = n_block_entities_raw.0 as usize;
-        let mut block_entities = Vec::with_capacity(n_block_entities);
+        let mut block_entities = Vec::with_capacity(crate::limits::array_capacity(n_block_entities, data)?);
         for _ in 0..n_block_entities {
             let Deserialized { value: entity, data: rest } = NamedNbtTag::mc_deserialize(data)?;
             data = rest;
@@ -722,6 +722,39 @@ define_protocol!(753, Packet753, RawPacket753, RawPacket753Body, Packet753Kind =
     }
 });
 
+impl crate::protocol::HasCompressionThreshold for Packet753 {
+    fn compression_threshold(&self) -> Option<i32> {
+        match self {
+            Packet753::LoginSetCompression(body) => Some(body.threshold.0),
+            _ => None,
+        }
+    }
+}
+
+impl crate::protocol::HasNextState for Packet753 {
+    fn next_state(&self) -> Option<crate::protocol::State> {
+        use crate::protocol::State;
+        match self {
+            Packet753::Handshake(body) => Some(match body.next_state {
+                HandshakeNextState::Status => State::Status,
+                HandshakeNextState::Login => State::Login,
+            }),
+            Packet753::LoginSuccess(_) => Some(State::Play),
+            _ => None,
+        }
+    }
+}
+
+impl crate::limits::HasFieldLimits for Packet753 {
+    fn check_field_limits(&self, limits: &crate::limits::DeserializeLimits) -> Result<(), DeserializeErr> {
+        match self {
+            Packet753::LoginStart(body) => limits.check_username(body.name.as_str()),
+            Packet753::LoginSuccess(body) => limits.check_username(body.username.as_str()),
+            _ => Ok(()),
+        }
+    }
+}
+
 // helper types
 
 // handshake enum
@@ -2622,8 +2655,8 @@ impl Deserialize for RecipeCraftingShapedSpec {
         let Deserialized { value: height, data } = <VarInt>::mc_deserialize(data)?;
         let Deserialized { value: group, mut data } = <String>::mc_deserialize(data)?;
 
-        let ingredients_count = width.0 as usize * height.0 as usize;
-        let mut ingredients: Vec<RecipeIngredient> = Vec::with_capacity(ingredients_count);
+        let ingredients_count = (width.0 as usize).saturating_mul(height.0 as usize);
+        let mut ingredients: Vec<RecipeIngredient> = Vec::with_capacity(crate::limits::array_capacity(ingredients_count, data)?);
         for _ in 0..ingredients_count {
             let Deserialized { value: elem, data: rest } = RecipeIngredient::mc_deserialize(data)?;
             data = rest;
@@ -2745,7 +2778,7 @@ impl Deserialize for ChunkData {
         let Deserialized { value: chunk_data, data } = <CountedArray<u8, VarInt>>::mc_deserialize(data)?;
         let Deserialized { value: n_block_entities_raw, mut data } = VarInt::mc_deserialize(data)?;
         let n_block_entities = n_block_entities_raw.0 as usize;
-        let mut block_entities = Vec::with_capacity(n_block_entities);
+        let mut block_entities = Vec::with_capacity(crate::limits::array_capacity(n_block_entities, data)?);
         for _ in 0..n_block_entities {
             let Deserialized { value: entity, data: rest } = NamedNbtTag::mc_deserialize(data)?;
             data = rest;
@@ -2777,6 +2810,164 @@ impl TestRandom for ChunkData {
     }
 }
 
+// borrowed versions of the packets a proxy sees the most, decoded with RawPacket753's
+// deserialize_borrowed, which point into the packet instead of copying strings and bytes out of it
+proto_borrowed_struct!(PlayServerChatMessageRef<'a> {
+    message: ChatRef<'a>,
+    position: ChatPosition,
+    sender: UUID4
+});
+
+proto_borrowed_struct!(PlayClientChatMessageRef<'a> {
+    message: &'a str
+});
+
+proto_borrowed_struct!(PlayServerPluginMessageRef<'a> {
+    channel: &'a str,
+    data: RemainingBytesRef<'a>
+});
+
+proto_borrowed_struct!(PlayClientPluginMessageRef<'a> {
+    channel: &'a str,
+    data: RemainingBytesRef<'a>
+});
+
+// parses the chat JSON, which can fail
+impl core::convert::TryFrom<&PlayServerChatMessageRef<'_>> for PlayServerChatMessageSpec {
+    type Error = DeserializeErr;
+
+    fn try_from(other: &PlayServerChatMessageRef<'_>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            message: other.message.to_chat()?,
+            position: other.position.clone(),
+            sender: other.sender,
+        })
+    }
+}
+
+impl From<PlayClientChatMessageRef<'_>> for PlayClientChatMessageSpec {
+    fn from(other: PlayClientChatMessageRef<'_>) -> Self {
+        Self { message: other.message.to_owned() }
+    }
+}
+
+impl From<PlayServerPluginMessageRef<'_>> for PlayServerPluginMessageSpec {
+    fn from(other: PlayServerPluginMessageRef<'_>) -> Self {
+        Self { channel: other.channel.to_owned(), data: other.data.into() }
+    }
+}
+
+impl From<PlayClientPluginMessageRef<'_>> for PlayClientPluginMessageSpec {
+    fn from(other: PlayClientPluginMessageRef<'_>) -> Self {
+        Self { channel: other.channel.to_owned(), data: other.data.into() }
+    }
+}
+
+// ChunkData, borrowing the section data and the still encoded block entities
+#[derive(Clone, PartialEq, Debug)]
+pub struct ChunkDataRef<'a> {
+    pub position: ChunkPosition<i32>,
+    pub primary_bit_mask: VarInt,
+    pub heightmaps: NamedNbtTag,
+    pub biomes: Option<CountedArray<VarInt, VarInt>>,
+    pub data: &'a [u8],
+    pub num_block_entities: VarInt,
+    pub block_entities: RemainingBytesRef<'a>,
+}
+
+impl ChunkDataRef<'_> {
+    pub fn block_entities(&self) -> Result<Vec<NamedNbtTag>, DeserializeErr> {
+        let count = self.num_block_entities.0 as usize;
+        let mut data = self.block_entities.data;
+        let mut out = Vec::with_capacity(crate::limits::array_capacity(count, data)?);
+        for index in 0..count {
+            let Deserialized { value: entity, data: rest } = NamedNbtTag::mc_deserialize(data)
+                .map_err(|err| err.at_index(index, self.block_entities.data.len() - data.len()))?;
+            data = rest;
+            out.push(entity);
+        }
+
+        if !data.is_empty() {
+            return Err(DeserializeErr::CannotUnderstandValue(alloc::format!(
+                "{} bytes left after block entities", data.len())));
+        }
+
+        Ok(out)
+    }
+}
+
+// decodes the block entities, which can fail
+impl core::convert::TryFrom<&ChunkDataRef<'_>> for ChunkData {
+    type Error = DeserializeErr;
+
+    fn try_from(other: &ChunkDataRef<'_>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            position: other.position,
+            primary_bit_mask: other.primary_bit_mask,
+            heightmaps: other.heightmaps.clone(),
+            biomes: other.biomes.clone(),
+            data: other.data.to_vec().into(),
+            block_entities: other.block_entities()?,
+        })
+    }
+}
+
+impl Serialize for ChunkDataRef<'_> {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        to.serialize_other(&self.position)?;
+        to.serialize_other(&self.biomes.is_some())?;
+        to.serialize_other(&self.primary_bit_mask)?;
+        to.serialize_other(&self.heightmaps)?;
+        if let Some(biomes) = &self.biomes {
+            to.serialize_other(biomes)?;
+        }
+
+        to.serialize_other(&self.data)?;
+        to.serialize_other(&self.num_block_entities)?;
+        to.serialize_other(&self.block_entities)
+    }
+}
+
+impl<'a> BorrowDeserialize<'a> for ChunkDataRef<'a> {
+    fn mc_deserialize_borrowed(start: &'a [u8]) -> DeserializeResult<'a, Self> {
+        let data = start;
+        let Deserialized { value: position, data } = <ChunkPosition<i32>>::mc_deserialize(data)
+            .map_err(|err| err.in_field("position", start.len() - data.len()))?;
+        let Deserialized { value: is_full_chunk, data } = bool::mc_deserialize(data)
+            .map_err(|err| err.in_field("is_full_chunk", start.len() - data.len()))?;
+        let Deserialized { value: primary_bit_mask, data } = VarInt::mc_deserialize(data)
+            .map_err(|err| err.in_field("primary_bit_mask", start.len() - data.len()))?;
+        let Deserialized { value: heightmaps, mut data } = NamedNbtTag::mc_deserialize(data)
+            .map_err(|err| err.in_field("heightmaps", start.len() - data.len()))?;
+        let biomes = if is_full_chunk {
+            let Deserialized { value: biomes, data: rest } = <CountedArray<VarInt, VarInt>>::mc_deserialize(data)
+                .map_err(|err| err.in_field("biomes", start.len() - data.len()))?;
+            data = rest;
+            Some(biomes)
+        } else {
+            None
+        };
+        let Deserialized { value: chunk_data, data } = <&[u8]>::mc_deserialize_borrowed(data)
+            .map_err(|err| err.in_field("data", start.len() - data.len()))?;
+        let block_entities_at = start.len() - data.len();
+        let Deserialized { value: num_block_entities, data } = VarInt::mc_deserialize(data)
+            .map_err(|err| err.in_field("num_block_entities", block_entities_at))?;
+        crate::limits::current()
+            .check_array_length(num_block_entities.0 as usize)
+            .map_err(|err| err.in_field("num_block_entities", block_entities_at))?;
+
+        Deserialized::ok(ChunkDataRef {
+            position,
+            primary_bit_mask,
+            heightmaps,
+            biomes,
+            data: chunk_data,
+            num_block_entities,
+            block_entities: RemainingBytesRef { data },
+        }, &[])
+    }
+}
+
 pub const LIGHT_DATA_LENGTH: usize = 2048;
 pub const LIGHT_DATA_SECTIONS: usize = 18;
 
@@ -3239,6 +3430,9 @@ proto_struct!(DustParticleData {
 pub mod tests {
     use super::*;
     use crate::packet_test_cases;
+    use crate::protocol::{HasPacketId, Packet};
+    #[cfg(feature = "gat")]
+    use crate::protocol::PacketKind;
 
     packet_test_cases!(RawPacket753, Packet753, Handshake, HandshakeSpec,
         test_handshake, bench_write_handshake, bench_read_handshake);
@@ -3699,6 +3893,208 @@ pub mod tests {
     packet_test_cases!(RawPacket753, Packet753, PlayUseItem, PlayUseItemSpec,
         test_play_use_item, bench_write_play_use_item, bench_read_play_use_item);
 
+    fn body_of(packet: &Packet753) -> Vec<u8> {
+        use crate::protocol::HasPacketBody;
+
+        let mut out = BytesSerializer::default();
+        packet.mc_serialize_body(&mut out).unwrap();
+        out.into_bytes()
+    }
+
+    fn borrowed<'a, T>(kind: Packet753Kind, body: &'a [u8]) -> T where T: BorrowDeserialize<'a> + Serialize {
+        let raw = match kind.with_body_data(body) {
+            RawPacket753::PlayServerChatMessage(raw) => raw.deserialize_borrowed(),
+            RawPacket753::PlayClientChatMessage(raw) => raw.deserialize_borrowed(),
+            RawPacket753::PlayServerPluginMessage(raw) => raw.deserialize_borrowed(),
+            RawPacket753::PlayClientPluginMessage(raw) => raw.deserialize_borrowed(),
+            RawPacket753::PlayChunkData(raw) => raw.deserialize_borrowed(),
+            other => panic!("no borrowed type for {:?}", other),
+        };
+        let value: T = raw.expect("deserializes borrowed");
+
+        // and writes back exactly what it was read from
+        let mut out = BytesSerializer::default();
+        value.mc_serialize(&mut out).unwrap();
+        assert_eq!(out.into_bytes().as_slice(), body);
+        value
+    }
+
+    #[test]
+    fn test_borrowed_packets() {
+        use core::convert::TryFrom;
+
+        let spec = PlayServerChatMessageSpec {
+            message: Chat::from_text("hello"),
+            position: ChatPosition::ChatBox,
+            sender: UUID4::from(7u128),
+        };
+        let body = body_of(&Packet753::PlayServerChatMessage(spec.clone()));
+        let chat: PlayServerChatMessageRef = borrowed(Packet753Kind::PlayServerChatMessage, &body);
+        assert_eq!(PlayServerChatMessageSpec::try_from(&chat).unwrap(), spec);
+
+        let spec = PlayClientChatMessageSpec { message: "/help".to_owned() };
+        let body = body_of(&Packet753::PlayClientChatMessage(spec.clone()));
+        let chat: PlayClientChatMessageRef = borrowed(Packet753Kind::PlayClientChatMessage, &body);
+        assert_eq!(chat.message.as_ptr(), body[1..].as_ptr());
+        assert_eq!(PlayClientChatMessageSpec::from(chat), spec);
+
+        let spec = PlayServerPluginMessageSpec { channel: "minecraft:brand".to_owned(), data: vec![6, b'v', b'a'].into() };
+        let body = body_of(&Packet753::PlayServerPluginMessage(spec.clone()));
+        let message: PlayServerPluginMessageRef = borrowed(Packet753Kind::PlayServerPluginMessage, &body);
+        assert_eq!(message.data.as_ptr(), body[16..].as_ptr());
+        assert_eq!(PlayServerPluginMessageSpec::from(message), spec);
+
+        let spec = PlayClientPluginMessageSpec { channel: "a:b".to_owned(), data: vec![].into() };
+        let body = body_of(&Packet753::PlayClientPluginMessage(spec.clone()));
+        let message: PlayClientPluginMessageRef = borrowed(Packet753Kind::PlayClientPluginMessage, &body);
+        assert_eq!(PlayClientPluginMessageSpec::from(message), spec);
+
+        let spec = ChunkData {
+            position: ChunkPosition { x: 1, z: -2 },
+            primary_bit_mask: VarInt(3),
+            heightmaps: NamedNbtTag::test_gen_random(),
+            biomes: Some(vec![VarInt(1), VarInt(2)].into()),
+            data: vec![1, 2, 3, 4].into(),
+            block_entities: vec![NamedNbtTag::test_gen_random(), NamedNbtTag::test_gen_random()],
+        };
+        let body = body_of(&Packet753::PlayChunkData(PlayChunkDataWrapper { data: spec.clone() }));
+        let chunk: ChunkDataRef = borrowed(Packet753Kind::PlayChunkData, &body);
+        assert_eq!(chunk.data, &[1, 2, 3, 4]);
+        assert_eq!(ChunkData::try_from(&chunk).unwrap(), spec);
+    }
+
+    #[test]
+    fn test_array_limits() {
+        let limits = crate::limits::DeserializeLimits { max_array_length: 2, ..Default::default() };
+
+        let mut out = BytesSerializer::default();
+        ChunkData {
+            position: ChunkPosition { x: 0, z: 0 },
+            primary_bit_mask: VarInt(0),
+            heightmaps: NamedNbtTag { root: nbt::Tag::Compound(vec![]).with_name("") },
+            biomes: None,
+            data: vec![].into(),
+            block_entities: vec![NamedNbtTag { root: nbt::Tag::Compound(vec![]).with_name("") }; 3],
+        }.mc_serialize(&mut out).unwrap();
+        let chunk = out.into_bytes();
+        assert!(ChunkData::mc_deserialize(chunk.as_slice()).is_ok());
+        assert!(matches!(
+            limits.scope(|| ChunkData::mc_deserialize(chunk.as_slice())),
+            Err(DeserializeErr::LimitExceeded { what: "array", length: 3, max: 2 })
+        ));
+        let err = limits.scope(|| ChunkDataRef::mc_deserialize_borrowed(chunk.as_slice())).map(|_| ()).unwrap_err();
+        assert!(matches!(err.root(), DeserializeErr::LimitExceeded { what: "array", length: 3, max: 2 }));
+        assert_eq!(err.path(), &[PathSegment::Name("num_block_entities")]);
+
+        // a 2^31 - 1 by 2^31 - 1 recipe without any ingredients
+        let mut out = BytesSerializer::default();
+        VarInt(i32::MAX).mc_serialize(&mut out).unwrap();
+        VarInt(i32::MAX).mc_serialize(&mut out).unwrap();
+        String::new().mc_serialize(&mut out).unwrap();
+        assert!(matches!(
+            RecipeCraftingShapedSpec::mc_deserialize(out.into_bytes().as_slice()),
+            Err(DeserializeErr::LimitExceeded { what: "array", .. })
+        ));
+    }
+
+    #[test]
+    fn test_borrowed_error_location() {
+        let mut out = BytesSerializer::default();
+        PlayServerChatMessageSpec {
+            message: Chat::from_text("hello"),
+            position: ChatPosition::ChatBox,
+            sender: UUID4::from(7u128),
+        }.mc_serialize(&mut out).unwrap();
+        let body = out.into_bytes();
+
+        let raw = match Packet753Kind::PlayServerChatMessage.with_body_data(&body[..body.len() - 1]) {
+            RawPacket753::PlayServerChatMessage(raw) => raw,
+            _ => unreachable!(),
+        };
+        let err = match raw.deserialize_borrowed::<PlayServerChatMessageRef>() {
+            Err(crate::protocol::PacketErr::DeserializeFailed(err)) => err,
+            other => panic!("expected a deserialize error, got {:?}", other.map(|_| ())),
+        };
+        assert!(matches!(err.root(), DeserializeErr::Eof));
+        assert_eq!(err.path(), &[PathSegment::Name("PlayServerChatMessage"), PathSegment::Name("sender")]);
+
+        let mut out = BytesSerializer::default();
+        ChunkData {
+            position: ChunkPosition { x: 0, z: 0 },
+            primary_bit_mask: VarInt(0),
+            heightmaps: NamedNbtTag { root: nbt::Tag::Compound(vec![]).with_name("") },
+            biomes: None,
+            data: vec![1, 2, 3, 4].into(),
+            block_entities: vec![],
+        }.mc_serialize(&mut out).unwrap();
+        let chunk = out.into_bytes();
+        // drop the block entity count and the last byte of the chunk data
+        let err = ChunkDataRef::mc_deserialize_borrowed(&chunk[..chunk.len() - 2]).map(|_| ()).unwrap_err();
+        assert!(matches!(err.root(), DeserializeErr::Eof));
+        assert_eq!(err.path(), &[PathSegment::Name("data")]);
+        assert_eq!(err.offset(), Some(chunk.len() - 6));
+    }
+
+    fn join_game() -> PlayJoinGameSpec {
+        PlayJoinGameSpec {
+            entity_id: 1,
+            is_hardcore: false,
+            gamemode: GameMode::Survival,
+            previous_gamemode: PreviousGameMode::NoPrevious,
+            worlds: alloc::vec!["a".to_owned(), "bb".to_owned()].into(),
+            dimension_codec: NamedNbtTag { root: nbt::Tag::Compound(alloc::vec![]).with_name("") },
+            dimension: NamedNbtTag { root: nbt::Tag::Compound(alloc::vec![]).with_name("") },
+            world_name: "minecraft:overworld".to_owned(),
+            hashed_seed: 0,
+            max_players: VarInt(20),
+            view_distance: VarInt(10),
+            reduced_debug_info: false,
+            enable_respawn_screen: true,
+            is_debug: false,
+            is_flat: false,
+        }
+    }
+
+    #[test]
+    fn test_error_location() {
+        let mut out = BytesSerializer::default();
+        join_game().mc_serialize(&mut out).unwrap();
+        let body = out.into_bytes();
+
+        // cut off inside the second world name, which starts after 7 bytes of fields, the count and
+        // the first name
+        let id = HasPacketId::id(&Packet753Kind::PlayJoinGame);
+        let err = match Packet753::mc_deserialize_body(id, &body[..12]) {
+            Err(crate::protocol::PacketErr::DeserializeFailed(err)) => err,
+            other => panic!("expected a deserialize error, got {:?}", other.map(|_| ())),
+        };
+        assert!(matches!(err.root(), DeserializeErr::Eof));
+        assert_eq!(err.path(), &[
+            PathSegment::Name("PlayJoinGame"),
+            PathSegment::Name("worlds"),
+            PathSegment::Index(1),
+        ]);
+        assert_eq!(err.offset(), Some(10));
+        assert_eq!(alloc::format!("{}", err), "at PlayJoinGame.worlds[1] (byte 10): unexpected eof");
+    }
+
+    #[test]
+    fn test_error_location_in_enum_variant() {
+        let mut out = BytesSerializer::default();
+        PlayBossBarSpec {
+            uuid: UUID4::from(1u128),
+            action: BossBarAction::UpdateHealth(BossBarUpdateHealthSpec { health: 0.5 }),
+        }.mc_serialize(&mut out).unwrap();
+        let body = out.into_bytes();
+
+        let id = HasPacketId::id(&Packet753Kind::PlayBossBar);
+        let err = match Packet753::mc_deserialize_body(id, &body[..body.len() - 1]) {
+            Err(crate::protocol::PacketErr::DeserializeFailed(err)) => err,
+            other => panic!("expected a deserialize error, got {:?}", other.map(|_| ())),
+        };
+        assert_eq!(alloc::format!("{}", err), "at PlayBossBar.action.UpdateHealth.health (byte 17): unexpected eof");
+    }
+
     // trust me, this is some cutting edge shit
     // I'm definitely not generating code using a unit test
     #[test]
@@ -0,0 +1,699 @@
+// 1.16.4 and 1.16.5 only swapped the ids of the serverbound recipe book packets, so every body and
+// helper type is the one from 1.16.3
+pub use crate::v1_16_3::*;
+use crate::*;
+use alloc::borrow::ToOwned;
+
+define_protocol!(754, Packet754, RawPacket754, RawPacket754Body, Packet754Kind => {
+    // handshaking
+    Handshake, 0x00, Handshaking, ServerBound => HandshakeSpec,
+    // status
+    StatusRequest, 0x00, Status, ServerBound => StatusRequestSpec,
+    StatusPing, 0x01, Status, ServerBound => StatusPingSpec,
+    StatusResponse, 0x00, Status, ClientBound => StatusResponseSpec,
+    StatusPong, 0x01, Status, ClientBound => StatusPongSpec,
+    // login
+    LoginDisconnect, 0x00, Login, ClientBound => LoginDisconnectSpec,
+    LoginEncryptionRequest, 0x01, Login, ClientBound => LoginEncryptionRequestSpec,
+    LoginSuccess, 0x02, Login, ClientBound => LoginSuccessSpec,
+    LoginSetCompression, 0x03, Login, ClientBound => LoginSetCompressionSpec,
+    LoginPluginRequest, 0x04, Login, ClientBound => LoginPluginRequestSpec,
+    LoginStart, 0x00, Login, ServerBound => LoginStartSpec,
+    LoginEncryptionResponse, 0x01, Login, ServerBound => LoginEncryptionResponseSpec,
+    LoginPluginResponse, 0x02, Login, ServerBound => LoginPluginResponseSpec,
+    // play
+    // client bound
+    PlaySpawnEntity, 0x00, Play, ClientBound => PlaySpawnEntitySpec,
+    PlaySpawnExperienceOrb, 0x01, Play, ClientBound => PlaySpawnExperienceOrbSpec,
+    PlaySpawnLivingEntity, 0x02, Play, ClientBound => PlaySpawnLivingEntitySpec,
+    PlaySpawnPainting, 0x03, Play, ClientBound => PlaySpawnPaintingSpec,
+    PlaySpawnPlayer, 0x04, Play, ClientBound => PlaySpawnPlayerSpec,
+    PlayEntityAnimation, 0x05, Play, ClientBound => PlayEntityAnimationSpec,
+    PlayStatistics, 0x06, Play, ClientBound => PlayStatisticsSpec,
+    PlayAcknowledgePlayerDigging, 0x07, Play, ClientBound => PlayAcknowledgePlayerDiggingSpec,
+    PlayBlockBreakAnimation, 0x08, Play, ClientBound => PlayBlockBreakAnimationSpec,
+    PlayBlockEntityData, 0x09, Play, ClientBound => PlayBlockEntityDataSpec,
+    PlayBlockAction, 0x0A, Play, ClientBound => PlayBlockActionSpec,
+    PlayBlockChange, 0x0B, Play, ClientBound => PlayBlockChangeSpec,
+    PlayBossBar, 0x0C, Play, ClientBound => PlayBossBarSpec,
+    PlayServerDifficulty, 0x0D, Play, ClientBound => PlayServerDifficultySpec,
+    PlayServerChatMessage, 0x0E, Play, ClientBound => PlayServerChatMessageSpec,
+    PlayTabComplete, 0x0F, Play, ClientBound => PlayTabCompleteSpec,
+    PlayDeclareCommands, 0x10, Play, ClientBound => PlayDeclareCommandsSpec,
+    PlayServerWindowConfirmation, 0x11, Play, ClientBound => PlayServerWindowConfirmationSpec,
+    PlayServerCloseWindow, 0x12, Play, ClientBound => PlayServerCloseWindowSpec,
+    PlayWindowItems, 0x13, Play, ClientBound => PlayWindowItemsSpec,
+    PlayWindowProperty, 0x14, Play, ClientBound => PlayWindowPropertySpec,
+    PlaySetSlot, 0x15, Play, ClientBound => PlaySetSlotSpec,
+    PlaySetCooldown, 0x16, Play, ClientBound => PlaySetCooldownSpec,
+    PlayServerPluginMessage, 0x17, Play, ClientBound => PlayServerPluginMessageSpec,
+    PlayNamedSoundEffect, 0x18, Play, ClientBound => PlayNamedSoundEffectSpec,
+    PlayDisconnect, 0x19, Play, ClientBound => PlayDisconnectSpec,
+    PlayEntityStatus, 0x1A, Play, ClientBound => PlayEntityStatusSpec,
+    PlayExplosion, 0x1B, Play, ClientBound => PlayExplosionSpec,
+    PlayUnloadChunk, 0x1C, Play, ClientBound => PlayUnloadChunkSpec,
+    PlayChangeGameState, 0x1D, Play, ClientBound => PlayChangeGameStateSpec,
+    PlayOpenHorseWindow, 0x1E, Play, ClientBound => PlayOpenHorseWindowSpec,
+    PlayServerKeepAlive, 0x1F, Play, ClientBound => PlayServerKeepAliveSpec,
+    PlayChunkData, 0x20, Play, ClientBound => PlayChunkDataWrapper,
+    PlayEffect, 0x21, Play, ClientBound => PlayEffectSpec,
+    PlayParticle, 0x22, Play, ClientBound => PlayParticleSpec,
+    PlayUpdateLight, 0x23, Play, ClientBound => PlayUpdateLightSpec,
+    PlayJoinGame, 0x24, Play, ClientBound => PlayJoinGameSpec,
+    PlayMapData, 0x25, Play, ClientBound => PlayMapDataSpec,
+    PlayTradeList, 0x26, Play, ClientBound => PlayTradeListSpec,
+    PlayEntityPosition, 0x27, Play, ClientBound => PlayEntityPositionSpec,
+    PlayEntityPositionAndRotation, 0x28, Play, ClientBound => PlayEntityPositionAndRotationSpec,
+    PlayEntityRotation, 0x29, Play, ClientBound => PlayEntityRotationSpec,
+    PlayEntityMovement, 0x2A, Play, ClientBound => PlayEntityMovementSpec,
+    PlayServerVehicleMove, 0x2B, Play, ClientBound => PlayEntityVehicleMoveSpec,
+    PlayOpenBook, 0x2C, Play, ClientBound => PlayOpenBookSpec,
+    PlayOpenWindow, 0x2D, Play, ClientBound => PlayOpenWindowSpec,
+    PlayOpenSignEditor, 0x2E, Play, ClientBound => PlayOpenSignEditorSpec,
+    PlayCraftRecipeResponse, 0x2F, Play, ClientBound => PlayCraftRecipeResponseSpec,
+    PlayServerPlayerAbilities, 0x30, Play, ClientBound => PlayServerPlayerAbilitiesSpec,
+    PlayCombatEvent, 0x31, Play, ClientBound => PlayCombatEventSpec,
+    PlayPlayerInfo, 0x32, Play, ClientBound => PlayPlayerInfoSpec,
+    PlayFacePlayer, 0x33, Play, ClientBound => PlayFacePlayerSpec,
+    PlayServerPlayerPositionAndLook, 0x34, Play, ClientBound => PlayServerPlayerPositionAndLookSpec,
+    PlayUnlockRecipes, 0x35, Play, ClientBound => PlayUnlockRecipesSpec,
+    PlayDestroyEntities, 0x36, Play, ClientBound => PlayDestroyEntitiesSpec,
+    PlayRemoveEntityEffect, 0x37, Play, ClientBound => PlayRemoveEntityEffectSpec,
+    PlayResourcePackSend, 0x38, Play, ClientBound => PlayResourcePackSendSpec,
+    PlayRespawn, 0x39, Play, ClientBound => PlayRespawnSpec,
+    PlayEntityHeadLook, 0x3A, Play, ClientBound => PlayEntityHeadLookSpec,
+    PlayMultiBlockChange, 0x3B, Play, ClientBound => PlayMultiBlockChangeSpec,
+    PlaySelectAdvancementTab, 0x3C, Play, ClientBound => PlaySelectAdvancementTabSpec,
+    PlayWorldBorder, 0x3D, Play, ClientBound => PlayWorldBorderSpec,
+    PlayCamera, 0x3E, Play, ClientBound => PlayCameraSpec,
+    PlayServerHeldItemChange, 0x3F, Play, ClientBound => PlayServerHeldItemChangeSpec,
+    PlayUpdateViewPosition, 0x40, Play, ClientBound => PlayUpdateViewPositionSpec,
+    PlayUpdateViewDistance, 0x41, Play, ClientBound => PlayUpdateViewDistanceSpec,
+    PlaySpawnPosition, 0x42, Play, ClientBound => PlaySpawnPositionSpec,
+    PlayDisplayScoreboard, 0x43, Play, ClientBound => PlayDisplayScoreboardSpec,
+    PlayEntityMetadata, 0x44, Play, ClientBound => PlayEntityMetadataSpec,
+    PlayAttachEntity, 0x45, Play, ClientBound => PlayAttachEntitySpec,
+    PlayEntityVelocity, 0x46, Play, ClientBound => PlayEntityVelocitySpec,
+    PlayEntityEquipment, 0x47, Play, ClientBound => PlayEntityEquiptmentSpec,
+    PlaySetExperience, 0x48, Play, ClientBound => PlaySetExperienceSpec,
+    PlayUpdatehealth, 0x49, Play, ClientBound => PlayUpdateHealthSpec,
+    PlayScoreboardObjective, 0x4A, Play, ClientBound => PlayScoreboardObjectiveSpec,
+    PlaySetPassengers, 0x4B, Play, ClientBound => PlaySetPassengersSpec,
+    PlayTeams, 0x4C, Play, ClientBound => PlayTeamsSpec,
+    PlayUpdateScore, 0x4D, Play, ClientBound => PlayUpdateScoreSpec,
+    PlayTimeUpdate, 0x4E, Play, ClientBound => PlayTimeUpdateSpec,
+    PlayTitle, 0x4F, Play, ClientBound => PlayTitleSpec,
+    PlayEntitySoundEffect, 0x50, Play, ClientBound => PlayEntitySoundEffectSpec,
+    PlaySoundEffect, 0x51, Play, ClientBound => PlaySoundEffectSpec,
+    PlayStopSound, 0x52, Play, ClientBound => PlayStopSoundSpec,
+    PlayerPlayerListHeaderAndFooter, 0x53, Play, ClientBound => PlayPlayerListHeaderAndFooterSpec,
+    PlayNbtQueryResponse, 0x54, Play, ClientBound => PlayNbtQueryResponseSpec,
+    PlayCollectItem, 0x55, Play, ClientBound => PlayCollectItemSpec,
+    PlayEntityTeleport, 0x56, Play, ClientBound => PlayEntityTeleportSpec,
+    PlayAdvancements, 0x57, Play, ClientBound => PlayAdvancementsSpec,
+    PlayEntityProperties, 0x58, Play, ClientBound => PlayEntityPropertiesSpec,
+    PlayEntityEffect, 0x59, Play, ClientBound => PlayEntityEffectSpec,
+    PlayDeclareRecipes, 0x5A, Play, ClientBound => PlayDeclareRecipesSpec,
+    PlayTags, 0x5B, Play, ClientBound => PlayTagsSpec,
+    // play server bound
+    PlayTeleportConfirm, 0x00, Play, ServerBound => PlayTeleportConfirmSpec,
+    PlayQueryBlockNbt, 0x01, Play, ServerBound => PlayQueryBlockNbtSpec,
+    PlayQueryEntityNbt, 0x0D, Play, ServerBound => PlayQueryEntityNbtSpec,
+    PlaySetDifficulty, 0x02, Play, ServerBound => PlaySetDifficultySpec,
+    PlayClientChatMessage, 0x03, Play, ServerBound => PlayClientChatMessageSpec,
+    PlayClientStatus, 0x04, Play, ServerBound => PlayClientStatusSpec,
+    PlayClientSettings, 0x05, Play, ServerBound => PlayClientSettingsSpec,
+    PlayClientTabComplete, 0x06, Play, ServerBound => PlayClientTabCompleteSpec,
+    PlayClientWindowConfirmation, 0x07, Play, ServerBound => PlayClientWindowConfirmationSpec,
+    PlayClickWindowButton, 0x08, Play, ServerBound => PlayClickWindowButtonSpec,
+    PlayClickWindow, 0x09, Play, ServerBound => PlayClickWindowSpec,
+    PlayClientCloseWindow, 0x0A, Play, ServerBound => PlayClientCloseWindowSpec,
+    PlayClientPluginMessage, 0x0B, Play, ServerBound => PlayClientPluginMessageSpec,
+    PlayEditBook, 0x0C, Play, ServerBound => PlayEditBookSpec,
+    PlayInteractEntity, 0x0E, Play, ServerBound => PlayInteractEntitySpec,
+    PlayGenerateStructure, 0x0F, Play, ServerBound => PlayGenerateStructureSpec,
+    PlayClientKeepAlive, 0x10, Play, ServerBound => PlayClientKeepAliveSpec,
+    PlayLockDifficulty, 0x11, Play, ServerBound => PlayLockDifficultySpec,
+    PlayPlayerPosition, 0x12, Play, ServerBound => PlayPlayerPositionSpec,
+    PlayClientPlayerPositionAndRotation, 0x13, Play, ServerBound => PlayClientPlayerPositionAndRotationSpec,
+    PlayPlayerRotation, 0x14, Play, ServerBound => PlayPlayerRotationSpec,
+    PlayPlayerMovement, 0x15, Play, ServerBound => PlayPlayerMovementSpec,
+    PlayClientVehicleMove, 0x16, Play, ServerBound => PlayClientVehicleMoveSpec,
+    PlaySteerBoat, 0x17, Play, ServerBound => PlaySteerBoatSpec,
+    PlayPickItem, 0x18, Play, ServerBound => PlayPickItemSpec,
+    PlayCraftRecipeRequest, 0x19, Play, ServerBound => PlayCraftRecipeRequestSpec,
+    PlayClientPlayerAbilities, 0x1A, Play, ServerBound => PlayClientPlayerAbilitiesSpec,
+    PlayPlayerDigging, 0x1B, Play, ServerBound => PlayPlayerDiggingSpec,
+    PlayEntityAction, 0x1C, Play, ServerBound => PlayEntityActionSpec,
+    PlaySteerVehicle, 0x1D, Play, ServerBound => PlaySteerVehicleSpec,
+    PlaySetRecipeBookState, 0x1E, Play, ServerBound => PlaySetRecipeBookStateSpec,
+    PlaySetDisplayedRecipe, 0x1F, Play, ServerBound => PlaySetDisplayedRecipeSpec,
+    PlayNameItem, 0x20, Play, ServerBound => PlayNameItemSpec,
+    PlayResourcePackStatus, 0x21, Play, ServerBound => PlayResourcePackStatusSpec,
+    PlayAdvancementTab, 0x22, Play, ServerBound => PlayAdvancementTabSpec,
+    PlaySelectTrade, 0x23, Play, ServerBound => PlaySelectTradeSpec,
+    PlaySetBeaconEffect, 0x24, Play, ServerBound => PlaySetBeaconEffectSpec,
+    PlayClientHeldItemChange, 0x25, Play, ServerBound => PlayClientHeldItemChangeSpec,
+    PlayUpdateCommandBlock, 0x26, Play, ServerBound => PlayUpdateCommandBlockSpec,
+    PlayUpdateCommandBlockMinecart, 0x27, Play, ServerBound => PlayUpdateCommandBlockMinecartSpec,
+    PlayUpdateJigsawBlock, 0x28, Play, ServerBound => PlayUpdateJigsawBlockSpec,
+    PlayCreativeInventoryAction, 0x29, Play, ServerBound => PlayCreativeInventoryActionSpec,
+    PlayUpdateStructureBlock, 0x2A, Play, ServerBound => PlayUpdateStructureBlockSpec,
+    PlayUpdateSign, 0x2B, Play, ServerBound => PlayUpdateSignSpec,
+    PlayClientAnimation, 0x2C, Play, ServerBound => PlayClientAnimationSpec,
+    PlaySpectate, 0x2D, Play, ServerBound => PlaySpectateSpec,
+    PlayBlockPlacement, 0x2E, Play, ServerBound => PlayBlockPlacementSpec,
+    PlayUseItem, 0x2F, Play, ServerBound => PlayUseItemSpec
+});
+
+impl crate::protocol::HasCompressionThreshold for Packet754 {
+    fn compression_threshold(&self) -> Option<i32> {
+        match self {
+            Packet754::LoginSetCompression(body) => Some(body.threshold.0),
+            _ => None,
+        }
+    }
+}
+
+impl crate::protocol::HasNextState for Packet754 {
+    fn next_state(&self) -> Option<crate::protocol::State> {
+        use crate::protocol::State;
+        match self {
+            Packet754::Handshake(body) => Some(match body.next_state {
+                HandshakeNextState::Status => State::Status,
+                HandshakeNextState::Login => State::Login,
+            }),
+            Packet754::LoginSuccess(_) => Some(State::Play),
+            _ => None,
+        }
+    }
+}
+
+impl crate::limits::HasFieldLimits for Packet754 {
+    fn check_field_limits(&self, limits: &crate::limits::DeserializeLimits) -> Result<(), DeserializeErr> {
+        match self {
+            Packet754::LoginStart(body) => limits.check_username(body.name.as_str()),
+            Packet754::LoginSuccess(body) => limits.check_username(body.username.as_str()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+pub mod tests {
+    use super::*;
+    use crate::packet_test_cases;
+    use crate::protocol::{HasPacketId, TestRandom};
+
+    packet_test_cases!(RawPacket754, Packet754, Handshake, HandshakeSpec,
+        test_handshake, bench_write_handshake, bench_read_handshake);
+
+    packet_test_cases!(RawPacket754, Packet754, StatusRequest, StatusRequestSpec,
+        test_status_request, bench_write_status_request, bench_read_status_request);
+
+    packet_test_cases!(RawPacket754, Packet754, StatusPing, StatusPingSpec,
+        test_status_ping, bench_write_status_ping, bench_read_status_ping);
+
+    packet_test_cases!(RawPacket754, Packet754, StatusResponse, StatusResponseSpec,
+        test_status_response, bench_write_status_response, bench_read_status_response);
+
+    packet_test_cases!(RawPacket754, Packet754, StatusPong, StatusPongSpec,
+        test_status_pong, bench_write_status_pong, bench_read_status_pong);
+
+    packet_test_cases!(RawPacket754, Packet754, LoginDisconnect, LoginDisconnectSpec,
+        test_login_disconnect, bench_write_login_disconnect, bench_read_login_disconnect);
+
+    packet_test_cases!(RawPacket754, Packet754, LoginEncryptionRequest, LoginEncryptionRequestSpec,
+        test_login_encryption_request, bench_write_login_encryption_request, bench_read_login_encryption_request);
+
+    packet_test_cases!(RawPacket754, Packet754, LoginSuccess, LoginSuccessSpec,
+        test_login_success, bench_write_login_success, bench_read_login_success);
+
+    packet_test_cases!(RawPacket754, Packet754, LoginSetCompression, LoginSetCompressionSpec,
+        test_login_set_compression, bench_write_login_set_compression, bench_read_login_set_compression);
+
+    packet_test_cases!(RawPacket754, Packet754, LoginPluginRequest, LoginPluginRequestSpec,
+        test_login_plugin_request, bench_write_login_plugin_request, bench_read_login_plugin_request);
+
+    packet_test_cases!(RawPacket754, Packet754, LoginStart, LoginStartSpec,
+        test_login_start, bench_write_login_start, bench_read_login_start);
+
+    packet_test_cases!(RawPacket754, Packet754, LoginEncryptionResponse, LoginEncryptionResponseSpec,
+        test_login_encryption_response, bench_write_login_encryption_response, bench_read_login_encryption_response);
+
+    packet_test_cases!(RawPacket754, Packet754, LoginPluginResponse, LoginPluginResponseSpec,
+        test_login_plugin_response, bench_write_login_plugin_response, bench_read_login_plugin_response);
+
+    packet_test_cases!(RawPacket754, Packet754, PlaySpawnEntity, PlaySpawnEntitySpec,
+        test_play_spawn_entity, bench_write_play_spawn_entity, bench_read_play_spawn_entity);
+
+    packet_test_cases!(RawPacket754, Packet754, PlaySpawnExperienceOrb, PlaySpawnExperienceOrbSpec,
+        test_play_spawn_experience_orb, bench_write_play_spawn_experience_orb, bench_read_play_spawn_experience_orb);
+
+    packet_test_cases!(RawPacket754, Packet754, PlaySpawnLivingEntity, PlaySpawnLivingEntitySpec,
+        test_play_spawn_living_entity, bench_write_play_spawn_living_entity, bench_read_play_spawn_living_entity);
+
+    packet_test_cases!(RawPacket754, Packet754, PlaySpawnPainting, PlaySpawnPaintingSpec,
+        test_play_spawn_painting, bench_write_play_spawn_painting, bench_read_play_spawn_painting);
+
+    packet_test_cases!(RawPacket754, Packet754, PlaySpawnPlayer, PlaySpawnPlayerSpec,
+        test_play_spawn_player, bench_write_play_spawn_player, bench_read_play_spawn_player);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayEntityAnimation, PlayEntityAnimationSpec,
+        test_play_entity_animation, bench_write_play_entity_animation, bench_read_play_entity_animation);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayStatistics, PlayStatisticsSpec,
+        test_play_statistics, bench_write_play_statistics, bench_read_play_statistics);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayAcknowledgePlayerDigging, PlayAcknowledgePlayerDiggingSpec,
+        test_play_acknowledge_player_digging, bench_write_play_acknowledge_player_digging, bench_read_play_acknowledge_player_digging);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayBlockBreakAnimation, PlayBlockBreakAnimationSpec,
+        test_play_block_break_animation, bench_write_play_block_break_animation, bench_read_play_block_break_animation);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayBlockEntityData, PlayBlockEntityDataSpec,
+        test_play_block_entity_data, bench_write_play_block_entity_data, bench_read_play_block_entity_data);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayBlockAction, PlayBlockActionSpec,
+        test_play_block_action, bench_write_play_block_action, bench_read_play_block_action);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayBlockChange, PlayBlockChangeSpec,
+        test_play_block_change, bench_write_play_block_change, bench_read_play_block_change);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayBossBar, PlayBossBarSpec,
+        test_play_boss_bar, bench_write_play_boss_bar, bench_read_play_boss_bar);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayServerDifficulty, PlayServerDifficultySpec,
+        test_play_server_difficulty, bench_write_play_server_difficulty, bench_read_play_server_difficulty);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayServerChatMessage, PlayServerChatMessageSpec,
+        test_play_server_chat_message, bench_write_play_server_chat_message, bench_read_play_server_chat_message);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayTabComplete, PlayTabCompleteSpec,
+        test_play_tab_complete, bench_write_play_tab_complete, bench_read_play_tab_complete);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayDeclareCommands, PlayDeclareCommandsSpec,
+        test_play_declare_commands, bench_write_play_declare_commands, bench_read_play_declare_commands);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayServerWindowConfirmation, PlayServerWindowConfirmationSpec,
+        test_play_server_window_confirmation, bench_write_play_server_window_confirmation, bench_read_play_server_window_confirmation);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayServerCloseWindow, PlayServerCloseWindowSpec,
+        test_play_server_close_window, bench_write_play_server_close_window, bench_read_play_server_close_window);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayWindowItems, PlayWindowItemsSpec,
+        test_play_window_items, bench_write_play_window_items, bench_read_play_window_items);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayWindowProperty, PlayWindowPropertySpec,
+        test_play_window_property, bench_write_play_window_property, bench_read_play_window_property);
+
+    packet_test_cases!(RawPacket754, Packet754, PlaySetSlot, PlaySetSlotSpec,
+        test_play_set_slot, bench_write_play_set_slot, bench_read_play_set_slot);
+
+    packet_test_cases!(RawPacket754, Packet754, PlaySetCooldown, PlaySetCooldownSpec,
+        test_play_set_cooldown, bench_write_play_set_cooldown, bench_read_play_set_cooldown);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayServerPluginMessage, PlayServerPluginMessageSpec,
+        test_play_server_plugin_message, bench_write_play_server_plugin_message, bench_read_play_server_plugin_message);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayNamedSoundEffect, PlayNamedSoundEffectSpec,
+        test_play_named_sound_effect, bench_write_play_named_sound_effect, bench_read_play_named_sound_effect);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayDisconnect, PlayDisconnectSpec,
+        test_play_disconnect, bench_write_play_disconnect, bench_read_play_disconnect);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayEntityStatus, PlayEntityStatusSpec,
+        test_play_entity_status, bench_write_play_entity_status, bench_read_play_entity_status);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayExplosion, PlayExplosionSpec,
+        test_play_explosion, bench_write_play_explosion, bench_read_play_explosion);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayUnloadChunk, PlayUnloadChunkSpec,
+        test_play_unload_chunk, bench_write_play_unload_chunk, bench_read_play_unload_chunk);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayChangeGameState, PlayChangeGameStateSpec,
+        test_play_change_game_state, bench_write_play_change_game_state, bench_read_play_change_game_state);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayOpenHorseWindow, PlayOpenHorseWindowSpec,
+        test_play_open_horse_window, bench_write_play_open_horse_window, bench_read_play_open_horse_window);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayServerKeepAlive, PlayServerKeepAliveSpec,
+        test_play_server_keep_alive, bench_write_play_server_keep_alive, bench_read_play_server_keep_alive);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayChunkData, PlayChunkDataWrapper,
+        test_play_chunk_data, bench_write_play_chunk_data, bench_read_play_chunk_data);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayEffect, PlayEffectSpec,
+        test_play_effect, bench_write_play_effect, bench_read_play_effect);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayParticle, PlayParticleSpec,
+        test_play_particle, bench_write_play_particle, bench_read_play_particle);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayUpdateLight, PlayUpdateLightSpec,
+        test_play_update_light, bench_write_play_update_light, bench_read_play_update_light);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayJoinGame, PlayJoinGameSpec,
+        test_play_join_game, bench_write_play_join_game, bench_read_play_join_game);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayMapData, PlayMapDataSpec,
+        test_play_map_data, bench_write_play_map_data, bench_read_play_map_data);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayTradeList, PlayTradeListSpec,
+        test_play_trade_list, bench_write_play_trade_list, bench_read_play_trade_list);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayEntityPosition, PlayEntityPositionSpec,
+        test_play_entity_position, bench_write_play_entity_position, bench_read_play_entity_position);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayEntityPositionAndRotation, PlayEntityPositionAndRotationSpec,
+        test_play_entity_position_and_rotation, bench_write_play_entity_position_and_rotation, bench_read_play_entity_position_and_rotation);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayEntityRotation, PlayEntityRotationSpec,
+        test_play_entity_rotation, bench_write_play_entity_rotation, bench_read_play_entity_rotation);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayEntityMovement, PlayEntityMovementSpec,
+        test_play_entity_movement, bench_write_play_entity_movement, bench_read_play_entity_movement);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayServerVehicleMove, PlayEntityVehicleMoveSpec,
+        test_play_server_vehicle_move, bench_write_play_server_vehicle_move, bench_read_play_server_vehicle_move);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayOpenBook, PlayOpenBookSpec,
+        test_play_open_book, bench_write_play_open_book, bench_read_play_open_book);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayOpenWindow, PlayOpenWindowSpec,
+        test_play_open_window, bench_write_play_open_window, bench_read_play_open_window);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayOpenSignEditor, PlayOpenSignEditorSpec,
+        test_play_open_sign_editor, bench_write_play_open_sign_editor, bench_read_play_open_sign_editor);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayCraftRecipeResponse, PlayCraftRecipeResponseSpec,
+        test_play_craft_recipe_response, bench_write_play_craft_recipe_response, bench_read_play_craft_recipe_response);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayServerPlayerAbilities, PlayServerPlayerAbilitiesSpec,
+        test_play_server_player_abilities, bench_write_play_server_player_abilities, bench_read_play_server_player_abilities);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayCombatEvent, PlayCombatEventSpec,
+        test_play_combat_event, bench_write_play_combat_event, bench_read_play_combat_event);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayPlayerInfo, PlayPlayerInfoSpec,
+        test_play_player_info, bench_write_play_player_info, bench_read_play_player_info);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayFacePlayer, PlayFacePlayerSpec,
+        test_play_face_player, bench_write_play_face_player, bench_read_play_face_player);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayServerPlayerPositionAndLook, PlayServerPlayerPositionAndLookSpec,
+        test_play_server_player_position_and_look, bench_write_play_server_player_position_and_look, bench_read_play_server_player_position_and_look);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayUnlockRecipes, PlayUnlockRecipesSpec,
+        test_play_unlock_recipes, bench_write_play_unlock_recipes, bench_read_play_unlock_recipes);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayDestroyEntities, PlayDestroyEntitiesSpec,
+        test_play_destroy_entities, bench_write_play_destroy_entities, bench_read_play_destroy_entities);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayRemoveEntityEffect, PlayRemoveEntityEffectSpec,
+        test_play_remove_entity_effect, bench_write_play_remove_entity_effect, bench_read_play_remove_entity_effect);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayResourcePackSend, PlayResourcePackSendSpec,
+        test_play_resource_pack_send, bench_write_play_resource_pack_send, bench_read_play_resource_pack_send);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayRespawn, PlayRespawnSpec,
+        test_play_respawn, bench_write_play_respawn, bench_read_play_respawn);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayEntityHeadLook, PlayEntityHeadLookSpec,
+        test_play_entity_head_look, bench_write_play_entity_head_look, bench_read_play_entity_head_look);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayMultiBlockChange, PlayMultiBlockChangeSpec,
+        test_play_multi_block_change, bench_write_play_multi_block_change, bench_read_play_multi_block_change);
+
+    packet_test_cases!(RawPacket754, Packet754, PlaySelectAdvancementTab, PlaySelectAdvancementTabSpec,
+        test_play_select_advancement_tab, bench_write_play_select_advancement_tab, bench_read_play_select_advancement_tab);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayWorldBorder, PlayWorldBorderSpec,
+        test_play_world_border, bench_write_play_world_border, bench_read_play_world_border);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayCamera, PlayCameraSpec,
+        test_play_camera, bench_write_play_camera, bench_read_play_camera);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayServerHeldItemChange, PlayServerHeldItemChangeSpec,
+        test_play_server_held_item_change, bench_write_play_server_held_item_change, bench_read_play_server_held_item_change);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayUpdateViewPosition, PlayUpdateViewPositionSpec,
+        test_play_update_view_position, bench_write_play_update_view_position, bench_read_play_update_view_position);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayUpdateViewDistance, PlayUpdateViewDistanceSpec,
+        test_play_update_view_distance, bench_write_play_update_view_distance, bench_read_play_update_view_distance);
+
+    packet_test_cases!(RawPacket754, Packet754, PlaySpawnPosition, PlaySpawnPositionSpec,
+        test_play_spawn_position, bench_write_play_spawn_position, bench_read_play_spawn_position);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayDisplayScoreboard, PlayDisplayScoreboardSpec,
+        test_play_display_scoreboard, bench_write_play_display_scoreboard, bench_read_play_display_scoreboard);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayEntityMetadata, PlayEntityMetadataSpec,
+        test_play_entity_metadata, bench_write_play_entity_metadata, bench_read_play_entity_metadata);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayAttachEntity, PlayAttachEntitySpec,
+        test_play_attach_entity, bench_write_play_attach_entity, bench_read_play_attach_entity);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayEntityVelocity, PlayEntityVelocitySpec,
+        test_play_entity_velocity, bench_write_play_entity_velocity, bench_read_play_entity_velocity);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayEntityEquipment, PlayEntityEquiptmentSpec,
+        test_play_entity_equipment, bench_write_play_entity_equipment, bench_read_play_entity_equipment);
+
+    packet_test_cases!(RawPacket754, Packet754, PlaySetExperience, PlaySetExperienceSpec,
+        test_play_set_experience, bench_write_play_set_experience, bench_read_play_set_experience);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayUpdatehealth, PlayUpdateHealthSpec,
+        test_play_updatehealth, bench_write_play_updatehealth, bench_read_play_updatehealth);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayScoreboardObjective, PlayScoreboardObjectiveSpec,
+        test_play_scoreboard_objective, bench_write_play_scoreboard_objective, bench_read_play_scoreboard_objective);
+
+    packet_test_cases!(RawPacket754, Packet754, PlaySetPassengers, PlaySetPassengersSpec,
+        test_play_set_passengers, bench_write_play_set_passengers, bench_read_play_set_passengers);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayTeams, PlayTeamsSpec,
+        test_play_teams, bench_write_play_teams, bench_read_play_teams);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayUpdateScore, PlayUpdateScoreSpec,
+        test_play_update_score, bench_write_play_update_score, bench_read_play_update_score);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayTimeUpdate, PlayTimeUpdateSpec,
+        test_play_time_update, bench_write_play_time_update, bench_read_play_time_update);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayTitle, PlayTitleSpec,
+        test_play_title, bench_write_play_title, bench_read_play_title);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayEntitySoundEffect, PlayEntitySoundEffectSpec,
+        test_play_entity_sound_effect, bench_write_play_entity_sound_effect, bench_read_play_entity_sound_effect);
+
+    packet_test_cases!(RawPacket754, Packet754, PlaySoundEffect, PlaySoundEffectSpec,
+        test_play_sound_effect, bench_write_play_sound_effect, bench_read_play_sound_effect);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayStopSound, PlayStopSoundSpec,
+        test_play_stop_sound, bench_write_play_stop_sound, bench_read_play_stop_sound);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayerPlayerListHeaderAndFooter, PlayPlayerListHeaderAndFooterSpec,
+        test_player_player_list_header_and_footer, bench_write_player_player_list_header_and_footer, bench_read_player_player_list_header_and_footer);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayNbtQueryResponse, PlayNbtQueryResponseSpec,
+        test_play_nbt_query_response, bench_write_play_nbt_query_response, bench_read_play_nbt_query_response);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayCollectItem, PlayCollectItemSpec,
+        test_play_collect_item, bench_write_play_collect_item, bench_read_play_collect_item);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayEntityTeleport, PlayEntityTeleportSpec,
+        test_play_entity_teleport, bench_write_play_entity_teleport, bench_read_play_entity_teleport);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayAdvancements, PlayAdvancementsSpec,
+        test_play_advancements, bench_write_play_advancements, bench_read_play_advancements);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayEntityProperties, PlayEntityPropertiesSpec,
+        test_play_entity_properties, bench_write_play_entity_properties, bench_read_play_entity_properties);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayEntityEffect, PlayEntityEffectSpec,
+        test_play_entity_effect, bench_write_play_entity_effect, bench_read_play_entity_effect);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayDeclareRecipes, PlayDeclareRecipesSpec,
+        test_play_declare_recipes, bench_write_play_declare_recipes, bench_read_play_declare_recipes);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayTags, PlayTagsSpec,
+        test_play_tags, bench_write_play_tags, bench_read_play_tags);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayTeleportConfirm, PlayTeleportConfirmSpec,
+        test_play_teleport_confirm, bench_write_play_teleport_confirm, bench_read_play_teleport_confirm);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayQueryBlockNbt, PlayQueryBlockNbtSpec,
+        test_play_query_block_nbt, bench_write_play_query_block_nbt, bench_read_play_query_block_nbt);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayQueryEntityNbt, PlayQueryEntityNbtSpec,
+        test_play_query_entity_nbt, bench_write_play_query_entity_nbt, bench_read_play_query_entity_nbt);
+
+    packet_test_cases!(RawPacket754, Packet754, PlaySetDifficulty, PlaySetDifficultySpec,
+        test_play_set_difficulty, bench_write_play_set_difficulty, bench_read_play_set_difficulty);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayClientChatMessage, PlayClientChatMessageSpec,
+        test_play_client_chat_message, bench_write_play_client_chat_message, bench_read_play_client_chat_message);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayClientStatus, PlayClientStatusSpec,
+        test_play_client_status, bench_write_play_client_status, bench_read_play_client_status);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayClientSettings, PlayClientSettingsSpec,
+        test_play_client_settings, bench_write_play_client_settings, bench_read_play_client_settings);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayClientTabComplete, PlayClientTabCompleteSpec,
+        test_play_client_tab_complete, bench_write_play_client_tab_complete, bench_read_play_client_tab_complete);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayClientWindowConfirmation, PlayClientWindowConfirmationSpec,
+        test_play_client_window_confirmation, bench_write_play_client_window_confirmation, bench_read_play_client_window_confirmation);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayClickWindowButton, PlayClickWindowButtonSpec,
+        test_play_click_window_button, bench_write_play_click_window_button, bench_read_play_click_window_button);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayClickWindow, PlayClickWindowSpec,
+        test_play_click_window, bench_write_play_click_window, bench_read_play_click_window);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayClientCloseWindow, PlayClientCloseWindowSpec,
+        test_play_client_close_window, bench_write_play_client_close_window, bench_read_play_client_close_window);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayClientPluginMessage, PlayClientPluginMessageSpec,
+        test_play_client_plugin_message, bench_write_play_client_plugin_message, bench_read_play_client_plugin_message);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayEditBook, PlayEditBookSpec,
+        test_play_edit_book, bench_write_play_edit_book, bench_read_play_edit_book);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayInteractEntity, PlayInteractEntitySpec,
+        test_play_interact_entity, bench_write_play_interact_entity, bench_read_play_interact_entity);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayGenerateStructure, PlayGenerateStructureSpec,
+        test_play_generate_structure, bench_write_play_generate_structure, bench_read_play_generate_structure);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayClientKeepAlive, PlayClientKeepAliveSpec,
+        test_play_client_keep_alive, bench_write_play_client_keep_alive, bench_read_play_client_keep_alive);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayLockDifficulty, PlayLockDifficultySpec,
+        test_play_lock_difficulty, bench_write_play_lock_difficulty, bench_read_play_lock_difficulty);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayPlayerPosition, PlayPlayerPositionSpec,
+        test_play_player_position, bench_write_play_player_position, bench_read_play_player_position);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayClientPlayerPositionAndRotation, PlayClientPlayerPositionAndRotationSpec,
+        test_play_client_player_position_and_rotation, bench_write_play_client_player_position_and_rotation, bench_read_play_client_player_position_and_rotation);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayPlayerRotation, PlayPlayerRotationSpec,
+        test_play_player_rotation, bench_write_play_player_rotation, bench_read_play_player_rotation);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayPlayerMovement, PlayPlayerMovementSpec,
+        test_play_player_movement, bench_write_play_player_movement, bench_read_play_player_movement);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayClientVehicleMove, PlayClientVehicleMoveSpec,
+        test_play_client_vehicle_move, bench_write_play_client_vehicle_move, bench_read_play_client_vehicle_move);
+
+    packet_test_cases!(RawPacket754, Packet754, PlaySteerBoat, PlaySteerBoatSpec,
+        test_play_steer_boat, bench_write_play_steer_boat, bench_read_play_steer_boat);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayPickItem, PlayPickItemSpec,
+        test_play_pick_item, bench_write_play_pick_item, bench_read_play_pick_item);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayCraftRecipeRequest, PlayCraftRecipeRequestSpec,
+        test_play_craft_recipe_request, bench_write_play_craft_recipe_request, bench_read_play_craft_recipe_request);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayClientPlayerAbilities, PlayClientPlayerAbilitiesSpec,
+        test_play_client_player_abilities, bench_write_play_client_player_abilities, bench_read_play_client_player_abilities);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayPlayerDigging, PlayPlayerDiggingSpec,
+        test_play_player_digging, bench_write_play_player_digging, bench_read_play_player_digging);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayEntityAction, PlayEntityActionSpec,
+        test_play_entity_action, bench_write_play_entity_action, bench_read_play_entity_action);
+
+    packet_test_cases!(RawPacket754, Packet754, PlaySteerVehicle, PlaySteerVehicleSpec,
+        test_play_steer_vehicle, bench_write_play_steer_vehicle, bench_read_play_steer_vehicle);
+
+    packet_test_cases!(RawPacket754, Packet754, PlaySetDisplayedRecipe, PlaySetDisplayedRecipeSpec,
+        test_play_set_displayed_recipe, bench_write_play_set_displayed_recipe, bench_read_play_set_displayed_recipe);
+
+    packet_test_cases!(RawPacket754, Packet754, PlaySetRecipeBookState, PlaySetRecipeBookStateSpec,
+        test_play_set_recipe_book_state, bench_write_play_set_recipe_book_state, bench_read_play_set_recipe_book_state);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayNameItem, PlayNameItemSpec,
+        test_play_name_item, bench_write_play_name_item, bench_read_play_name_item);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayResourcePackStatus, PlayResourcePackStatusSpec,
+        test_play_resource_pack_status, bench_write_play_resource_pack_status, bench_read_play_resource_pack_status);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayAdvancementTab, PlayAdvancementTabSpec,
+        test_play_advancement_tab, bench_write_play_advancement_tab, bench_read_play_advancement_tab);
+
+    packet_test_cases!(RawPacket754, Packet754, PlaySelectTrade, PlaySelectTradeSpec,
+        test_play_select_trade, bench_write_play_select_trade, bench_read_play_select_trade);
+
+    packet_test_cases!(RawPacket754, Packet754, PlaySetBeaconEffect, PlaySetBeaconEffectSpec,
+        test_play_set_beacon_effect, bench_write_play_set_beacon_effect, bench_read_play_set_beacon_effect);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayClientHeldItemChange, PlayClientHeldItemChangeSpec,
+        test_play_client_held_item_change, bench_write_play_client_held_item_change, bench_read_play_client_held_item_change);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayUpdateCommandBlock, PlayUpdateCommandBlockSpec,
+        test_play_update_command_block, bench_write_play_update_command_block, bench_read_play_update_command_block);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayUpdateCommandBlockMinecart, PlayUpdateCommandBlockMinecartSpec,
+        test_play_update_command_block_minecart, bench_write_play_update_command_block_minecart, bench_read_play_update_command_block_minecart);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayUpdateJigsawBlock, PlayUpdateJigsawBlockSpec,
+        test_play_update_jigsaw_block, bench_write_play_update_jigsaw_block, bench_read_play_update_jigsaw_block);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayCreativeInventoryAction, PlayCreativeInventoryActionSpec,
+        test_play_creative_inventory_action, bench_write_play_creative_inventory_action, bench_read_play_creative_inventory_action);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayUpdateStructureBlock, PlayUpdateStructureBlockSpec,
+        test_play_update_structure_block, bench_write_play_update_structure_block, bench_read_play_update_structure_block);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayUpdateSign, PlayUpdateSignSpec,
+        test_play_update_sign, bench_write_play_update_sign, bench_read_play_update_sign);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayClientAnimation, PlayClientAnimationSpec,
+        test_play_client_animation, bench_write_play_client_animation, bench_read_play_client_animation);
+
+    packet_test_cases!(RawPacket754, Packet754, PlaySpectate, PlaySpectateSpec,
+        test_play_spectate, bench_write_play_spectate, bench_read_play_spectate);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayBlockPlacement, PlayBlockPlacementSpec,
+        test_play_block_placement, bench_write_play_block_placement, bench_read_play_block_placement);
+
+    packet_test_cases!(RawPacket754, Packet754, PlayUseItem, PlayUseItemSpec,
+        test_play_use_item, bench_write_play_use_item, bench_read_play_use_item);
+
+    #[test]
+    fn test_recipe_book_ids_swapped() {
+        use crate::protocol::{PacketDirection::ServerBound, State::Play};
+
+        let state = Packet754::PlaySetRecipeBookState(PlaySetRecipeBookStateSpec::test_gen_random());
+        assert_eq!(state.id(), (0x1E, Play, ServerBound).into());
+        let displayed = Packet754::PlaySetDisplayedRecipe(PlaySetDisplayedRecipeSpec::test_gen_random());
+        assert_eq!(displayed.id(), (0x1F, Play, ServerBound).into());
+    }
+
+    #[test]
+    fn test_same_ids_as_1_16_3() {
+        use crate::v1_16_3::Packet753;
+
+        let changed = ["PlaySetRecipeBookState", "PlaySetDisplayedRecipe"];
+        let old = Packet753::describe().packets;
+        let new = Packet754::describe().packets;
+        assert_eq!(old.len(), new.len());
+        for new in new.iter().filter(|packet| !changed.contains(&packet.name.as_str())) {
+            let old = old.iter().find(|packet| packet.name == new.name).expect("exists in 1.16.3");
+            assert_eq!(old.body_struct, new.body_struct);
+            assert_eq!(old.fields, new.fields);
+            assert_eq!((old.id, &old.state, &old.direction), (new.id, &new.state, &new.direction));
+        }
+
+        // reused bodies still report their fields
+        let chat = new.iter().find(|packet| packet.name == "PlayServerChatMessage").expect("exists");
+        let fields: Vec<_> = chat.fields.iter().map(|field| (field.name.as_str(), field.kind.as_str())).collect();
+        assert_eq!(fields, vec![("message", "Chat"), ("position", "ChatPosition"), ("sender", "UUID4")]);
+        let set_state = new.iter().find(|packet| packet.name == "PlaySetRecipeBookState").expect("exists");
+        assert!(!set_state.fields.is_empty());
+    }
+}
@@ -0,0 +1,1252 @@
+// 1.17.1 mostly reshuffles ids, so bodies that kept their 1.16.3 layout are reused from there. The
+// packets that changed shape are redefined here and shadow the 1.16.3 names.
+pub use crate::v1_16_3::*;
+use crate::{types::*, *};
+use alloc::{borrow::ToOwned, boxed::Box, fmt, string::String, vec::Vec};
+
+#[cfg(all(test, feature = "std"))]
+use crate::protocol::TestRandom;
+
+define_protocol!(756, Packet756, RawPacket756, RawPacket756Body, Packet756Kind => {
+    // handshaking
+    Handshake, 0x00, Handshaking, ServerBound => HandshakeSpec,
+    // status
+    StatusRequest, 0x00, Status, ServerBound => StatusRequestSpec,
+    StatusPing, 0x01, Status, ServerBound => StatusPingSpec,
+    StatusResponse, 0x00, Status, ClientBound => StatusResponseSpec,
+    StatusPong, 0x01, Status, ClientBound => StatusPongSpec,
+    // login
+    LoginDisconnect, 0x00, Login, ClientBound => LoginDisconnectSpec,
+    LoginEncryptionRequest, 0x01, Login, ClientBound => LoginEncryptionRequestSpec,
+    LoginSuccess, 0x02, Login, ClientBound => LoginSuccessSpec,
+    LoginSetCompression, 0x03, Login, ClientBound => LoginSetCompressionSpec,
+    LoginPluginRequest, 0x04, Login, ClientBound => LoginPluginRequestSpec,
+    LoginStart, 0x00, Login, ServerBound => LoginStartSpec,
+    LoginEncryptionResponse, 0x01, Login, ServerBound => LoginEncryptionResponseSpec,
+    LoginPluginResponse, 0x02, Login, ServerBound => LoginPluginResponseSpec,
+    // play
+    // client bound
+    PlaySpawnEntity, 0x00, Play, ClientBound => PlaySpawnEntitySpec,
+    PlaySpawnExperienceOrb, 0x01, Play, ClientBound => PlaySpawnExperienceOrbSpec,
+    PlaySpawnLivingEntity, 0x02, Play, ClientBound => PlaySpawnLivingEntitySpec,
+    PlaySpawnPainting, 0x03, Play, ClientBound => PlaySpawnPaintingSpec,
+    PlaySpawnPlayer, 0x04, Play, ClientBound => PlaySpawnPlayerSpec,
+    PlaySculkVibrationSignal, 0x05, Play, ClientBound => PlaySculkVibrationSignalSpec {
+        source_position: IntPosition,
+        destination: VibrationDestination,
+        arrival_ticks: VarInt
+    },
+    PlayEntityAnimation, 0x06, Play, ClientBound => PlayEntityAnimationSpec,
+    PlayStatistics, 0x07, Play, ClientBound => PlayStatisticsSpec,
+    PlayAcknowledgePlayerDigging, 0x08, Play, ClientBound => PlayAcknowledgePlayerDiggingSpec,
+    PlayBlockBreakAnimation, 0x09, Play, ClientBound => PlayBlockBreakAnimationSpec,
+    PlayBlockEntityData, 0x0A, Play, ClientBound => PlayBlockEntityDataSpec,
+    PlayBlockAction, 0x0B, Play, ClientBound => PlayBlockActionSpec,
+    PlayBlockChange, 0x0C, Play, ClientBound => PlayBlockChangeSpec,
+    PlayBossBar, 0x0D, Play, ClientBound => PlayBossBarSpec,
+    PlayServerDifficulty, 0x0E, Play, ClientBound => PlayServerDifficultySpec,
+    PlayServerChatMessage, 0x0F, Play, ClientBound => PlayServerChatMessageSpec,
+    PlayClearTitles, 0x10, Play, ClientBound => PlayClearTitlesSpec {
+        reset: bool
+    },
+    PlayTabComplete, 0x11, Play, ClientBound => PlayTabCompleteSpec,
+    PlayDeclareCommands, 0x12, Play, ClientBound => PlayDeclareCommandsSpec,
+    PlayServerCloseWindow, 0x13, Play, ClientBound => PlayServerCloseWindowSpec,
+    PlayWindowItems, 0x14, Play, ClientBound => PlayWindowItemsSpec {
+        window_id: u8,
+        state_id: VarInt,
+        slots: CountedArray<Slot, VarInt>,
+        carried_item: Slot
+    },
+    PlayWindowProperty, 0x15, Play, ClientBound => PlayWindowPropertySpec,
+    PlaySetSlot, 0x16, Play, ClientBound => PlaySetSlotSpec {
+        window_id: i8,
+        state_id: VarInt,
+        slot: i16,
+        slot_data: Slot
+    },
+    PlaySetCooldown, 0x17, Play, ClientBound => PlaySetCooldownSpec,
+    PlayServerPluginMessage, 0x18, Play, ClientBound => PlayServerPluginMessageSpec,
+    PlayNamedSoundEffect, 0x19, Play, ClientBound => PlayNamedSoundEffectSpec,
+    PlayDisconnect, 0x1A, Play, ClientBound => PlayDisconnectSpec,
+    PlayEntityStatus, 0x1B, Play, ClientBound => PlayEntityStatusSpec,
+    PlayExplosion, 0x1C, Play, ClientBound => PlayExplosionSpec {
+        position: Vec3<f32>,
+        strength: f32,
+        records: CountedArray<Vec3<i8>, VarInt>,
+        player_motion: Vec3<f32>
+    },
+    PlayUnloadChunk, 0x1D, Play, ClientBound => PlayUnloadChunkSpec,
+    PlayChangeGameState, 0x1E, Play, ClientBound => PlayChangeGameStateSpec,
+    PlayOpenHorseWindow, 0x1F, Play, ClientBound => PlayOpenHorseWindowSpec,
+    PlayInitializeWorldBorder, 0x20, Play, ClientBound => PlayInitializeWorldBorderSpec {
+        position: TopDownPosition<f64>,
+        old_diameter: f64,
+        new_diameter: f64,
+        speed: VarLong,
+        portal_teleport_boundary: VarInt,
+        warning_blocks: VarInt,
+        warning_time: VarInt
+    },
+    PlayServerKeepAlive, 0x21, Play, ClientBound => PlayServerKeepAliveSpec,
+    PlayChunkData, 0x22, Play, ClientBound => PlayChunkDataSpec {
+        position: ChunkPosition<i32>,
+        primary_bit_mask: BitSet,
+        heightmaps: NamedNbtTag,
+        biomes: CountedArray<VarInt, VarInt>,
+        data: CountedArray<u8, VarInt>,
+        block_entities: CountedArray<NamedNbtTag, VarInt>
+    },
+    PlayEffect, 0x23, Play, ClientBound => PlayEffectSpec,
+    PlayParticle, 0x24, Play, ClientBound => PlayParticleSpec,
+    PlayUpdateLight, 0x25, Play, ClientBound => PlayUpdateLightSpec {
+        chunk: ChunkPosition<VarInt>,
+        trust_edges: bool,
+        update: LightingUpdateSpec
+    },
+    PlayJoinGame, 0x26, Play, ClientBound => PlayJoinGameSpec,
+    PlayMapData, 0x27, Play, ClientBound => PlayMapDataSpec {
+        map_id: VarInt,
+        scale: i8,
+        locked: bool,
+        icons: Option<CountedArray<MapIconSpec, VarInt>>,
+        columns: MapColumns
+    },
+    PlayTradeList, 0x28, Play, ClientBound => PlayTradeListSpec,
+    PlayEntityPosition, 0x29, Play, ClientBound => PlayEntityPositionSpec,
+    PlayEntityPositionAndRotation, 0x2A, Play, ClientBound => PlayEntityPositionAndRotationSpec,
+    PlayEntityRotation, 0x2B, Play, ClientBound => PlayEntityRotationSpec,
+    PlayServerVehicleMove, 0x2C, Play, ClientBound => PlayEntityVehicleMoveSpec,
+    PlayOpenBook, 0x2D, Play, ClientBound => PlayOpenBookSpec,
+    PlayOpenWindow, 0x2E, Play, ClientBound => PlayOpenWindowSpec,
+    PlayOpenSignEditor, 0x2F, Play, ClientBound => PlayOpenSignEditorSpec,
+    PlayPing, 0x30, Play, ClientBound => PlayPingSpec {
+        id: i32
+    },
+    PlayCraftRecipeResponse, 0x31, Play, ClientBound => PlayCraftRecipeResponseSpec,
+    PlayServerPlayerAbilities, 0x32, Play, ClientBound => PlayServerPlayerAbilitiesSpec,
+    PlayEndCombatEvent, 0x33, Play, ClientBound => PlayEndCombatEventSpec {
+        duration_ticks: VarInt,
+        entity_id: i32
+    },
+    PlayEnterCombatEvent, 0x34, Play, ClientBound => PlayEnterCombatEventSpec {},
+    PlayDeathCombatEvent, 0x35, Play, ClientBound => PlayDeathCombatEventSpec {
+        player_id: VarInt,
+        entity_id: i32,
+        message: Chat
+    },
+    PlayPlayerInfo, 0x36, Play, ClientBound => PlayPlayerInfoSpec,
+    PlayFacePlayer, 0x37, Play, ClientBound => PlayFacePlayerSpec,
+    PlayServerPlayerPositionAndLook, 0x38, Play, ClientBound => PlayServerPlayerPositionAndLookSpec {
+        location: EntityLocation<f64, f32>,
+        flags: PositionAndLookFlags,
+        teleport_id: VarInt,
+        dismount_vehicle: bool
+    },
+    PlayUnlockRecipes, 0x39, Play, ClientBound => PlayUnlockRecipesSpec,
+    PlayDestroyEntities, 0x3A, Play, ClientBound => PlayDestroyEntitiesSpec,
+    PlayRemoveEntityEffect, 0x3B, Play, ClientBound => PlayRemoveEntityEffectSpec,
+    PlayResourcePackSend, 0x3C, Play, ClientBound => PlayResourcePackSendSpec {
+        url: String,
+        hash: String,
+        forced: bool,
+        prompt_message: Option<Chat>
+    },
+    PlayRespawn, 0x3D, Play, ClientBound => PlayRespawnSpec,
+    PlayEntityHeadLook, 0x3E, Play, ClientBound => PlayEntityHeadLookSpec,
+    PlayMultiBlockChange, 0x3F, Play, ClientBound => PlayMultiBlockChangeSpec,
+    PlaySelectAdvancementTab, 0x40, Play, ClientBound => PlaySelectAdvancementTabSpec,
+    PlayActionBar, 0x41, Play, ClientBound => PlayActionBarSpec {
+        text: Chat
+    },
+    PlayWorldBorderCenter, 0x42, Play, ClientBound => PlayWorldBorderCenterSpec {
+        position: TopDownPosition<f64>
+    },
+    PlayWorldBorderLerpSize, 0x43, Play, ClientBound => PlayWorldBorderLerpSizeSpec {
+        old_diameter: f64,
+        new_diameter: f64,
+        speed: VarLong
+    },
+    PlayWorldBorderSize, 0x44, Play, ClientBound => PlayWorldBorderSizeSpec {
+        diameter: f64
+    },
+    PlayWorldBorderWarningDelay, 0x45, Play, ClientBound => PlayWorldBorderWarningDelaySpec {
+        warning_time: VarInt
+    },
+    PlayWorldBorderWarningReach, 0x46, Play, ClientBound => PlayWorldBorderWarningReachSpec {
+        warning_blocks: VarInt
+    },
+    PlayCamera, 0x47, Play, ClientBound => PlayCameraSpec,
+    PlayServerHeldItemChange, 0x48, Play, ClientBound => PlayServerHeldItemChangeSpec,
+    PlayUpdateViewPosition, 0x49, Play, ClientBound => PlayUpdateViewPositionSpec,
+    PlayUpdateViewDistance, 0x4A, Play, ClientBound => PlayUpdateViewDistanceSpec,
+    PlaySpawnPosition, 0x4B, Play, ClientBound => PlaySpawnPositionSpec {
+        location: IntPosition,
+        angle: f32
+    },
+    PlayDisplayScoreboard, 0x4C, Play, ClientBound => PlayDisplayScoreboardSpec,
+    PlayEntityMetadata, 0x4D, Play, ClientBound => PlayEntityMetadataSpec,
+    PlayAttachEntity, 0x4E, Play, ClientBound => PlayAttachEntitySpec,
+    PlayEntityVelocity, 0x4F, Play, ClientBound => PlayEntityVelocitySpec,
+    PlayEntityEquipment, 0x50, Play, ClientBound => PlayEntityEquiptmentSpec,
+    PlaySetExperience, 0x51, Play, ClientBound => PlaySetExperienceSpec,
+    PlayUpdatehealth, 0x52, Play, ClientBound => PlayUpdateHealthSpec,
+    PlayScoreboardObjective, 0x53, Play, ClientBound => PlayScoreboardObjectiveSpec,
+    PlaySetPassengers, 0x54, Play, ClientBound => PlaySetPassengersSpec,
+    PlayTeams, 0x55, Play, ClientBound => PlayTeamsSpec,
+    PlayUpdateScore, 0x56, Play, ClientBound => PlayUpdateScoreSpec,
+    PlaySetTitleSubtitle, 0x57, Play, ClientBound => PlaySetTitleSubtitleSpec {
+        text: Chat
+    },
+    PlayTimeUpdate, 0x58, Play, ClientBound => PlayTimeUpdateSpec,
+    PlaySetTitleText, 0x59, Play, ClientBound => PlaySetTitleTextSpec {
+        text: Chat
+    },
+    PlaySetTitleTimes, 0x5A, Play, ClientBound => PlaySetTitleTimesSpec {
+        fade_in: i32,
+        stay: i32,
+        fade_out: i32
+    },
+    PlayEntitySoundEffect, 0x5B, Play, ClientBound => PlayEntitySoundEffectSpec,
+    PlaySoundEffect, 0x5C, Play, ClientBound => PlaySoundEffectSpec,
+    PlayStopSound, 0x5D, Play, ClientBound => PlayStopSoundSpec,
+    PlayerPlayerListHeaderAndFooter, 0x5E, Play, ClientBound => PlayPlayerListHeaderAndFooterSpec,
+    PlayNbtQueryResponse, 0x5F, Play, ClientBound => PlayNbtQueryResponseSpec,
+    PlayCollectItem, 0x60, Play, ClientBound => PlayCollectItemSpec,
+    PlayEntityTeleport, 0x61, Play, ClientBound => PlayEntityTeleportSpec,
+    PlayAdvancements, 0x62, Play, ClientBound => PlayAdvancementsSpec,
+    PlayEntityProperties, 0x63, Play, ClientBound => PlayEntityPropertiesSpec {
+        entity_id: VarInt,
+        properties: CountedArray<EntityPropertySpec, VarInt>
+    },
+    PlayEntityEffect, 0x64, Play, ClientBound => PlayEntityEffectSpec,
+    PlayDeclareRecipes, 0x65, Play, ClientBound => PlayDeclareRecipesSpec,
+    PlayTags, 0x66, Play, ClientBound => PlayTagsSpec {
+        tags: CountedArray<TagTypeSpec, VarInt>
+    },
+
+    // play server bound
+    PlayTeleportConfirm, 0x00, Play, ServerBound => PlayTeleportConfirmSpec,
+    PlayQueryBlockNbt, 0x01, Play, ServerBound => PlayQueryBlockNbtSpec,
+    PlaySetDifficulty, 0x02, Play, ServerBound => PlaySetDifficultySpec,
+    PlayClientChatMessage, 0x03, Play, ServerBound => PlayClientChatMessageSpec,
+    PlayClientStatus, 0x04, Play, ServerBound => PlayClientStatusSpec,
+    PlayClientSettings, 0x05, Play, ServerBound => PlayClientSettingsSpec {
+        locale: String,
+        view_distance: i8,
+        chat_mode: ClientChatMode,
+        chat_colors: bool,
+        displayed_skin_parts: ClientDisplayedSkinParts,
+        main_hand: ClientMainHand,
+        disable_text_filtering: bool
+    },
+    PlayClientTabComplete, 0x06, Play, ServerBound => PlayClientTabCompleteSpec,
+    PlayClickWindowButton, 0x07, Play, ServerBound => PlayClickWindowButtonSpec,
+    PlayClickWindow, 0x08, Play, ServerBound => PlayClickWindowSpec {
+        window_id: u8,
+        state_id: VarInt,
+        slot: i16,
+        button: i8,
+        mode: InventoryOperationMode,
+        changed_slots: CountedArray<ChangedSlotSpec, VarInt>,
+        carried_item: Slot
+    },
+    PlayClientCloseWindow, 0x09, Play, ServerBound => PlayClientCloseWindowSpec,
+    PlayClientPluginMessage, 0x0A, Play, ServerBound => PlayClientPluginMessageSpec,
+    PlayEditBook, 0x0B, Play, ServerBound => PlayEditBookSpec,
+    PlayQueryEntityNbt, 0x0C, Play, ServerBound => PlayQueryEntityNbtSpec,
+    PlayInteractEntity, 0x0D, Play, ServerBound => PlayInteractEntitySpec,
+    PlayGenerateStructure, 0x0E, Play, ServerBound => PlayGenerateStructureSpec,
+    PlayClientKeepAlive, 0x0F, Play, ServerBound => PlayClientKeepAliveSpec,
+    PlayLockDifficulty, 0x10, Play, ServerBound => PlayLockDifficultySpec,
+    PlayPlayerPosition, 0x11, Play, ServerBound => PlayPlayerPositionSpec,
+    PlayClientPlayerPositionAndRotation, 0x12, Play, ServerBound => PlayClientPlayerPositionAndRotationSpec,
+    PlayPlayerRotation, 0x13, Play, ServerBound => PlayPlayerRotationSpec,
+    PlayPlayerMovement, 0x14, Play, ServerBound => PlayPlayerMovementSpec,
+    PlayClientVehicleMove, 0x15, Play, ServerBound => PlayClientVehicleMoveSpec,
+    PlaySteerBoat, 0x16, Play, ServerBound => PlaySteerBoatSpec,
+    PlayPickItem, 0x17, Play, ServerBound => PlayPickItemSpec,
+    PlayCraftRecipeRequest, 0x18, Play, ServerBound => PlayCraftRecipeRequestSpec,
+    PlayClientPlayerAbilities, 0x19, Play, ServerBound => PlayClientPlayerAbilitiesSpec,
+    PlayPlayerDigging, 0x1A, Play, ServerBound => PlayPlayerDiggingSpec,
+    PlayEntityAction, 0x1B, Play, ServerBound => PlayEntityActionSpec,
+    PlaySteerVehicle, 0x1C, Play, ServerBound => PlaySteerVehicleSpec,
+    PlayPong, 0x1D, Play, ServerBound => PlayPongSpec {
+        id: i32
+    },
+    PlaySetRecipeBookState, 0x1E, Play, ServerBound => PlaySetRecipeBookStateSpec,
+    PlaySetDisplayedRecipe, 0x1F, Play, ServerBound => PlaySetDisplayedRecipeSpec,
+    PlayNameItem, 0x20, Play, ServerBound => PlayNameItemSpec,
+    PlayResourcePackStatus, 0x21, Play, ServerBound => PlayResourcePackStatusSpec,
+    PlayAdvancementTab, 0x22, Play, ServerBound => PlayAdvancementTabSpec,
+    PlaySelectTrade, 0x23, Play, ServerBound => PlaySelectTradeSpec,
+    PlaySetBeaconEffect, 0x24, Play, ServerBound => PlaySetBeaconEffectSpec,
+    PlayClientHeldItemChange, 0x25, Play, ServerBound => PlayClientHeldItemChangeSpec,
+    PlayUpdateCommandBlock, 0x26, Play, ServerBound => PlayUpdateCommandBlockSpec,
+    PlayUpdateCommandBlockMinecart, 0x27, Play, ServerBound => PlayUpdateCommandBlockMinecartSpec,
+    PlayUpdateJigsawBlock, 0x28, Play, ServerBound => PlayUpdateJigsawBlockSpec,
+    PlayCreativeInventoryAction, 0x29, Play, ServerBound => PlayCreativeInventoryActionSpec,
+    PlayUpdateStructureBlock, 0x2A, Play, ServerBound => PlayUpdateStructureBlockSpec,
+    PlayUpdateSign, 0x2B, Play, ServerBound => PlayUpdateSignSpec,
+    PlayClientAnimation, 0x2C, Play, ServerBound => PlayClientAnimationSpec,
+    PlaySpectate, 0x2D, Play, ServerBound => PlaySpectateSpec,
+    PlayBlockPlacement, 0x2E, Play, ServerBound => PlayBlockPlacementSpec,
+    PlayUseItem, 0x2F, Play, ServerBound => PlayUseItemSpec
+});
+
+impl crate::protocol::HasCompressionThreshold for Packet756 {
+    fn compression_threshold(&self) -> Option<i32> {
+        match self {
+            Packet756::LoginSetCompression(body) => Some(body.threshold.0),
+            _ => None,
+        }
+    }
+}
+
+impl crate::protocol::HasNextState for Packet756 {
+    fn next_state(&self) -> Option<crate::protocol::State> {
+        use crate::protocol::State;
+        match self {
+            Packet756::Handshake(body) => Some(match body.next_state {
+                HandshakeNextState::Status => State::Status,
+                HandshakeNextState::Login => State::Login,
+            }),
+            Packet756::LoginSuccess(_) => Some(State::Play),
+            _ => None,
+        }
+    }
+}
+
+impl crate::limits::HasFieldLimits for Packet756 {
+    fn check_field_limits(&self, limits: &crate::limits::DeserializeLimits) -> Result<(), DeserializeErr> {
+        match self {
+            Packet756::LoginStart(body) => limits.check_username(body.name.as_str()),
+            Packet756::LoginSuccess(body) => limits.check_username(body.username.as_str()),
+            _ => Ok(()),
+        }
+    }
+}
+
+proto_str_enum!(VibrationDestination,
+    "minecraft:block" :: Block(IntPosition),
+    "minecraft:entity" :: Entity(VarInt)
+);
+
+proto_struct!(ChangedSlotSpec {
+    slot: i16,
+    item: Slot
+});
+
+proto_struct!(TagTypeSpec {
+    kind: String,
+    tags: CountedArray<TagSpec, VarInt>
+});
+
+// the vertical extent of a dimension, as sent in the "min_y" and "height" fields of the dimension
+// type. Since 1.17 the number of chunk sections is no longer fixed at 16.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WorldHeight {
+    pub min_y: i32,
+    pub height: i32,
+}
+
+impl WorldHeight {
+    pub const OVERWORLD: WorldHeight = WorldHeight { min_y: 0, height: 256 };
+
+    pub fn from_dimension(dimension: &NamedNbtTag) -> Option<Self> {
+        let entries = match &dimension.root.payload {
+            nbt::Tag::Compound(entries) => entries,
+            _ => return None,
+        };
+
+        let int_field = move |name: &str| entries.iter()
+            .find(move |entry| entry.name == name)
+            .and_then(move |entry| match entry.payload {
+                nbt::Tag::Int(v) => Some(v),
+                _ => None,
+            });
+
+        Some(Self {
+            min_y: int_field("min_y")?,
+            height: int_field("height")?,
+        })
+    }
+
+    pub fn section_count(&self) -> usize {
+        (self.height.max(0) >> 4) as usize
+    }
+
+    // light data also covers one section below and one above the world
+    pub fn light_section_count(&self) -> usize {
+        self.section_count() + 2
+    }
+
+    pub fn min_section(&self) -> i32 {
+        self.min_y >> 4
+    }
+
+    pub fn section_y(&self, index: usize) -> i32 {
+        self.min_section() + index as i32
+    }
+
+    pub fn section_index(&self, section_y: i32) -> Option<usize> {
+        let index = section_y - self.min_section();
+        if index >= 0 && (index as usize) < self.section_count() {
+            Some(index as usize)
+        } else {
+            None
+        }
+    }
+}
+
+impl PlayChunkDataSpec {
+    pub fn has_section(&self, index: usize) -> bool {
+        self.primary_bit_mask.get(index)
+    }
+
+    // the section Y of every section included in data, bottom to top
+    pub fn section_ys(&self, height: &WorldHeight) -> Vec<i32> {
+        (0..height.section_count())
+            .filter(move |index| self.has_section(*index))
+            .map(move |index| height.section_y(index))
+            .collect()
+    }
+}
+
+pub const LIGHT_DATA_LENGTH: usize = 2048;
+// far more than the 256 light sections of the tallest world 1.17 allows
+pub const MAX_LIGHT_SECTIONS: usize = 4096;
+
+#[derive(Clone, PartialEq)]
+pub enum LightSection {
+    Unchanged,
+    Empty,
+    Data(Box<[u8; LIGHT_DATA_LENGTH]>),
+}
+
+impl fmt::Debug for LightSection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LightSection::Unchanged => f.write_str("Unchanged"),
+            LightSection::Empty => f.write_str("Empty"),
+            LightSection::Data(_) => f.write_str("Data"),
+        }
+    }
+}
+
+// index 0 is the section below the world, see WorldHeight::light_section_count
+#[derive(Clone, PartialEq, Default)]
+pub struct LightingData {
+    pub sections: Vec<LightSection>,
+}
+
+impl LightingData {
+    fn deserialize<'a>(update_mask: &BitSet, empty_mask: &BitSet, data: &'a [u8]) -> DeserializeResult<'a, Self> {
+        let Deserialized { value: count, mut data } = VarInt::mc_deserialize(data)?;
+        if count.0 < 0 {
+            return Err(DeserializeErr::NegativeLength(count));
+        }
+
+        // the masks are padded to whole longs, only the sections up to the last set bit matter
+        let n_sections = core::cmp::max(update_mask.highest_set_bit(), empty_mask.highest_set_bit())
+            .map(move |bit| bit + 1)
+            .unwrap_or(0);
+        if n_sections > MAX_LIGHT_SECTIONS {
+            return Err(DeserializeErr::CannotUnderstandValue(alloc::format!(
+                "light mask has bit {} set, but there are at most {} sections", n_sections - 1, MAX_LIGHT_SECTIONS)));
+        }
+
+        let mut sections = Vec::new();
+        let mut n_read = 0;
+        for i in 0..n_sections {
+            let section = match (update_mask.get(i), empty_mask.get(i)) {
+                (true, true) => {
+                    return Err(DeserializeErr::CannotUnderstandValue(alloc::format!("light section {} is both updated and empty", i)));
+                }
+                (true, false) => {
+                    if n_read == count.0 {
+                        return Err(DeserializeErr::CannotUnderstandValue(alloc::format!("light update mask has more bits than the {} arrays sent", count)));
+                    }
+                    n_read += 1;
+
+                    let Deserialized { value: length, data: rest } = VarInt::mc_deserialize(data)?;
+                    if (length.0 as usize) != LIGHT_DATA_LENGTH {
+                        return Err(DeserializeErr::CannotUnderstandValue(alloc::format!("bad data length in light update {}", length)));
+                    }
+
+                    if rest.len() < LIGHT_DATA_LENGTH {
+                        return Err(DeserializeErr::Eof);
+                    }
+
+                    let (section, rest) = rest.split_at(LIGHT_DATA_LENGTH);
+                    let mut to_vec = Box::new([0u8; LIGHT_DATA_LENGTH]);
+                    to_vec.copy_from_slice(section);
+                    data = rest;
+                    LightSection::Data(to_vec)
+                }
+                (false, true) => LightSection::Empty,
+                (false, false) => LightSection::Unchanged,
+            };
+            sections.push(section);
+        }
+
+        if n_read != count.0 {
+            return Err(DeserializeErr::CannotUnderstandValue(alloc::format!("light update has {} arrays but the mask only has {} bits", count, n_read)));
+        }
+
+        Deserialized::ok(Self { sections }, data)
+    }
+
+    pub fn update_mask(&self) -> BitSet {
+        self.compute_mask(move |section| matches!(section, LightSection::Data(_)))
+    }
+
+    pub fn empty_mask(&self) -> BitSet {
+        self.compute_mask(move |section| matches!(section, LightSection::Empty))
+    }
+
+    fn compute_mask<F>(&self, f: F) -> BitSet where F: Fn(&LightSection) -> bool {
+        let mut out = BitSet::default();
+        for (i, section) in self.sections.iter().enumerate() {
+            if f(section) {
+                out.set(i, true);
+            }
+        }
+
+        out
+    }
+
+    fn serialize_data<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        let count = self.sections.iter()
+            .filter(move |section| matches!(section, LightSection::Data(_)))
+            .count();
+        to.serialize_other(&VarInt(count as i32))?;
+        for section in &self.sections {
+            if let LightSection::Data(contents) = section {
+                to.serialize_other(&VarInt(LIGHT_DATA_LENGTH as i32))?;
+                to.serialize_bytes(&contents[..])?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for LightingData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "LightingData(update={:?}, empty={:?}, sections={})",
+            self.update_mask().words,
+            self.empty_mask().words,
+            self.sections.len())
+    }
+}
+
+impl fmt::Display for LightingData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <dyn fmt::Debug>::fmt(self, f)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for LightingData {
+    fn test_gen_random() -> Self {
+        let n_sections = rand::random::<usize>() % 27;
+        let mut sections = Vec::with_capacity(n_sections);
+        for _ in 0..n_sections {
+            sections.push(match rand::random::<u8>() % 3 {
+                0 => LightSection::Unchanged,
+                1 => LightSection::Empty,
+                _ => {
+                    let mut data_arr = Box::new([0u8; LIGHT_DATA_LENGTH]);
+                    for k in data_arr.iter_mut() {
+                        *k = rand::random();
+                    }
+                    LightSection::Data(data_arr)
+                }
+            });
+        }
+
+        while let Some(LightSection::Unchanged) = sections.last() {
+            sections.pop();
+        }
+
+        Self { sections }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct LightingUpdateSpec {
+    pub skylight_data: LightingData,
+    pub blocklight_data: LightingData,
+}
+
+impl Serialize for LightingUpdateSpec {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        self.skylight_data.update_mask().mc_serialize(to)?;
+        self.blocklight_data.update_mask().mc_serialize(to)?;
+        self.skylight_data.empty_mask().mc_serialize(to)?;
+        self.blocklight_data.empty_mask().mc_serialize(to)?;
+        self.skylight_data.serialize_data(to)?;
+        self.blocklight_data.serialize_data(to)
+    }
+}
+
+impl Deserialize for LightingUpdateSpec {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: skylight_update_mask, data } = BitSet::mc_deserialize(data)?;
+        let Deserialized { value: blocklight_update_mask, data } = BitSet::mc_deserialize(data)?;
+        let Deserialized { value: skylight_empty_mask, data } = BitSet::mc_deserialize(data)?;
+        let Deserialized { value: blocklight_empty_mask, data } = BitSet::mc_deserialize(data)?;
+
+        let Deserialized { value: skylight_data, data } = LightingData::deserialize(&skylight_update_mask, &skylight_empty_mask, data)?;
+        let Deserialized { value: blocklight_data, data } = LightingData::deserialize(&blocklight_update_mask, &blocklight_empty_mask, data)?;
+
+        Deserialized::ok(Self {
+            skylight_data,
+            blocklight_data,
+        }, data)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for LightingUpdateSpec {
+    fn test_gen_random() -> Self {
+        Self {
+            skylight_data: LightingData::test_gen_random(),
+            blocklight_data: LightingData::test_gen_random(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+pub mod tests {
+    use super::*;
+    use crate::packet_test_cases;
+    use crate::protocol::{HasPacketId, TestRandom};
+
+    packet_test_cases!(RawPacket756, Packet756, Handshake, HandshakeSpec,
+        test_handshake, bench_write_handshake, bench_read_handshake);
+
+    packet_test_cases!(RawPacket756, Packet756, StatusRequest, StatusRequestSpec,
+        test_status_request, bench_write_status_request, bench_read_status_request);
+
+    packet_test_cases!(RawPacket756, Packet756, StatusPing, StatusPingSpec,
+        test_status_ping, bench_write_status_ping, bench_read_status_ping);
+
+    packet_test_cases!(RawPacket756, Packet756, StatusResponse, StatusResponseSpec,
+        test_status_response, bench_write_status_response, bench_read_status_response);
+
+    packet_test_cases!(RawPacket756, Packet756, StatusPong, StatusPongSpec,
+        test_status_pong, bench_write_status_pong, bench_read_status_pong);
+
+    packet_test_cases!(RawPacket756, Packet756, LoginDisconnect, LoginDisconnectSpec,
+        test_login_disconnect, bench_write_login_disconnect, bench_read_login_disconnect);
+
+    packet_test_cases!(RawPacket756, Packet756, LoginEncryptionRequest, LoginEncryptionRequestSpec,
+        test_login_encryption_request, bench_write_login_encryption_request, bench_read_login_encryption_request);
+
+    packet_test_cases!(RawPacket756, Packet756, LoginSuccess, LoginSuccessSpec,
+        test_login_success, bench_write_login_success, bench_read_login_success);
+
+    packet_test_cases!(RawPacket756, Packet756, LoginSetCompression, LoginSetCompressionSpec,
+        test_login_set_compression, bench_write_login_set_compression, bench_read_login_set_compression);
+
+    packet_test_cases!(RawPacket756, Packet756, LoginPluginRequest, LoginPluginRequestSpec,
+        test_login_plugin_request, bench_write_login_plugin_request, bench_read_login_plugin_request);
+
+    packet_test_cases!(RawPacket756, Packet756, LoginStart, LoginStartSpec,
+        test_login_start, bench_write_login_start, bench_read_login_start);
+
+    packet_test_cases!(RawPacket756, Packet756, LoginEncryptionResponse, LoginEncryptionResponseSpec,
+        test_login_encryption_response, bench_write_login_encryption_response, bench_read_login_encryption_response);
+
+    packet_test_cases!(RawPacket756, Packet756, LoginPluginResponse, LoginPluginResponseSpec,
+        test_login_plugin_response, bench_write_login_plugin_response, bench_read_login_plugin_response);
+
+    packet_test_cases!(RawPacket756, Packet756, PlaySpawnEntity, PlaySpawnEntitySpec,
+        test_play_spawn_entity, bench_write_play_spawn_entity, bench_read_play_spawn_entity);
+
+    packet_test_cases!(RawPacket756, Packet756, PlaySpawnExperienceOrb, PlaySpawnExperienceOrbSpec,
+        test_play_spawn_experience_orb, bench_write_play_spawn_experience_orb, bench_read_play_spawn_experience_orb);
+
+    packet_test_cases!(RawPacket756, Packet756, PlaySpawnLivingEntity, PlaySpawnLivingEntitySpec,
+        test_play_spawn_living_entity, bench_write_play_spawn_living_entity, bench_read_play_spawn_living_entity);
+
+    packet_test_cases!(RawPacket756, Packet756, PlaySpawnPainting, PlaySpawnPaintingSpec,
+        test_play_spawn_painting, bench_write_play_spawn_painting, bench_read_play_spawn_painting);
+
+    packet_test_cases!(RawPacket756, Packet756, PlaySpawnPlayer, PlaySpawnPlayerSpec,
+        test_play_spawn_player, bench_write_play_spawn_player, bench_read_play_spawn_player);
+
+    packet_test_cases!(RawPacket756, Packet756, PlaySculkVibrationSignal, PlaySculkVibrationSignalSpec,
+        test_play_sculk_vibration_signal, bench_write_play_sculk_vibration_signal, bench_read_play_sculk_vibration_signal);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayEntityAnimation, PlayEntityAnimationSpec,
+        test_play_entity_animation, bench_write_play_entity_animation, bench_read_play_entity_animation);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayStatistics, PlayStatisticsSpec,
+        test_play_statistics, bench_write_play_statistics, bench_read_play_statistics);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayAcknowledgePlayerDigging, PlayAcknowledgePlayerDiggingSpec,
+        test_play_acknowledge_player_digging, bench_write_play_acknowledge_player_digging, bench_read_play_acknowledge_player_digging);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayBlockBreakAnimation, PlayBlockBreakAnimationSpec,
+        test_play_block_break_animation, bench_write_play_block_break_animation, bench_read_play_block_break_animation);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayBlockEntityData, PlayBlockEntityDataSpec,
+        test_play_block_entity_data, bench_write_play_block_entity_data, bench_read_play_block_entity_data);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayBlockAction, PlayBlockActionSpec,
+        test_play_block_action, bench_write_play_block_action, bench_read_play_block_action);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayBlockChange, PlayBlockChangeSpec,
+        test_play_block_change, bench_write_play_block_change, bench_read_play_block_change);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayBossBar, PlayBossBarSpec,
+        test_play_boss_bar, bench_write_play_boss_bar, bench_read_play_boss_bar);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayServerDifficulty, PlayServerDifficultySpec,
+        test_play_server_difficulty, bench_write_play_server_difficulty, bench_read_play_server_difficulty);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayServerChatMessage, PlayServerChatMessageSpec,
+        test_play_server_chat_message, bench_write_play_server_chat_message, bench_read_play_server_chat_message);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayClearTitles, PlayClearTitlesSpec,
+        test_play_clear_titles, bench_write_play_clear_titles, bench_read_play_clear_titles);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayTabComplete, PlayTabCompleteSpec,
+        test_play_tab_complete, bench_write_play_tab_complete, bench_read_play_tab_complete);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayDeclareCommands, PlayDeclareCommandsSpec,
+        test_play_declare_commands, bench_write_play_declare_commands, bench_read_play_declare_commands);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayServerCloseWindow, PlayServerCloseWindowSpec,
+        test_play_server_close_window, bench_write_play_server_close_window, bench_read_play_server_close_window);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayWindowItems, PlayWindowItemsSpec,
+        test_play_window_items, bench_write_play_window_items, bench_read_play_window_items);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayWindowProperty, PlayWindowPropertySpec,
+        test_play_window_property, bench_write_play_window_property, bench_read_play_window_property);
+
+    packet_test_cases!(RawPacket756, Packet756, PlaySetSlot, PlaySetSlotSpec,
+        test_play_set_slot, bench_write_play_set_slot, bench_read_play_set_slot);
+
+    packet_test_cases!(RawPacket756, Packet756, PlaySetCooldown, PlaySetCooldownSpec,
+        test_play_set_cooldown, bench_write_play_set_cooldown, bench_read_play_set_cooldown);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayServerPluginMessage, PlayServerPluginMessageSpec,
+        test_play_server_plugin_message, bench_write_play_server_plugin_message, bench_read_play_server_plugin_message);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayNamedSoundEffect, PlayNamedSoundEffectSpec,
+        test_play_named_sound_effect, bench_write_play_named_sound_effect, bench_read_play_named_sound_effect);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayDisconnect, PlayDisconnectSpec,
+        test_play_disconnect, bench_write_play_disconnect, bench_read_play_disconnect);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayEntityStatus, PlayEntityStatusSpec,
+        test_play_entity_status, bench_write_play_entity_status, bench_read_play_entity_status);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayExplosion, PlayExplosionSpec,
+        test_play_explosion, bench_write_play_explosion, bench_read_play_explosion);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayUnloadChunk, PlayUnloadChunkSpec,
+        test_play_unload_chunk, bench_write_play_unload_chunk, bench_read_play_unload_chunk);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayChangeGameState, PlayChangeGameStateSpec,
+        test_play_change_game_state, bench_write_play_change_game_state, bench_read_play_change_game_state);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayOpenHorseWindow, PlayOpenHorseWindowSpec,
+        test_play_open_horse_window, bench_write_play_open_horse_window, bench_read_play_open_horse_window);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayInitializeWorldBorder, PlayInitializeWorldBorderSpec,
+        test_play_initialize_world_border, bench_write_play_initialize_world_border, bench_read_play_initialize_world_border);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayServerKeepAlive, PlayServerKeepAliveSpec,
+        test_play_server_keep_alive, bench_write_play_server_keep_alive, bench_read_play_server_keep_alive);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayChunkData, PlayChunkDataSpec,
+        test_play_chunk_data, bench_write_play_chunk_data, bench_read_play_chunk_data);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayEffect, PlayEffectSpec,
+        test_play_effect, bench_write_play_effect, bench_read_play_effect);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayParticle, PlayParticleSpec,
+        test_play_particle, bench_write_play_particle, bench_read_play_particle);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayUpdateLight, PlayUpdateLightSpec,
+        test_play_update_light, bench_write_play_update_light, bench_read_play_update_light);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayJoinGame, PlayJoinGameSpec,
+        test_play_join_game, bench_write_play_join_game, bench_read_play_join_game);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayMapData, PlayMapDataSpec,
+        test_play_map_data, bench_write_play_map_data, bench_read_play_map_data);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayTradeList, PlayTradeListSpec,
+        test_play_trade_list, bench_write_play_trade_list, bench_read_play_trade_list);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayEntityPosition, PlayEntityPositionSpec,
+        test_play_entity_position, bench_write_play_entity_position, bench_read_play_entity_position);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayEntityPositionAndRotation, PlayEntityPositionAndRotationSpec,
+        test_play_entity_position_and_rotation, bench_write_play_entity_position_and_rotation, bench_read_play_entity_position_and_rotation);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayEntityRotation, PlayEntityRotationSpec,
+        test_play_entity_rotation, bench_write_play_entity_rotation, bench_read_play_entity_rotation);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayServerVehicleMove, PlayEntityVehicleMoveSpec,
+        test_play_server_vehicle_move, bench_write_play_server_vehicle_move, bench_read_play_server_vehicle_move);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayOpenBook, PlayOpenBookSpec,
+        test_play_open_book, bench_write_play_open_book, bench_read_play_open_book);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayOpenWindow, PlayOpenWindowSpec,
+        test_play_open_window, bench_write_play_open_window, bench_read_play_open_window);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayOpenSignEditor, PlayOpenSignEditorSpec,
+        test_play_open_sign_editor, bench_write_play_open_sign_editor, bench_read_play_open_sign_editor);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayPing, PlayPingSpec,
+        test_play_ping, bench_write_play_ping, bench_read_play_ping);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayCraftRecipeResponse, PlayCraftRecipeResponseSpec,
+        test_play_craft_recipe_response, bench_write_play_craft_recipe_response, bench_read_play_craft_recipe_response);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayServerPlayerAbilities, PlayServerPlayerAbilitiesSpec,
+        test_play_server_player_abilities, bench_write_play_server_player_abilities, bench_read_play_server_player_abilities);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayEndCombatEvent, PlayEndCombatEventSpec,
+        test_play_end_combat_event, bench_write_play_end_combat_event, bench_read_play_end_combat_event);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayEnterCombatEvent, PlayEnterCombatEventSpec,
+        test_play_enter_combat_event, bench_write_play_enter_combat_event, bench_read_play_enter_combat_event);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayDeathCombatEvent, PlayDeathCombatEventSpec,
+        test_play_death_combat_event, bench_write_play_death_combat_event, bench_read_play_death_combat_event);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayPlayerInfo, PlayPlayerInfoSpec,
+        test_play_player_info, bench_write_play_player_info, bench_read_play_player_info);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayFacePlayer, PlayFacePlayerSpec,
+        test_play_face_player, bench_write_play_face_player, bench_read_play_face_player);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayServerPlayerPositionAndLook, PlayServerPlayerPositionAndLookSpec,
+        test_play_server_player_position_and_look, bench_write_play_server_player_position_and_look, bench_read_play_server_player_position_and_look);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayUnlockRecipes, PlayUnlockRecipesSpec,
+        test_play_unlock_recipes, bench_write_play_unlock_recipes, bench_read_play_unlock_recipes);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayDestroyEntities, PlayDestroyEntitiesSpec,
+        test_play_destroy_entities, bench_write_play_destroy_entities, bench_read_play_destroy_entities);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayRemoveEntityEffect, PlayRemoveEntityEffectSpec,
+        test_play_remove_entity_effect, bench_write_play_remove_entity_effect, bench_read_play_remove_entity_effect);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayResourcePackSend, PlayResourcePackSendSpec,
+        test_play_resource_pack_send, bench_write_play_resource_pack_send, bench_read_play_resource_pack_send);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayRespawn, PlayRespawnSpec,
+        test_play_respawn, bench_write_play_respawn, bench_read_play_respawn);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayEntityHeadLook, PlayEntityHeadLookSpec,
+        test_play_entity_head_look, bench_write_play_entity_head_look, bench_read_play_entity_head_look);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayMultiBlockChange, PlayMultiBlockChangeSpec,
+        test_play_multi_block_change, bench_write_play_multi_block_change, bench_read_play_multi_block_change);
+
+    packet_test_cases!(RawPacket756, Packet756, PlaySelectAdvancementTab, PlaySelectAdvancementTabSpec,
+        test_play_select_advancement_tab, bench_write_play_select_advancement_tab, bench_read_play_select_advancement_tab);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayActionBar, PlayActionBarSpec,
+        test_play_action_bar, bench_write_play_action_bar, bench_read_play_action_bar);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayWorldBorderCenter, PlayWorldBorderCenterSpec,
+        test_play_world_border_center, bench_write_play_world_border_center, bench_read_play_world_border_center);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayWorldBorderLerpSize, PlayWorldBorderLerpSizeSpec,
+        test_play_world_border_lerp_size, bench_write_play_world_border_lerp_size, bench_read_play_world_border_lerp_size);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayWorldBorderSize, PlayWorldBorderSizeSpec,
+        test_play_world_border_size, bench_write_play_world_border_size, bench_read_play_world_border_size);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayWorldBorderWarningDelay, PlayWorldBorderWarningDelaySpec,
+        test_play_world_border_warning_delay, bench_write_play_world_border_warning_delay, bench_read_play_world_border_warning_delay);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayWorldBorderWarningReach, PlayWorldBorderWarningReachSpec,
+        test_play_world_border_warning_reach, bench_write_play_world_border_warning_reach, bench_read_play_world_border_warning_reach);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayCamera, PlayCameraSpec,
+        test_play_camera, bench_write_play_camera, bench_read_play_camera);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayServerHeldItemChange, PlayServerHeldItemChangeSpec,
+        test_play_server_held_item_change, bench_write_play_server_held_item_change, bench_read_play_server_held_item_change);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayUpdateViewPosition, PlayUpdateViewPositionSpec,
+        test_play_update_view_position, bench_write_play_update_view_position, bench_read_play_update_view_position);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayUpdateViewDistance, PlayUpdateViewDistanceSpec,
+        test_play_update_view_distance, bench_write_play_update_view_distance, bench_read_play_update_view_distance);
+
+    packet_test_cases!(RawPacket756, Packet756, PlaySpawnPosition, PlaySpawnPositionSpec,
+        test_play_spawn_position, bench_write_play_spawn_position, bench_read_play_spawn_position);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayDisplayScoreboard, PlayDisplayScoreboardSpec,
+        test_play_display_scoreboard, bench_write_play_display_scoreboard, bench_read_play_display_scoreboard);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayEntityMetadata, PlayEntityMetadataSpec,
+        test_play_entity_metadata, bench_write_play_entity_metadata, bench_read_play_entity_metadata);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayAttachEntity, PlayAttachEntitySpec,
+        test_play_attach_entity, bench_write_play_attach_entity, bench_read_play_attach_entity);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayEntityVelocity, PlayEntityVelocitySpec,
+        test_play_entity_velocity, bench_write_play_entity_velocity, bench_read_play_entity_velocity);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayEntityEquipment, PlayEntityEquiptmentSpec,
+        test_play_entity_equipment, bench_write_play_entity_equipment, bench_read_play_entity_equipment);
+
+    packet_test_cases!(RawPacket756, Packet756, PlaySetExperience, PlaySetExperienceSpec,
+        test_play_set_experience, bench_write_play_set_experience, bench_read_play_set_experience);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayUpdatehealth, PlayUpdateHealthSpec,
+        test_play_updatehealth, bench_write_play_updatehealth, bench_read_play_updatehealth);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayScoreboardObjective, PlayScoreboardObjectiveSpec,
+        test_play_scoreboard_objective, bench_write_play_scoreboard_objective, bench_read_play_scoreboard_objective);
+
+    packet_test_cases!(RawPacket756, Packet756, PlaySetPassengers, PlaySetPassengersSpec,
+        test_play_set_passengers, bench_write_play_set_passengers, bench_read_play_set_passengers);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayTeams, PlayTeamsSpec,
+        test_play_teams, bench_write_play_teams, bench_read_play_teams);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayUpdateScore, PlayUpdateScoreSpec,
+        test_play_update_score, bench_write_play_update_score, bench_read_play_update_score);
+
+    packet_test_cases!(RawPacket756, Packet756, PlaySetTitleSubtitle, PlaySetTitleSubtitleSpec,
+        test_play_set_title_subtitle, bench_write_play_set_title_subtitle, bench_read_play_set_title_subtitle);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayTimeUpdate, PlayTimeUpdateSpec,
+        test_play_time_update, bench_write_play_time_update, bench_read_play_time_update);
+
+    packet_test_cases!(RawPacket756, Packet756, PlaySetTitleText, PlaySetTitleTextSpec,
+        test_play_set_title_text, bench_write_play_set_title_text, bench_read_play_set_title_text);
+
+    packet_test_cases!(RawPacket756, Packet756, PlaySetTitleTimes, PlaySetTitleTimesSpec,
+        test_play_set_title_times, bench_write_play_set_title_times, bench_read_play_set_title_times);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayEntitySoundEffect, PlayEntitySoundEffectSpec,
+        test_play_entity_sound_effect, bench_write_play_entity_sound_effect, bench_read_play_entity_sound_effect);
+
+    packet_test_cases!(RawPacket756, Packet756, PlaySoundEffect, PlaySoundEffectSpec,
+        test_play_sound_effect, bench_write_play_sound_effect, bench_read_play_sound_effect);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayStopSound, PlayStopSoundSpec,
+        test_play_stop_sound, bench_write_play_stop_sound, bench_read_play_stop_sound);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayerPlayerListHeaderAndFooter, PlayPlayerListHeaderAndFooterSpec,
+        test_player_player_list_header_and_footer, bench_write_player_player_list_header_and_footer, bench_read_player_player_list_header_and_footer);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayNbtQueryResponse, PlayNbtQueryResponseSpec,
+        test_play_nbt_query_response, bench_write_play_nbt_query_response, bench_read_play_nbt_query_response);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayCollectItem, PlayCollectItemSpec,
+        test_play_collect_item, bench_write_play_collect_item, bench_read_play_collect_item);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayEntityTeleport, PlayEntityTeleportSpec,
+        test_play_entity_teleport, bench_write_play_entity_teleport, bench_read_play_entity_teleport);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayAdvancements, PlayAdvancementsSpec,
+        test_play_advancements, bench_write_play_advancements, bench_read_play_advancements);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayEntityProperties, PlayEntityPropertiesSpec,
+        test_play_entity_properties, bench_write_play_entity_properties, bench_read_play_entity_properties);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayEntityEffect, PlayEntityEffectSpec,
+        test_play_entity_effect, bench_write_play_entity_effect, bench_read_play_entity_effect);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayDeclareRecipes, PlayDeclareRecipesSpec,
+        test_play_declare_recipes, bench_write_play_declare_recipes, bench_read_play_declare_recipes);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayTags, PlayTagsSpec,
+        test_play_tags, bench_write_play_tags, bench_read_play_tags);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayTeleportConfirm, PlayTeleportConfirmSpec,
+        test_play_teleport_confirm, bench_write_play_teleport_confirm, bench_read_play_teleport_confirm);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayQueryBlockNbt, PlayQueryBlockNbtSpec,
+        test_play_query_block_nbt, bench_write_play_query_block_nbt, bench_read_play_query_block_nbt);
+
+    packet_test_cases!(RawPacket756, Packet756, PlaySetDifficulty, PlaySetDifficultySpec,
+        test_play_set_difficulty, bench_write_play_set_difficulty, bench_read_play_set_difficulty);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayClientChatMessage, PlayClientChatMessageSpec,
+        test_play_client_chat_message, bench_write_play_client_chat_message, bench_read_play_client_chat_message);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayClientStatus, PlayClientStatusSpec,
+        test_play_client_status, bench_write_play_client_status, bench_read_play_client_status);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayClientSettings, PlayClientSettingsSpec,
+        test_play_client_settings, bench_write_play_client_settings, bench_read_play_client_settings);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayClientTabComplete, PlayClientTabCompleteSpec,
+        test_play_client_tab_complete, bench_write_play_client_tab_complete, bench_read_play_client_tab_complete);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayClickWindowButton, PlayClickWindowButtonSpec,
+        test_play_click_window_button, bench_write_play_click_window_button, bench_read_play_click_window_button);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayClickWindow, PlayClickWindowSpec,
+        test_play_click_window, bench_write_play_click_window, bench_read_play_click_window);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayClientCloseWindow, PlayClientCloseWindowSpec,
+        test_play_client_close_window, bench_write_play_client_close_window, bench_read_play_client_close_window);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayClientPluginMessage, PlayClientPluginMessageSpec,
+        test_play_client_plugin_message, bench_write_play_client_plugin_message, bench_read_play_client_plugin_message);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayEditBook, PlayEditBookSpec,
+        test_play_edit_book, bench_write_play_edit_book, bench_read_play_edit_book);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayQueryEntityNbt, PlayQueryEntityNbtSpec,
+        test_play_query_entity_nbt, bench_write_play_query_entity_nbt, bench_read_play_query_entity_nbt);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayInteractEntity, PlayInteractEntitySpec,
+        test_play_interact_entity, bench_write_play_interact_entity, bench_read_play_interact_entity);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayGenerateStructure, PlayGenerateStructureSpec,
+        test_play_generate_structure, bench_write_play_generate_structure, bench_read_play_generate_structure);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayClientKeepAlive, PlayClientKeepAliveSpec,
+        test_play_client_keep_alive, bench_write_play_client_keep_alive, bench_read_play_client_keep_alive);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayLockDifficulty, PlayLockDifficultySpec,
+        test_play_lock_difficulty, bench_write_play_lock_difficulty, bench_read_play_lock_difficulty);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayPlayerPosition, PlayPlayerPositionSpec,
+        test_play_player_position, bench_write_play_player_position, bench_read_play_player_position);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayClientPlayerPositionAndRotation, PlayClientPlayerPositionAndRotationSpec,
+        test_play_client_player_position_and_rotation, bench_write_play_client_player_position_and_rotation, bench_read_play_client_player_position_and_rotation);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayPlayerRotation, PlayPlayerRotationSpec,
+        test_play_player_rotation, bench_write_play_player_rotation, bench_read_play_player_rotation);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayPlayerMovement, PlayPlayerMovementSpec,
+        test_play_player_movement, bench_write_play_player_movement, bench_read_play_player_movement);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayClientVehicleMove, PlayClientVehicleMoveSpec,
+        test_play_client_vehicle_move, bench_write_play_client_vehicle_move, bench_read_play_client_vehicle_move);
+
+    packet_test_cases!(RawPacket756, Packet756, PlaySteerBoat, PlaySteerBoatSpec,
+        test_play_steer_boat, bench_write_play_steer_boat, bench_read_play_steer_boat);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayPickItem, PlayPickItemSpec,
+        test_play_pick_item, bench_write_play_pick_item, bench_read_play_pick_item);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayCraftRecipeRequest, PlayCraftRecipeRequestSpec,
+        test_play_craft_recipe_request, bench_write_play_craft_recipe_request, bench_read_play_craft_recipe_request);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayClientPlayerAbilities, PlayClientPlayerAbilitiesSpec,
+        test_play_client_player_abilities, bench_write_play_client_player_abilities, bench_read_play_client_player_abilities);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayPlayerDigging, PlayPlayerDiggingSpec,
+        test_play_player_digging, bench_write_play_player_digging, bench_read_play_player_digging);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayEntityAction, PlayEntityActionSpec,
+        test_play_entity_action, bench_write_play_entity_action, bench_read_play_entity_action);
+
+    packet_test_cases!(RawPacket756, Packet756, PlaySteerVehicle, PlaySteerVehicleSpec,
+        test_play_steer_vehicle, bench_write_play_steer_vehicle, bench_read_play_steer_vehicle);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayPong, PlayPongSpec,
+        test_play_pong, bench_write_play_pong, bench_read_play_pong);
+
+    packet_test_cases!(RawPacket756, Packet756, PlaySetRecipeBookState, PlaySetRecipeBookStateSpec,
+        test_play_set_recipe_book_state, bench_write_play_set_recipe_book_state, bench_read_play_set_recipe_book_state);
+
+    packet_test_cases!(RawPacket756, Packet756, PlaySetDisplayedRecipe, PlaySetDisplayedRecipeSpec,
+        test_play_set_displayed_recipe, bench_write_play_set_displayed_recipe, bench_read_play_set_displayed_recipe);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayNameItem, PlayNameItemSpec,
+        test_play_name_item, bench_write_play_name_item, bench_read_play_name_item);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayResourcePackStatus, PlayResourcePackStatusSpec,
+        test_play_resource_pack_status, bench_write_play_resource_pack_status, bench_read_play_resource_pack_status);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayAdvancementTab, PlayAdvancementTabSpec,
+        test_play_advancement_tab, bench_write_play_advancement_tab, bench_read_play_advancement_tab);
+
+    packet_test_cases!(RawPacket756, Packet756, PlaySelectTrade, PlaySelectTradeSpec,
+        test_play_select_trade, bench_write_play_select_trade, bench_read_play_select_trade);
+
+    packet_test_cases!(RawPacket756, Packet756, PlaySetBeaconEffect, PlaySetBeaconEffectSpec,
+        test_play_set_beacon_effect, bench_write_play_set_beacon_effect, bench_read_play_set_beacon_effect);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayClientHeldItemChange, PlayClientHeldItemChangeSpec,
+        test_play_client_held_item_change, bench_write_play_client_held_item_change, bench_read_play_client_held_item_change);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayUpdateCommandBlock, PlayUpdateCommandBlockSpec,
+        test_play_update_command_block, bench_write_play_update_command_block, bench_read_play_update_command_block);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayUpdateCommandBlockMinecart, PlayUpdateCommandBlockMinecartSpec,
+        test_play_update_command_block_minecart, bench_write_play_update_command_block_minecart, bench_read_play_update_command_block_minecart);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayUpdateJigsawBlock, PlayUpdateJigsawBlockSpec,
+        test_play_update_jigsaw_block, bench_write_play_update_jigsaw_block, bench_read_play_update_jigsaw_block);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayCreativeInventoryAction, PlayCreativeInventoryActionSpec,
+        test_play_creative_inventory_action, bench_write_play_creative_inventory_action, bench_read_play_creative_inventory_action);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayUpdateStructureBlock, PlayUpdateStructureBlockSpec,
+        test_play_update_structure_block, bench_write_play_update_structure_block, bench_read_play_update_structure_block);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayUpdateSign, PlayUpdateSignSpec,
+        test_play_update_sign, bench_write_play_update_sign, bench_read_play_update_sign);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayClientAnimation, PlayClientAnimationSpec,
+        test_play_client_animation, bench_write_play_client_animation, bench_read_play_client_animation);
+
+    packet_test_cases!(RawPacket756, Packet756, PlaySpectate, PlaySpectateSpec,
+        test_play_spectate, bench_write_play_spectate, bench_read_play_spectate);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayBlockPlacement, PlayBlockPlacementSpec,
+        test_play_block_placement, bench_write_play_block_placement, bench_read_play_block_placement);
+
+    packet_test_cases!(RawPacket756, Packet756, PlayUseItem, PlayUseItemSpec,
+        test_play_use_item, bench_write_play_use_item, bench_read_play_use_item);
+
+    fn light_section(fill: u8) -> LightSection {
+        LightSection::Data(Box::new([fill; LIGHT_DATA_LENGTH]))
+    }
+
+    #[test]
+    fn test_light_update_past_eighteen_sections() {
+        let mut skylight_sections = vec![LightSection::Unchanged; 26];
+        skylight_sections[0] = light_section(1);
+        skylight_sections[20] = LightSection::Empty;
+        skylight_sections[25] = light_section(2);
+        let update = LightingUpdateSpec {
+            skylight_data: LightingData { sections: skylight_sections },
+            blocklight_data: LightingData::default(),
+        };
+        assert_eq!(update.skylight_data.update_mask().words, vec![1 | (1 << 25)]);
+        assert_eq!(update.skylight_data.empty_mask().words, vec![1 << 20]);
+
+        let mut serializer = BytesSerializer::default();
+        update.mc_serialize(&mut serializer).expect("serializes");
+        let bytes = serializer.into_bytes();
+        let Deserialized { value, data } = LightingUpdateSpec::mc_deserialize(&bytes).expect("deserializes");
+        assert!(data.is_empty());
+        assert_eq!(value, update);
+    }
+
+    #[test]
+    fn test_light_update_rejects_overlapping_masks() {
+        let mut serializer = BytesSerializer::default();
+        for mask in &[1i64, 0, 1, 0] {
+            BitSet::from_words(vec![*mask]).mc_serialize(&mut serializer).unwrap();
+        }
+        VarInt(1).mc_serialize(&mut serializer).unwrap();
+        VarInt(LIGHT_DATA_LENGTH as i32).mc_serialize(&mut serializer).unwrap();
+        serializer.serialize_bytes(&[0u8; LIGHT_DATA_LENGTH]).unwrap();
+        VarInt(0).mc_serialize(&mut serializer).unwrap();
+
+        let bytes = serializer.into_bytes();
+        assert!(matches!(
+            LightingUpdateSpec::mc_deserialize(&bytes),
+            Err(DeserializeErr::CannotUnderstandValue(_))));
+    }
+
+    #[test]
+    fn test_light_update_rejects_oversized_masks() {
+        let mut serializer = BytesSerializer::default();
+        let mut empty_mask = BitSet::default();
+        empty_mask.set(MAX_LIGHT_SECTIONS, true);
+        for mask in &[BitSet::default(), BitSet::default(), empty_mask, BitSet::default()] {
+            mask.mc_serialize(&mut serializer).unwrap();
+        }
+        VarInt(0).mc_serialize(&mut serializer).unwrap();
+        VarInt(0).mc_serialize(&mut serializer).unwrap();
+
+        let bytes = serializer.into_bytes();
+        assert!(matches!(
+            LightingUpdateSpec::mc_deserialize(&bytes),
+            Err(DeserializeErr::CannotUnderstandValue(msg)) if msg.contains("at most 4096 sections")));
+
+        // padding words of zeros don't add any sections
+        let mut serializer = BytesSerializer::default();
+        let mut empty_mask = BitSet::from_words(vec![0; 1000]);
+        empty_mask.set(3, true);
+        for mask in &[BitSet::default(), BitSet::default(), empty_mask, BitSet::default()] {
+            mask.mc_serialize(&mut serializer).unwrap();
+        }
+        VarInt(0).mc_serialize(&mut serializer).unwrap();
+        VarInt(0).mc_serialize(&mut serializer).unwrap();
+
+        let value = LightingUpdateSpec::mc_deserialize(&serializer.into_bytes()).expect("deserializes").value;
+        assert_eq!(value.skylight_data.sections.len(), 4);
+        assert_eq!(value.skylight_data.sections[3], LightSection::Empty);
+    }
+
+    #[test]
+    fn test_chunk_data_has_no_full_chunk_flag() {
+        let mut primary_bit_mask = BitSet::default();
+        primary_bit_mask.set(0, true);
+        primary_bit_mask.set(23, true);
+        let chunk = PlayChunkDataSpec {
+            position: ChunkPosition { x: 1, z: -1 },
+            primary_bit_mask,
+            heightmaps: NamedNbtTag::test_gen_random(),
+            biomes: vec![VarInt(1); 1536].into(),
+            data: vec![].into(),
+            block_entities: vec![].into(),
+        };
+
+        let mut serializer = BytesSerializer::default();
+        chunk.mc_serialize(&mut serializer).expect("serializes");
+        let bytes = serializer.into_bytes();
+        // position is two ints, then the bit set starts right away with its length
+        assert_eq!(&bytes[8..10], &[0x01, 0x00]);
+        assert_eq!(PlayChunkDataSpec::mc_deserialize(&bytes).expect("deserializes").value, chunk);
+
+        let height = WorldHeight { min_y: -64, height: 384 };
+        assert_eq!(chunk.section_ys(&height), vec![-4, 19]);
+    }
+
+    #[test]
+    fn test_world_height() {
+        let dimension = NamedNbtTag {
+            root: nbt::Tag::Compound(vec![
+                nbt::Tag::Int(-64).with_name("min_y"),
+                nbt::Tag::Int(384).with_name("height"),
+                nbt::Tag::Byte(1).with_name("natural"),
+            ]).with_name(""),
+        };
+        let height = WorldHeight::from_dimension(&dimension).expect("has height");
+        assert_eq!(height, WorldHeight { min_y: -64, height: 384 });
+        assert_eq!(height.section_count(), 24);
+        assert_eq!(height.light_section_count(), 26);
+        assert_eq!(height.min_section(), -4);
+        assert_eq!(height.section_y(0), -4);
+        assert_eq!(height.section_index(19), Some(23));
+        assert_eq!(height.section_index(20), None);
+        assert_eq!(height.section_index(-5), None);
+        assert_eq!(WorldHeight::OVERWORLD.section_count(), 16);
+
+        let no_height = NamedNbtTag { root: nbt::Tag::Compound(vec![]).with_name("") };
+        assert_eq!(WorldHeight::from_dimension(&no_height), None);
+    }
+
+    #[test]
+    fn test_ping_pong_ids() {
+        use crate::protocol::{PacketDirection::{ClientBound, ServerBound}, State::Play};
+
+        let ping = Packet756::PlayPing(PlayPingSpec { id: 7 });
+        assert_eq!(ping.id(), (0x30, Play, ClientBound).into());
+        let pong = Packet756::PlayPong(PlayPongSpec { id: 7 });
+        assert_eq!(pong.id(), (0x1D, Play, ServerBound).into());
+    }
+}
@@ -0,0 +1,2075 @@
+use crate::{types::*, uuid::*, *};
+use alloc::{string::String, vec::Vec, borrow::ToOwned};
+
+#[cfg(all(test, feature = "std"))]
+use crate::protocol::TestRandom;
+
+define_protocol!(47, Packet47, RawPacket47, RawPacket47Body, Packet47Kind => {
+    // handshaking
+    Handshake, 0x00, Handshaking, ServerBound => HandshakeSpec {
+        version: VarInt,
+        server_address: String,
+        server_port: u16,
+        next_state: HandshakeNextState
+    },
+
+    // status
+    StatusRequest, 0x00, Status, ServerBound => StatusRequestSpec {},
+    StatusPing, 0x01, Status, ServerBound => StatusPingSpec {
+        payload: i64
+    },
+    StatusResponse, 0x00, Status, ClientBound => StatusResponseSpec {
+        response: super::status::StatusSpec
+    },
+    StatusPong, 0x01, Status, ClientBound => StatusPongSpec {
+        payload: i64
+    },
+
+    // login
+    LoginDisconnect, 0x00, Login, ClientBound => LoginDisconnectSpec {
+        message: Chat
+    },
+    LoginEncryptionRequest, 0x01, Login, ClientBound => LoginEncryptionRequestSpec {
+        server_id: String,
+        public_key: CountedArray<u8, VarInt>,
+        verify_token: CountedArray<u8, VarInt>
+    },
+    LoginSuccess, 0x02, Login, ClientBound => LoginSuccessSpec {
+        uuid_string: String,
+        username: String
+    },
+    LoginSetCompression, 0x03, Login, ClientBound => LoginSetCompressionSpec {
+        threshold: VarInt
+    },
+    LoginStart, 0x00, Login, ServerBound => LoginStartSpec {
+        name: String
+    },
+    LoginEncryptionResponse, 0x01, Login, ServerBound => LoginEncryptionResponseSpec {
+        shared_secret: CountedArray<u8, VarInt>,
+        verify_token: CountedArray<u8, VarInt>
+    },
+
+    // play
+    // client bound
+    PlayServerKeepAlive, 0x00, Play, ClientBound => PlayServerKeepAliveSpec {
+        id: VarInt
+    },
+    PlayJoinGame, 0x01, Play, ClientBound => PlayJoinGameSpec {
+        entity_id: i32,
+        gamemode: JoinGameMode,
+        dimension: Dimension,
+        difficulty: Difficulty,
+        max_players: u8,
+        level_type: String,
+        reduced_debug_info: bool
+    },
+    PlayServerChatMessage, 0x02, Play, ClientBound => PlayServerChatMessageSpec {
+        message: Chat,
+        position: ChatPosition
+    },
+    PlayTimeUpdate, 0x03, Play, ClientBound => PlayTimeUpdateSpec {
+        world_age: i64,
+        time_of_day: i64
+    },
+    PlayEntityEquipment, 0x04, Play, ClientBound => PlayEntityEquipmentSpec {
+        entity_id: VarInt,
+        slot: EquipmentSlot,
+        item: Slot
+    },
+    PlaySpawnPosition, 0x05, Play, ClientBound => PlaySpawnPositionSpec {
+        location: LegacyIntPosition
+    },
+    PlayUpdateHealth, 0x06, Play, ClientBound => PlayUpdateHealthSpec {
+        health: f32,
+        food: VarInt,
+        saturation: f32
+    },
+    PlayRespawn, 0x07, Play, ClientBound => PlayRespawnSpec {
+        dimension: RespawnDimension,
+        difficulty: Difficulty,
+        gamemode: GameMode,
+        level_type: String
+    },
+    PlayServerPlayerPositionAndLook, 0x08, Play, ClientBound => PlayServerPlayerPositionAndLookSpec {
+        location: EntityLocation<f64, f32>,
+        flags: PositionAndLookFlags
+    },
+    PlayServerHeldItemChange, 0x09, Play, ClientBound => PlayServerHeldItemChangeSpec {
+        slot: i8
+    },
+    PlayUseBed, 0x0A, Play, ClientBound => PlayUseBedSpec {
+        entity_id: VarInt,
+        location: LegacyIntPosition
+    },
+    PlayEntityAnimation, 0x0B, Play, ClientBound => PlayEntityAnimationSpec {
+        entity_id: VarInt,
+        animation: EntityAnimationKind
+    },
+    PlaySpawnPlayer, 0x0C, Play, ClientBound => PlaySpawnPlayerSpec {
+        entity_id: VarInt,
+        uuid: UUID4,
+        location: EntityLocation<FixedInt, Angle>,
+        current_item: i16,
+        metadata: EntityMetadata
+    },
+    PlayCollectItem, 0x0D, Play, ClientBound => PlayCollectItemSpec {
+        collected_entity_id: VarInt,
+        collector_entity_id: VarInt
+    },
+    PlaySpawnEntity, 0x0E, Play, ClientBound => PlaySpawnEntitySpec {
+        entity_id: VarInt,
+        entity_type: u8,
+        position: Vec3<FixedInt>,
+        pitch: Angle,
+        yaw: Angle,
+        data: ObjectData
+    },
+    PlaySpawnLivingEntity, 0x0F, Play, ClientBound => PlaySpawnLivingEntitySpec {
+        entity_id: VarInt,
+        entity_type: u8,
+        location: EntityLocation<FixedInt, Angle>,
+        head_pitch: Angle,
+        velocity: Vec3<i16>,
+        metadata: EntityMetadata
+    },
+    PlaySpawnPainting, 0x10, Play, ClientBound => PlaySpawnPaintingSpec {
+        entity_id: VarInt,
+        title: String,
+        location: LegacyIntPosition,
+        direction: CardinalDirection
+    },
+    PlaySpawnExperienceOrb, 0x11, Play, ClientBound => PlaySpawnExperienceOrbSpec {
+        entity_id: VarInt,
+        position: Vec3<FixedInt>,
+        count: i16
+    },
+    PlayEntityVelocity, 0x12, Play, ClientBound => PlayEntityVelocitySpec {
+        entity_id: VarInt,
+        velocity: Vec3<i16>
+    },
+    PlayDestroyEntities, 0x13, Play, ClientBound => PlayDestroyEntitiesSpec {
+        entity_ids: CountedArray<VarInt, VarInt>
+    },
+    PlayEntityMovement, 0x14, Play, ClientBound => PlayEntityMovementSpec {
+        entity_id: VarInt
+    },
+    PlayEntityPosition, 0x15, Play, ClientBound => PlayEntityPositionSpec {
+        entity_id: VarInt,
+        delta: Vec3<i8>,
+        on_ground: bool
+    },
+    PlayEntityRotation, 0x16, Play, ClientBound => PlayEntityRotationSpec {
+        entity_id: VarInt,
+        rotation: EntityRotation<Angle>,
+        on_ground: bool
+    },
+    PlayEntityPositionAndRotation, 0x17, Play, ClientBound => PlayEntityPositionAndRotationSpec {
+        entity_id: VarInt,
+        delta: EntityLocation<i8, Angle>,
+        on_ground: bool
+    },
+    PlayEntityTeleport, 0x18, Play, ClientBound => PlayEntityTeleportSpec {
+        entity_id: VarInt,
+        location: EntityLocation<FixedInt, Angle>,
+        on_ground: bool
+    },
+    PlayEntityHeadLook, 0x19, Play, ClientBound => PlayEntityHeadLookSpec {
+        entity_id: VarInt,
+        head_yaw: Angle
+    },
+    PlayEntityStatus, 0x1A, Play, ClientBound => PlayEntityStatusSpec {
+        entity_id: i32,
+        raw_status: u8
+    },
+    PlayAttachEntity, 0x1B, Play, ClientBound => PlayAttachEntitySpec {
+        entity_id: i32,
+        vehicle_id: i32,
+        leash: bool
+    },
+    PlayEntityMetadata, 0x1C, Play, ClientBound => PlayEntityMetadataSpec {
+        entity_id: VarInt,
+        metadata: EntityMetadata
+    },
+    PlayEntityEffect, 0x1D, Play, ClientBound => PlayEntityEffectSpec {
+        entity_id: VarInt,
+        effect_id: EntityEffectKind,
+        amplifier: i8,
+        duration_ticks: VarInt,
+        hide_particles: bool
+    },
+    PlayRemoveEntityEffect, 0x1E, Play, ClientBound => PlayRemoveEntityEffectSpec {
+        entity_id: VarInt,
+        effect: EntityEffectKind
+    },
+    PlaySetExperience, 0x1F, Play, ClientBound => PlaySetExperienceSpec {
+        experience_bar: f32,
+        level: VarInt,
+        total_experience: VarInt
+    },
+    PlayEntityProperties, 0x20, Play, ClientBound => PlayEntityPropertiesSpec {
+        entity_id: VarInt,
+        properties: CountedArray<EntityPropertySpec, i32>
+    },
+    PlayChunkData, 0x21, Play, ClientBound => PlayChunkDataSpec {
+        position: ChunkPosition<i32>,
+        full_chunk: bool,
+        primary_bit_mask: u16,
+        data: CountedArray<u8, VarInt>
+    },
+    PlayMultiBlockChange, 0x22, Play, ClientBound => PlayMultiBlockChangeSpec {
+        chunk: ChunkPosition<i32>,
+        changes: CountedArray<MultiBlockChangeRecord, VarInt>
+    },
+    PlayBlockChange, 0x23, Play, ClientBound => PlayBlockChangeSpec {
+        location: LegacyIntPosition,
+        block_id: VarInt
+    },
+    PlayBlockAction, 0x24, Play, ClientBound => PlayBlockActionSpec {
+        location: LegacyIntPosition,
+        action_id: u8,
+        action_payload: u8,
+        block_type: VarInt
+    },
+    PlayBlockBreakAnimation, 0x25, Play, ClientBound => PlayBlockBreakAnimationSpec {
+        entity_id: VarInt,
+        location: LegacyIntPosition,
+        destroy_stage: i8
+    },
+    PlayMapChunkBulk, 0x26, Play, ClientBound => PlayMapChunkBulkSpec {
+        sky_light_sent: bool,
+        chunks: CountedArray<ChunkMetaSpec, VarInt>,
+        data: RemainingBytes
+    },
+    PlayExplosion, 0x27, Play, ClientBound => PlayExplosionSpec {
+        position: Vec3<f32>,
+        strength: f32,
+        records: CountedArray<Vec3<i8>, i32>,
+        player_motion: Vec3<f32>
+    },
+    PlayEffect, 0x28, Play, ClientBound => PlayEffectSpec {
+        effect_id: i32,
+        location: LegacyIntPosition,
+        data: i32,
+        disable_relative_volume: bool
+    },
+    PlayNamedSoundEffect, 0x29, Play, ClientBound => PlayNamedSoundEffectSpec {
+        sound_name: String,
+        position: Vec3<FixedInt>,
+        volume: f32,
+        pitch: u8
+    },
+    PlayParticle, 0x2A, Play, ClientBound => PlayParticleSpec {
+        particle: ParticleSpec
+    },
+    PlayChangeGameState, 0x2B, Play, ClientBound => PlayChangeGameStateSpec {
+        reason: GameChangeReason
+    },
+    PlaySpawnWeatherEntity, 0x2C, Play, ClientBound => PlaySpawnWeatherEntitySpec {
+        entity_id: VarInt,
+        entity_type: u8,
+        position: Vec3<FixedInt>
+    },
+    PlayOpenWindow, 0x2D, Play, ClientBound => PlayOpenWindowSpec {
+        window_id: u8,
+        window: WindowSpec
+    },
+    PlayServerCloseWindow, 0x2E, Play, ClientBound => PlayServerCloseWindowSpec {
+        window_id: u8
+    },
+    PlaySetSlot, 0x2F, Play, ClientBound => PlaySetSlotSpec {
+        window_id: i8,
+        slot: i16,
+        slot_data: Slot
+    },
+    PlayWindowItems, 0x30, Play, ClientBound => PlayWindowItemsSpec {
+        window_id: u8,
+        slots: CountedArray<Slot, i16>
+    },
+    PlayWindowProperty, 0x31, Play, ClientBound => PlayWindowPropertySpec {
+        window_id: u8,
+        property: i16,
+        value: i16
+    },
+    PlayServerWindowConfirmation, 0x32, Play, ClientBound => PlayServerWindowConfirmationSpec {
+        window_id: i8,
+        action_number: i16,
+        accepted: bool
+    },
+    PlayServerUpdateSign, 0x33, Play, ClientBound => PlayServerUpdateSignSpec {
+        location: LegacyIntPosition,
+        line1: Chat,
+        line2: Chat,
+        line3: Chat,
+        line4: Chat
+    },
+    PlayMapData, 0x34, Play, ClientBound => PlayMapDataSpec {
+        map_id: VarInt,
+        scale: i8,
+        icons: CountedArray<MapIconSpec, VarInt>,
+        columns: MapColumns
+    },
+    PlayBlockEntityData, 0x35, Play, ClientBound => PlayBlockEntityDataSpec {
+        location: LegacyIntPosition,
+        action: BlockEntityDataAction,
+        nbt_data: NamedNbtTag
+    },
+    PlayOpenSignEditor, 0x36, Play, ClientBound => PlayOpenSignEditorSpec {
+        location: LegacyIntPosition
+    },
+    PlayStatistics, 0x37, Play, ClientBound => PlayStatisticsSpec {
+        entries: CountedArray<Statistic, VarInt>
+    },
+    PlayPlayerInfo, 0x38, Play, ClientBound => PlayPlayerInfoSpec {
+        actions: PlayerInfoActionList
+    },
+    PlayServerPlayerAbilities, 0x39, Play, ClientBound => PlayServerPlayerAbilitiesSpec {
+        flags: PlayerAbilityFlags,
+        flying_speed: f32,
+        field_of_view_modifier: f32
+    },
+    PlayTabComplete, 0x3A, Play, ClientBound => PlayTabCompleteSpec {
+        matches: CountedArray<String, VarInt>
+    },
+    PlayScoreboardObjective, 0x3B, Play, ClientBound => PlayScoreboardObjectiveSpec {
+        objective_name: String,
+        action: ScoreboardObjectiveAction
+    },
+    PlayUpdateScore, 0x3C, Play, ClientBound => PlayUpdateScoreSpec {
+        entity_name: String,
+        update: UpdateScoreSpec
+    },
+    PlayDisplayScoreboard, 0x3D, Play, ClientBound => PlayDisplayScoreboardSpec {
+        position: ScoreboardPosition,
+        score_name: String
+    },
+    PlayTeams, 0x3E, Play, ClientBound => PlayTeamsSpec {
+        team_name: String,
+        action: TeamAction
+    },
+    PlayServerPluginMessage, 0x3F, Play, ClientBound => PlayServerPluginMessageSpec {
+        channel: String,
+        data: RemainingBytes
+    },
+    PlayDisconnect, 0x40, Play, ClientBound => PlayDisconnectSpec {
+        reason: Chat
+    },
+    PlayServerDifficulty, 0x41, Play, ClientBound => PlayServerDifficultySpec {
+        difficulty: Difficulty
+    },
+    PlayCombatEvent, 0x42, Play, ClientBound => PlayCombatEventSpec {
+        event: CombatEvent
+    },
+    PlayCamera, 0x43, Play, ClientBound => PlayCameraSpec {
+        camera_id: VarInt
+    },
+    PlayWorldBorder, 0x44, Play, ClientBound => PlayWorldBorderSpec {
+        action: WorldBorderAction
+    },
+    PlayTitle, 0x45, Play, ClientBound => PlayTitleSpec {
+        action: TitleActionSpec
+    },
+    PlaySetCompression, 0x46, Play, ClientBound => PlaySetCompressionSpec {
+        threshold: VarInt
+    },
+    PlayPlayerListHeaderAndFooter, 0x47, Play, ClientBound => PlayPlayerListHeaderAndFooterSpec {
+        header: Chat,
+        footer: Chat
+    },
+    PlayResourcePackSend, 0x48, Play, ClientBound => PlayResourcePackSendSpec {
+        url: String,
+        hash: String
+    },
+    PlayUpdateEntityNbt, 0x49, Play, ClientBound => PlayUpdateEntityNbtSpec {
+        entity_id: VarInt,
+        tag: NamedNbtTag
+    },
+
+    // play server bound
+    PlayClientKeepAlive, 0x00, Play, ServerBound => PlayClientKeepAliveSpec {
+        id: VarInt
+    },
+    PlayClientChatMessage, 0x01, Play, ServerBound => PlayClientChatMessageSpec {
+        message: String
+    },
+    PlayInteractEntity, 0x02, Play, ServerBound => PlayInteractEntitySpec {
+        entity_id: VarInt,
+        kind: InteractKind
+    },
+    PlayPlayerMovement, 0x03, Play, ServerBound => PlayPlayerMovementSpec {
+        on_ground: bool
+    },
+    PlayPlayerPosition, 0x04, Play, ServerBound => PlayPlayerPositionSpec {
+        feet_position: Vec3<f64>,
+        on_ground: bool
+    },
+    PlayPlayerRotation, 0x05, Play, ServerBound => PlayPlayerRotationSpec {
+        rotation: EntityRotation<f32>,
+        on_ground: bool
+    },
+    PlayClientPlayerPositionAndRotation, 0x06, Play, ServerBound => PlayClientPlayerPositionAndRotationSpec {
+        feet_location: EntityLocation<f64, f32>,
+        on_ground: bool
+    },
+    PlayPlayerDigging, 0x07, Play, ServerBound => PlayPlayerDiggingSpec {
+        status: PlayerDiggingStatus,
+        location: LegacyIntPosition,
+        face: DiggingFace
+    },
+    PlayBlockPlacement, 0x08, Play, ServerBound => PlayBlockPlacementSpec {
+        location: LegacyIntPosition,
+        face: i8,
+        held_item: Slot,
+        cursor_position: Vec3<u8>
+    },
+    PlayClientHeldItemChange, 0x09, Play, ServerBound => PlayClientHeldItemChangeSpec {
+        slot: i16
+    },
+    PlayClientAnimation, 0x0A, Play, ServerBound => PlayClientAnimationSpec {},
+    PlayEntityAction, 0x0B, Play, ServerBound => PlayEntityActionSpec {
+        entity_id: VarInt,
+        action: EntityActionKind,
+        jump_boost: VarInt
+    },
+    PlaySteerVehicle, 0x0C, Play, ServerBound => PlaySteerVehicleSpec {
+        sideways: f32,
+        forward: f32,
+        flags: SteerVehicleFlags
+    },
+    PlayClientCloseWindow, 0x0D, Play, ServerBound => PlayClientCloseWindowSpec {
+        window_id: u8
+    },
+    PlayClickWindow, 0x0E, Play, ServerBound => PlayClickWindowSpec {
+        window_id: u8,
+        slot: i16,
+        button: i8,
+        action_number: i16,
+        mode: InventoryOperationMode,
+        clicked_item: Slot
+    },
+    PlayClientWindowConfirmation, 0x0F, Play, ServerBound => PlayClientWindowConfirmationSpec {
+        window_id: i8,
+        action_num: i16,
+        accepted: bool
+    },
+    PlayCreativeInventoryAction, 0x10, Play, ServerBound => PlayCreativeInventoryActionSpec {
+        slot: i16,
+        clicked_item: Slot
+    },
+    PlayEnchantItem, 0x11, Play, ServerBound => PlayEnchantItemSpec {
+        window_id: i8,
+        enchantment: i8
+    },
+    PlayClientUpdateSign, 0x12, Play, ServerBound => PlayClientUpdateSignSpec {
+        location: LegacyIntPosition,
+        line1: Chat,
+        line2: Chat,
+        line3: Chat,
+        line4: Chat
+    },
+    PlayClientPlayerAbilities, 0x13, Play, ServerBound => PlayClientPlayerAbilitiesSpec {
+        flags: ClientPlayerAbilities,
+        flying_speed: f32,
+        walking_speed: f32
+    },
+    PlayClientTabComplete, 0x14, Play, ServerBound => PlayClientTabCompleteSpec {
+        text: String,
+        looked_at_block: Option<LegacyIntPosition>
+    },
+    PlayClientSettings, 0x15, Play, ServerBound => PlayClientSettingsSpec {
+        locale: String,
+        view_distance: i8,
+        chat_mode: ClientChatMode,
+        chat_colors: bool,
+        displayed_skin_parts: ClientDisplayedSkinParts
+    },
+    PlayClientStatus, 0x16, Play, ServerBound => PlayClientStatusSpec {
+        action: ClientStatusAction
+    },
+    PlayClientPluginMessage, 0x17, Play, ServerBound => PlayClientPluginMessageSpec {
+        channel: String,
+        data: RemainingBytes
+    },
+    PlaySpectate, 0x18, Play, ServerBound => PlaySpectateSpec {
+        target: UUID4
+    },
+    PlayResourcePackStatus, 0x19, Play, ServerBound => PlayResourcePackStatusSpec {
+        hash: String,
+        status: ResourcePackStatus
+    }
+});
+
+impl crate::protocol::HasCompressionThreshold for Packet47 {
+    fn compression_threshold(&self) -> Option<i32> {
+        match self {
+            Packet47::LoginSetCompression(body) => Some(body.threshold.0),
+            Packet47::PlaySetCompression(body) => Some(body.threshold.0),
+            _ => None,
+        }
+    }
+}
+
+impl crate::protocol::HasNextState for Packet47 {
+    fn next_state(&self) -> Option<crate::protocol::State> {
+        use crate::protocol::State;
+        match self {
+            Packet47::Handshake(body) => Some(match body.next_state {
+                HandshakeNextState::Status => State::Status,
+                HandshakeNextState::Login => State::Login,
+            }),
+            Packet47::LoginSuccess(_) => Some(State::Play),
+            _ => None,
+        }
+    }
+}
+
+impl crate::limits::HasFieldLimits for Packet47 {
+    fn check_field_limits(&self, limits: &crate::limits::DeserializeLimits) -> Result<(), DeserializeErr> {
+        match self {
+            Packet47::LoginStart(body) => limits.check_username(body.name.as_str()),
+            Packet47::LoginSuccess(body) => limits.check_username(body.username.as_str()),
+            _ => Ok(()),
+        }
+    }
+}
+
+// helper types
+// handshake enum
+proto_byte_enum!(HandshakeNextState,
+    0x01 :: Status,
+    0x02 :: Login
+);
+
+proto_byte_enum!(CardinalDirection,
+    0x00 :: South,
+    0x01 :: West,
+    0x02 :: North,
+    0x03 :: East
+);
+
+proto_byte_enum!(EntityAnimationKind,
+    0x00 :: SwingArm,
+    0x01 :: TakeDamage,
+    0x02 :: LeaveBed,
+    0x03 :: EatFood,
+    0x04 :: CriticalEffect,
+    0x05 :: MagicCriticalEffect
+);
+
+proto_struct!(Statistic {
+    name: String,
+    value: VarInt
+});
+
+proto_byte_enum!(BlockEntityDataAction,
+    0x01 :: SetMobSpawnerData,
+    0x02 :: SetCommandBlockText,
+    0x03 :: SetBeaconLevelAndPower,
+    0x04 :: SetMobHeadRotationAndSkin,
+    0x05 :: SetFlowerPotFlower,
+    0x06 :: SetBannerColorAndPatterns
+);
+
+proto_byte_enum!(Difficulty,
+    0x00 :: Peaceful,
+    0x01 :: Easy,
+    0x02 :: Normal,
+    0x03 :: Hard
+);
+
+proto_byte_enum!(ChatPosition,
+    0x00 :: ChatBox,
+    0x01 :: SystemMessage,
+    0x02 :: Hotbar
+);
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct BlockChangeHorizontalPosition {
+    pub rel_x: u8,
+    pub rel_z: u8,
+}
+
+impl Serialize for BlockChangeHorizontalPosition {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        to.serialize_byte((self.rel_x & 0xF) << 4 | (self.rel_z & 0xF))
+    }
+}
+
+impl Deserialize for BlockChangeHorizontalPosition {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        Ok(
+            u8::mc_deserialize(data)?.map(move |b| BlockChangeHorizontalPosition {
+                rel_x: (b >> 4) & 0xF,
+                rel_z: b & 0xF,
+            }),
+        )
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for BlockChangeHorizontalPosition {
+    fn test_gen_random() -> Self {
+        BlockChangeHorizontalPosition {
+            rel_x: rand::random::<u8>() % 16,
+            rel_z: rand::random::<u8>() % 16,
+        }
+    }
+}
+
+proto_struct!(MultiBlockChangeRecord {
+    horizontal_position: BlockChangeHorizontalPosition,
+    y_coordinate: u8,
+    block_id: VarInt
+});
+
+// "EntityHorse" is the only window type followed by an entity id
+const HORSE_WINDOW_TYPE: &str = "EntityHorse";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum WindowKind {
+    Container(String),
+    Horse(i32),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WindowSpec {
+    pub kind: WindowKind,
+    pub title: Chat,
+    pub number_of_slots: u8,
+}
+
+impl Serialize for WindowSpec {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        use WindowKind::*;
+        match &self.kind {
+            Container(name) => to.serialize_other(name)?,
+            Horse(_) => to.serialize_other(&HORSE_WINDOW_TYPE.to_owned())?,
+        }
+        to.serialize_other(&self.title)?;
+        to.serialize_other(&self.number_of_slots)?;
+        if let Horse(entity_id) = &self.kind {
+            to.serialize_other(entity_id)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Deserialize for WindowSpec {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: kind, data } = String::mc_deserialize(data)?;
+        let Deserialized { value: title, data } = Chat::mc_deserialize(data)?;
+        let Deserialized { value: number_of_slots, data } = u8::mc_deserialize(data)?;
+        let Deserialized { value: kind, data } = if kind == HORSE_WINDOW_TYPE {
+            i32::mc_deserialize(data)?.map(WindowKind::Horse)
+        } else {
+            Deserialized { value: WindowKind::Container(kind), data }
+        };
+
+        Deserialized::ok(Self {
+            kind,
+            title,
+            number_of_slots,
+        }, data)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for WindowSpec {
+    fn test_gen_random() -> Self {
+        let kind = if rand::random::<bool>() {
+            WindowKind::Horse(i32::test_gen_random())
+        } else {
+            WindowKind::Container("minecraft:chest".to_owned())
+        };
+
+        Self {
+            kind,
+            title: Chat::test_gen_random(),
+            number_of_slots: u8::test_gen_random(),
+        }
+    }
+}
+
+proto_byte_enum!(GameMode,
+    0x00 :: Survival,
+    0x01 :: Creative,
+    0x02 :: Adventure,
+    0x03 :: Spectator
+);
+
+// the hardcore flag is packed into the game mode byte of PlayJoinGame
+#[derive(Clone, Debug, PartialEq)]
+pub struct JoinGameMode {
+    pub mode: GameMode,
+    pub hardcore: bool,
+}
+
+impl Serialize for JoinGameMode {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        let hardcore = if self.hardcore { 0x08 } else { 0x00 };
+        to.serialize_byte(self.mode.id() | hardcore)
+    }
+}
+
+impl Deserialize for JoinGameMode {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: raw, data } = u8::mc_deserialize(data)?;
+        let hardcore = raw & 0x08 != 0;
+        Ok(GameMode::deserialize_with_id(raw & 0x07, data)?.map(move |mode| Self { mode, hardcore }))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for JoinGameMode {
+    fn test_gen_random() -> Self {
+        Self {
+            mode: GameMode::test_gen_random(),
+            hardcore: rand::random(),
+        }
+    }
+}
+
+proto_byte_enum!(WinGameAction,
+    0x00 :: Respawn,
+    0x01 :: RollCreditsAndRespawn
+);
+
+proto_byte_enum!(DemoEvent,
+    0x00 :: ShowWelcomeScreen,
+    0x65 :: TellMovementControls,
+    0x66 :: TellJumpControl,
+    0x67 :: TellInventoryControl
+);
+
+proto_enum_with_type!(i8, Dimension,
+    (-0x01, Nether),
+    (0x00, Overworld),
+    (0x01, End)
+);
+
+// respawn sends the same dimension as an int
+proto_int_enum!(RespawnDimension,
+    -0x01 :: Nether,
+     0x00 :: Overworld,
+     0x01 :: End
+);
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GameChangeReason {
+    InvalidBed,
+    EndRaining,
+    BeginRaining,
+    ChangeGameMode(GameMode),
+    ExitEnd(WinGameAction),
+    Demo(DemoEvent),
+    ArrowHitPlayer,
+    FadeValue(f32),
+    FadeTime(f32),
+    ElderGuardianMobAppearance,
+}
+
+impl Serialize for GameChangeReason {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        use GameChangeReason::*;
+        to.serialize_byte(match self {
+            InvalidBed => 0x00,
+            EndRaining => 0x01,
+            BeginRaining => 0x02,
+            ChangeGameMode(_) => 0x03,
+            ExitEnd(_) => 0x04,
+            Demo(_) => 0x05,
+            ArrowHitPlayer => 0x06,
+            FadeValue(_) => 0x07,
+            FadeTime(_) => 0x08,
+            ElderGuardianMobAppearance => 0x0A,
+        })?;
+
+        let value = match self {
+            ChangeGameMode(body) => body.id() as f32,
+            ExitEnd(body) => body.id() as f32,
+            Demo(body) => body.id() as f32,
+            FadeValue(body) => *body,
+            FadeTime(body) => *body,
+            _ => 0 as f32,
+        };
+        to.serialize_other(&value)
+    }
+}
+
+impl Deserialize for GameChangeReason {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: reason_id, data } = u8::mc_deserialize(data)?;
+        let Deserialized { value, data } = f32::mc_deserialize(data)?;
+        use GameChangeReason::*;
+        match reason_id {
+            0x00 => Deserialized::ok(InvalidBed, data),
+            0x01 => Deserialized::ok(EndRaining, data),
+            0x02 => Deserialized::ok(BeginRaining, data),
+            0x03 => Ok(GameMode::deserialize_with_id(value as u8, data)?.map(ChangeGameMode)),
+            0x04 => Ok(WinGameAction::deserialize_with_id(value as u8, data)?.map(ExitEnd)),
+            0x05 => Ok(DemoEvent::deserialize_with_id(value as u8, data)?.map(Demo)),
+            0x06 => Deserialized::ok(ArrowHitPlayer, data),
+            0x07 => Deserialized::ok(FadeValue(value), data),
+            0x08 => Deserialized::ok(FadeTime(value), data),
+            0x0A => Deserialized::ok(ElderGuardianMobAppearance, data),
+            other => Err(DeserializeErr::CannotUnderstandValue(alloc::format!(
+                "invalid game change reason id {}",
+                other
+            ))),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for GameChangeReason {
+    fn test_gen_random() -> Self {
+        use GameChangeReason::*;
+        match rand::random::<u8>() % 5 {
+            0 => BeginRaining,
+            1 => ChangeGameMode(GameMode::test_gen_random()),
+            2 => ExitEnd(WinGameAction::test_gen_random()),
+            3 => FadeValue(f32::test_gen_random()),
+            _ => ElderGuardianMobAppearance,
+        }
+    }
+}
+
+// particles which are followed by some VarInt arguments, with the number of arguments
+const PARTICLE_ARGUMENTS: [(i32, usize); 3] = [
+    (36, 2), // iconcrack
+    (37, 1), // blockcrack
+    (38, 1), // blockdust
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParticleSpec {
+    pub particle_id: i32,
+    pub long_distance: bool,
+    pub position: Vec3<f32>,
+    pub offset: Vec3<f32>,
+    pub particle_data: f32,
+    pub count: i32,
+    pub arguments: Vec<VarInt>,
+}
+
+impl ParticleSpec {
+    pub fn argument_count(particle_id: i32) -> usize {
+        PARTICLE_ARGUMENTS.iter()
+            .find(move |(id, _)| *id == particle_id)
+            .map(move |(_, count)| *count)
+            .unwrap_or(0)
+    }
+}
+
+impl Serialize for ParticleSpec {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        let expected = Self::argument_count(self.particle_id);
+        if self.arguments.len() != expected {
+            return Err(SerializeErr::CannotSerialize(alloc::format!(
+                "particle {} takes {} arguments, got {}",
+                self.particle_id, expected, self.arguments.len()
+            )));
+        }
+
+        to.serialize_other(&self.particle_id)?;
+        to.serialize_other(&self.long_distance)?;
+        to.serialize_other(&self.position)?;
+        to.serialize_other(&self.offset)?;
+        to.serialize_other(&self.particle_data)?;
+        to.serialize_other(&self.count)?;
+        for argument in &self.arguments {
+            to.serialize_other(argument)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Deserialize for ParticleSpec {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: particle_id, data } = i32::mc_deserialize(data)?;
+        let Deserialized { value: long_distance, data } = bool::mc_deserialize(data)?;
+        let Deserialized { value: position, data } = <Vec3<f32>>::mc_deserialize(data)?;
+        let Deserialized { value: offset, data } = <Vec3<f32>>::mc_deserialize(data)?;
+        let Deserialized { value: particle_data, data } = f32::mc_deserialize(data)?;
+        let Deserialized { value: count, mut data } = i32::mc_deserialize(data)?;
+        let n_arguments = Self::argument_count(particle_id);
+        let mut arguments = Vec::with_capacity(n_arguments);
+        for _ in 0..n_arguments {
+            let Deserialized { value: argument, data: rest } = VarInt::mc_deserialize(data)?;
+            data = rest;
+            arguments.push(argument);
+        }
+
+        Deserialized::ok(Self {
+            particle_id,
+            long_distance,
+            position,
+            offset,
+            particle_data,
+            count,
+            arguments,
+        }, data)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for ParticleSpec {
+    fn test_gen_random() -> Self {
+        let particle_id = (rand::random::<u8>() % 42) as i32;
+        let arguments = (0..Self::argument_count(particle_id))
+            .map(move |_| VarInt::test_gen_random())
+            .collect();
+
+        Self {
+            particle_id,
+            long_distance: rand::random(),
+            position: <Vec3<f32>>::test_gen_random(),
+            offset: <Vec3<f32>>::test_gen_random(),
+            particle_data: f32::test_gen_random(),
+            count: i32::test_gen_random(),
+            arguments,
+        }
+    }
+}
+
+proto_byte_enum!(MapIconType,
+    0x00 :: WhiteArrow,
+    0x01 :: GreenArrow,
+    0x02 :: RedArrow,
+    0x03 :: BlueArrow,
+    0x04 :: WhiteCross,
+    0x05 :: RedPointer,
+    0x06 :: WhiteCircle
+);
+
+// the icon type and direction share a single byte
+#[derive(Clone, Debug, PartialEq)]
+pub struct MapIconSpec {
+    pub kind: MapIconType,
+    pub direction: u8,
+    pub position: TopDownPosition<i8>,
+}
+
+impl Serialize for MapIconSpec {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        to.serialize_byte(self.kind.id() << 4 | (self.direction & 0xF))?;
+        to.serialize_other(&self.position)
+    }
+}
+
+impl Deserialize for MapIconSpec {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: raw, data } = u8::mc_deserialize(data)?;
+        let Deserialized { value: kind, data } = MapIconType::deserialize_with_id(raw >> 4, data)?;
+        let direction = raw & 0xF;
+        Ok(<TopDownPosition<i8>>::mc_deserialize(data)?.map(move |position| Self {
+            kind,
+            direction,
+            position,
+        }))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for MapIconSpec {
+    fn test_gen_random() -> Self {
+        Self {
+            kind: MapIconType::test_gen_random(),
+            direction: rand::random::<u8>() % 16,
+            position: <TopDownPosition<i8>>::test_gen_random(),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum MapColumns {
+    NoUpdates,
+    Updated(MapColumnsSpec),
+}
+
+proto_struct!(MapColumnsSpec {
+    columns: u8,
+    rows: u8,
+    position: TopDownPosition<u8>,
+    data: CountedArray<u8, VarInt>
+});
+
+impl Serialize for MapColumns {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        use MapColumns::*;
+        match self {
+            NoUpdates => to.serialize_other(&0u8),
+            Updated(body) => to.serialize_other(body),
+        }
+    }
+}
+
+impl Deserialize for MapColumns {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: columns, data: rest } = u8::mc_deserialize(data)?;
+        use MapColumns::*;
+        match columns {
+            0x00 => Deserialized::ok(NoUpdates, rest),
+            _ => Ok(MapColumnsSpec::mc_deserialize(data)?.map(Updated)),
+        }
+    }
+}
+
+impl From<Option<MapColumnsSpec>> for MapColumns {
+    fn from(other: Option<MapColumnsSpec>) -> Self {
+        use MapColumns::*;
+        match other {
+            Some(body) if body.columns != 0 => Updated(body),
+            _ => NoUpdates,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for MapColumns {
+    fn test_gen_random() -> Self {
+        <Option<MapColumnsSpec>>::test_gen_random().into()
+    }
+}
+
+proto_byte_flag!(PlayerAbilityFlags,
+    0x01 :: is_invulnerable set_invulnerable,
+    0x02 :: is_flying set_flying,
+    0x04 :: is_flight_allowed set_flight_allowed,
+    0x08 :: is_instant_break set_instant_break
+);
+
+proto_varint_enum!(CombatEvent,
+    0x00 :: Enter,
+    0x01 :: End(CombatEndSpec),
+    0x02 :: EntityDead(CombatEntityDeadSpec)
+);
+
+proto_struct!(CombatEndSpec {
+    duration_ticks: VarInt,
+    entity_id: i32
+});
+
+proto_struct!(CombatEntityDeadSpec {
+    player_id: VarInt,
+    entity_id: i32,
+    message: String
+});
+
+proto_struct!(PlayerInfoAction<A> {
+    uuid: UUID4,
+    action: A
+});
+
+// the player list still sends game modes as VarInts
+proto_varint_enum!(PlayerInfoActionList,
+    0x00 :: Add(CountedArray<PlayerInfoAction<PlayerAddActionSpec>, VarInt>),
+    0x01 :: UpdateGameMode(CountedArray<PlayerInfoAction<VarInt>, VarInt>),
+    0x02 :: UpdateLatency(CountedArray<PlayerInfoAction<VarInt>, VarInt>),
+    0x03 :: UpdateDisplayName(CountedArray<PlayerInfoAction<Option<Chat>>, VarInt>),
+    0x04 :: Remove(CountedArray<UUID4, VarInt>)
+);
+
+proto_struct!(PlayerAddActionSpec {
+    name: String,
+    properties: CountedArray<PlayerAddProperty, VarInt>,
+    game_mode: VarInt,
+    ping_ms: VarInt,
+    display_name: Option<Chat>
+});
+
+proto_struct!(PlayerAddProperty {
+    name: String,
+    value: String,
+    signature: Option<String>
+});
+
+proto_byte_flag!(PositionAndLookFlags,
+    0x01 :: is_x_rel set_x_rel,
+    0x02 :: is_y_rel set_y_rel,
+    0x04 :: is_z_rel set_z_rel,
+    0x08 :: is_y_rotation_rel set_y_rotation_rel,
+    0x10 :: is_x_rotation_rel set_x_rotation_rel
+);
+
+proto_byte_enum!(EntityEffectKind,
+    0x01 :: Speed,
+    0x02 :: Slowness,
+    0x03 :: Haste,
+    0x04 :: MiningFatigue,
+    0x05 :: Strength,
+    0x06 :: InstantHealth,
+    0x07 :: InstantDamage,
+    0x08 :: JumpBoost,
+    0x09 :: Nausea,
+    0x0A :: Regeneration,
+    0x0B :: Resistance,
+    0x0C :: FireResistance,
+    0x0D :: WaterBreathing,
+    0x0E :: Invisibility,
+    0x0F :: Blindness,
+    0x10 :: NightVision,
+    0x11 :: Hunger,
+    0x12 :: Weakness,
+    0x13 :: Poison,
+    0x14 :: Wither,
+    0x15 :: HealthBoost,
+    0x16 :: Absorption,
+    0x17 :: Saturation
+);
+
+proto_varint_enum!(WorldBorderAction,
+    0x00 :: SetSize(WorldBorderSetSizeSpec),
+    0x01 :: LerpSize(WorldBorderLerpSizeSpec),
+    0x02 :: SetCenter(TopDownPosition<f64>),
+    0x03 :: Initialize(WorldBorderInitializeSpec),
+    0x04 :: SetWarningTime(WorldBorderWarningTimeSpec),
+    0x05 :: SetWarningBlocks(WorldBorderWarningBlocksSpec)
+);
+
+proto_struct!(WorldBorderSetSizeSpec {
+    diameter: f64
+});
+
+proto_struct!(WorldBorderLerpSizeSpec {
+    old_diameter: f64,
+    new_diameter: f64,
+    speed: VarLong
+});
+
+proto_struct!(WorldBorderInitializeSpec {
+    position: TopDownPosition<f64>,
+    old_diameter: f64,
+    new_diameter: f64,
+    speed: VarLong,
+    portal_teleport_boundary: VarInt,
+    warning_time: VarInt,
+    warning_blocks: VarInt
+});
+
+proto_struct!(WorldBorderWarningTimeSpec {
+    warning_time: VarInt
+});
+
+proto_struct!(WorldBorderWarningBlocksSpec {
+    warning_blocks: VarInt
+});
+
+proto_byte_enum!(ScoreboardPosition,
+    0x00 :: List,
+    0x01 :: Sidebar,
+    0x02 :: BelowName,
+    0x03 :: TeamSpecific(i8)
+);
+
+proto_enum_with_type!(i16, EquipmentSlot,
+    (0x00i16, Held),
+    (0x01i16, ArmorBoots),
+    (0x02i16, ArmorLeggings),
+    (0x03i16, ArmorChestplate),
+    (0x04i16, ArmorHelmet)
+);
+
+proto_byte_enum!(ScoreboardObjectiveAction,
+    0x00 :: Create(ScoreboardObjectiveSpec),
+    0x01 :: Remove,
+    0x02 :: UpdateText(ScoreboardObjectiveSpec)
+);
+
+proto_str_enum!(ScoreboardObjectiveKind,
+    "integer" :: Integer,
+    "hearts" :: Hearts
+);
+
+proto_struct!(ScoreboardObjectiveSpec {
+    text: String,
+    kind: ScoreboardObjectiveKind
+});
+
+proto_byte_enum!(TeamAction,
+    0x00 :: Create(TeamActionCreateSpec),
+    0x01 :: Remove,
+    0x02 :: UpdateInfo(TeamActionUpdateInfoSpec),
+    0x03 :: AddPlayers(TeamActionPlayerList),
+    0x04 :: RemovePlayers(TeamActionPlayerList)
+);
+
+proto_str_enum!(TeamTagNameVisibility,
+    "always" :: Always,
+    "hideForOtherTeams" :: HideForOtherTeams,
+    "hideForOwnTeam" :: HideForOwnTeam,
+    "never" :: Never
+);
+
+proto_struct!(TeamActionPlayerList {
+    entities: CountedArray<String, VarInt>
+});
+
+proto_struct!(TeamActionCreateSpec {
+    display_name: String,
+    prefix: String,
+    suffix: String,
+    friendly_flags: TeamFriendlyFlags,
+    tag_name_visibility: TeamTagNameVisibility,
+    color: i8,
+    entities: CountedArray<String, VarInt>
+});
+
+proto_struct!(TeamActionUpdateInfoSpec {
+    display_name: String,
+    prefix: String,
+    suffix: String,
+    friendly_flags: TeamFriendlyFlags,
+    tag_name_visibility: TeamTagNameVisibility,
+    color: i8
+});
+
+proto_byte_flag!(TeamFriendlyFlags,
+    0x01 :: allow_friendly_fire set_friendly_fire,
+    0x02 :: show_invisible_teammates set_show_invisible_teammates
+);
+
+proto_byte_enum!(UpdateScoreAction,
+    0x00 :: Upsert(VarInt),
+    0x01 :: Remove
+);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct UpdateScoreSpec {
+    pub objective_name: String,
+    pub action: UpdateScoreAction,
+}
+
+impl Serialize for UpdateScoreSpec {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        to.serialize_byte(self.action.id())?;
+        to.serialize_other(&self.objective_name)?;
+        self.action.serialize_body(to)?;
+
+        Ok(())
+    }
+}
+
+impl Deserialize for UpdateScoreSpec {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: action_id, data } = u8::mc_deserialize(data)?;
+        let Deserialized { value: objective_name, data } = String::mc_deserialize(data)?;
+
+        Ok(UpdateScoreAction::deserialize_with_id(action_id, data)?.map(move |action| {
+            Self {
+                objective_name,
+                action,
+            }
+        }))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for UpdateScoreSpec {
+    fn test_gen_random() -> Self {
+        Self {
+            objective_name: String::test_gen_random(),
+            action: UpdateScoreAction::test_gen_random(),
+        }
+    }
+}
+
+proto_varint_enum!(TitleActionSpec,
+    0x00 :: SetTitle(Chat),
+    0x01 :: SetSubtitle(Chat),
+    0x02 :: SetTimesAndDisplay(TitleTimesSpec),
+    0x03 :: Hide,
+    0x04 :: Reset
+);
+
+proto_struct!(TitleTimesSpec {
+    fade_in: i32,
+    stay: i32,
+    fade_out: i32
+});
+
+proto_struct!(EntityPropertySpec {
+    key: String,
+    value: f64,
+    modifiers: CountedArray<EntityPropertyModifierSpec, VarInt>
+});
+
+proto_struct!(EntityPropertyModifierSpec {
+    uuid: UUID4,
+    amount: f64,
+    operation: EntityPropertyModifierOperation
+});
+
+proto_byte_enum!(EntityPropertyModifierOperation,
+    0x00 :: AddSubtractAmount,
+    0x01 :: AddSubtractAmountPercentOfCurrent,
+    0x02 :: MultiplyByAmountPercent
+);
+
+proto_varint_enum!(ClientStatusAction,
+    0x00 :: PerformRespawn,
+    0x01 :: RequestStats,
+    0x02 :: OpenInventoryAchievement
+);
+
+proto_byte_enum!(ClientChatMode,
+    0x00 :: Enabled,
+    0x01 :: CommandsOnly,
+    0x02 :: Hidden
+);
+
+proto_byte_flag!(ClientDisplayedSkinParts,
+    0x01 :: is_cape_enabled set_cape_enabled,
+    0x02 :: is_jacket_enabled set_jacket_enabled,
+    0x04 :: is_left_sleeve_enabled set_left_sleeve_enabled,
+    0x08 :: is_right_sleeve_enabled set_right_sleeve_enabled,
+    0x10 :: is_left_pants_leg_enabled set_left_pants_leg_enabled,
+    0x20 :: is_right_pant_legs_enabled set_right_pant_legs_enabled,
+    0x40 :: is_hat_enabled set_hat_enabled
+);
+
+proto_byte_enum!(InventoryOperationMode,
+    0x00 :: MouseClick,
+    0x01 :: ShiftClick,
+    0x02 :: NumberClick,
+    0x03 :: MiddleClick,
+    0x04 :: DropClick,
+    0x05 :: Drag,
+    0x06 :: DoubleClick
+);
+
+proto_varint_enum!(InteractKind,
+    0x00 :: Interact,
+    0x01 :: Attack,
+    0x02 :: InteractAt(Vec3<f32>)
+);
+
+proto_byte_flag!(ClientPlayerAbilities,
+    0x01 :: is_creative set_creative,
+    0x02 :: is_flying set_flying,
+    0x04 :: is_fly_enabled set_fly_enabled,
+    0x08 :: is_damaged_disabled set_damaged_disabled
+);
+
+proto_byte_enum!(PlayerDiggingStatus,
+    0x00 :: Started,
+    0x01 :: Cancelled,
+    0x02 :: Finished,
+    0x03 :: DropStack,
+    0x04 :: DropItem,
+    0x05 :: ShootArrowOrFishEating
+);
+
+proto_byte_enum!(DiggingFace,
+    0x00 :: Bottom,
+    0x01 :: Top,
+    0x02 :: North,
+    0x03 :: South,
+    0x04 :: West,
+    0x05 :: East
+);
+
+proto_varint_enum!(EntityActionKind,
+    0x00 :: StartSneaking,
+    0x01 :: StopSneaking,
+    0x02 :: LeaveBed,
+    0x03 :: StartSprinting,
+    0x04 :: StopSprinting,
+    0x05 :: JumpWithHorse,
+    0x06 :: OpenHorseInventory
+);
+
+proto_byte_flag!(SteerVehicleFlags,
+    0x01 :: is_jump set_jump,
+    0x02 :: is_unmount set_unmount
+);
+
+proto_varint_enum!(ResourcePackStatus,
+    0x00 :: Loaded,
+    0x01 :: Declined,
+    0x02 :: FailedDownload,
+    0x03 :: Accepted
+);
+// items are still identified by a numeric id and a damage value before the flattening
+#[derive(Debug, PartialEq, Clone)]
+pub struct ItemStack {
+    pub item_id: i16,
+    pub item_count: i8,
+    pub damage: i16,
+    pub nbt: Option<nbt::NamedTag>,
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for ItemStack {
+    fn test_gen_random() -> Self {
+        Self {
+            item_id: (rand::random::<u16>() % 512) as i16,
+            item_count: i8::test_gen_random() % 65,
+            damage: i16::test_gen_random(),
+            nbt: <Option<nbt::NamedTag>>::test_gen_random(),
+        }
+    }
+}
+
+const EMPTY_SLOT_ID: i16 = -1;
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Slot(pub Option<ItemStack>);
+
+impl Serialize for Slot {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        match &self.0 {
+            None => to.serialize_other(&EMPTY_SLOT_ID),
+            Some(item) => {
+                to.serialize_other(&item.item_id)?;
+                to.serialize_other(&item.item_count)?;
+                to.serialize_other(&item.damage)?;
+                match item.nbt.as_ref() {
+                    Some(nbt) => to.serialize_bytes(nbt.bytes().as_slice()),
+                    None => to.serialize_byte(nbt::Tag::End.id()),
+                }
+            }
+        }
+    }
+}
+
+impl Deserialize for Slot {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: item_id, data } = i16::mc_deserialize(data)?;
+        if item_id == EMPTY_SLOT_ID {
+            return Deserialized::ok(Slot(None), data);
+        }
+
+        let Deserialized { value: item_count, data } = i8::mc_deserialize(data)?;
+        let Deserialized { value: damage, data } = i16::mc_deserialize(data)?;
+        if data.is_empty() {
+            return Err(DeserializeErr::Eof);
+        }
+
+        Ok(match data[0] {
+            0x00 => Deserialized {
+                value: None,
+                data: &data[1..],
+            },
+            _ => nbt::read_named_tag(data)?.map(Some),
+        }.map(move |nbt| Slot(Some(ItemStack {
+            item_id,
+            item_count,
+            damage,
+            nbt,
+        }))))
+    }
+}
+
+impl From<Option<ItemStack>> for Slot {
+    fn from(other: Option<ItemStack>) -> Self {
+        Slot(other)
+    }
+}
+
+impl From<ItemStack> for Slot {
+    fn from(other: ItemStack) -> Self {
+        Slot(Some(other))
+    }
+}
+
+impl From<Slot> for Option<ItemStack> {
+    fn from(other: Slot) -> Self {
+        other.0
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for Slot {
+    fn test_gen_random() -> Self {
+        Slot(<Option<ItemStack>>::test_gen_random())
+    }
+}
+
+// the object data is followed by a velocity unless it is zero
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObjectData {
+    pub data: i32,
+    pub velocity: Option<Vec3<i16>>,
+}
+
+impl Serialize for ObjectData {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        to.serialize_other(&self.data)?;
+        if self.data != 0 {
+            let velocity = self.velocity.unwrap_or(Vec3 { x: 0, y: 0, z: 0 });
+            to.serialize_other(&velocity)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Deserialize for ObjectData {
+    fn mc_deserialize(data: &[u8]) -> DeserializeResult<'_, Self> {
+        let Deserialized { value: object_data, data } = i32::mc_deserialize(data)?;
+        Ok(if object_data != 0 {
+            <Vec3<i16>>::mc_deserialize(data)?.map(Some)
+        } else {
+            Deserialized { value: None, data }
+        }.map(move |velocity| Self {
+            data: object_data,
+            velocity,
+        }))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for ObjectData {
+    fn test_gen_random() -> Self {
+        let data = i32::test_gen_random();
+        let velocity = if data != 0 {
+            Some(<Vec3<i16>>::test_gen_random())
+        } else {
+            None
+        };
+
+        Self { data, velocity }
+    }
+}
+
+proto_struct!(ChunkMetaSpec {
+    position: ChunkPosition<i32>,
+    primary_bit_mask: u16
+});
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct EntityMetadata {
+    pub fields: Vec<EntityMetadataField>
+}
+
+// each field starts with a single byte holding the type in the top 3 bits and the index in the low 5
+const METADATA_END: u8 = 0x7F;
+pub const METADATA_MAX_INDEX: u8 = 0x1F;
+
+impl Serialize for EntityMetadata {
+    fn mc_serialize<S: Serializer>(&self, to: &mut S) -> SerializeResult {
+        for field in &self.fields {
+            let key = field.data.id() << 5 | (field.index & METADATA_MAX_INDEX);
+            if key == METADATA_END {
+                return Err(SerializeErr::CannotSerialize(alloc::format!(
+                    "metadata field {} of type {} collides with the end marker",
+                    field.index, field.data.name()
+                )));
+            }
+
+            to.serialize_byte(key)?;
+            field.data.serialize_body(to)?;
+        }
+        to.serialize_byte(METADATA_END)
+    }
+}
+
+impl Deserialize for EntityMetadata {
+    fn mc_deserialize(mut data: &[u8]) -> DeserializeResult<'_, Self> {
+        let mut fields = Vec::new();
+        loop {
+            let Deserialized { value: key, data: rest } = u8::mc_deserialize(data)?;
+            data = rest;
+            if key == METADATA_END {
+                break;
+            }
+
+            let Deserialized { value: field, data: rest } = EntityMetadataFieldData::deserialize_with_id(key >> 5, data)?;
+            data = rest;
+            fields.push(EntityMetadataField{
+                index: key & METADATA_MAX_INDEX,
+                data: field,
+            });
+        }
+
+        Deserialized::ok(Self{
+            fields,
+        }, data)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+impl TestRandom for EntityMetadata {
+    fn test_gen_random() -> Self {
+        let n_fields = rand::random::<usize>() % 10;
+        let mut fields = Vec::with_capacity(n_fields);
+        for i in 0..n_fields {
+            fields.push(EntityMetadataField{
+                index: i as u8,
+                data: EntityMetadataFieldData::test_gen_random(),
+            });
+        }
+
+        Self {
+            fields,
+        }
+    }
+}
+
+impl EntityMetadata {
+    pub fn set(&mut self, index: u8, data: EntityMetadataFieldData) {
+        for field in &mut self.fields {
+            if field.index == index {
+                field.data = data;
+                return;
+            }
+        }
+
+        self.fields.push(EntityMetadataField{
+            index,
+            data,
+        })
+    }
+
+    pub fn remove(&mut self, index: u8) -> bool {
+        if let Some(i) = self.fields.iter().position(move |field| field.index == index) {
+            self.fields.remove(i);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn get(&self, index: u8) -> Option<&EntityMetadataFieldData> {
+        self.fields.iter()
+            .find(move |field| field.index == index)
+            .map(move |field| &field.data)
+    }
+
+    pub fn get_mut(&mut self, index: u8) -> Option<&mut EntityMetadataFieldData> {
+        self.fields.iter_mut()
+            .find(move |field| field.index == index)
+            .map(move |field| &mut field.data)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EntityMetadataField {
+    pub index: u8,
+    pub data: EntityMetadataFieldData
+}
+
+proto_byte_enum!(EntityMetadataFieldData,
+    0x00 :: Byte(i8),
+    0x01 :: Short(i16),
+    0x02 :: Int(i32),
+    0x03 :: Float(f32),
+    0x04 :: String(String),
+    0x05 :: Slot(Slot),
+    0x06 :: Position(Vec3<i32>),
+    0x07 :: Rotation(Vec3<f32>)
+);
+
+#[cfg(all(test, feature = "std"))]
+pub mod tests {
+    use super::*;
+    use crate::packet_test_cases;
+    use crate::protocol::TestRandom;
+
+    packet_test_cases!(RawPacket47, Packet47, Handshake, HandshakeSpec,
+        test_handshake, bench_write_handshake, bench_read_handshake);
+
+    packet_test_cases!(RawPacket47, Packet47, StatusRequest, StatusRequestSpec,
+        test_status_request, bench_write_status_request, bench_read_status_request);
+
+    packet_test_cases!(RawPacket47, Packet47, StatusPing, StatusPingSpec,
+        test_status_ping, bench_write_status_ping, bench_read_status_ping);
+
+    packet_test_cases!(RawPacket47, Packet47, StatusResponse, StatusResponseSpec,
+        test_status_response, bench_write_status_response, bench_read_status_response);
+
+    packet_test_cases!(RawPacket47, Packet47, StatusPong, StatusPongSpec,
+        test_status_pong, bench_write_status_pong, bench_read_status_pong);
+
+    packet_test_cases!(RawPacket47, Packet47, LoginDisconnect, LoginDisconnectSpec,
+        test_login_disconnect, bench_write_login_disconnect, bench_read_login_disconnect);
+
+    packet_test_cases!(RawPacket47, Packet47, LoginEncryptionRequest, LoginEncryptionRequestSpec,
+        test_login_encryption_request, bench_write_login_encryption_request, bench_read_login_encryption_request);
+
+    packet_test_cases!(RawPacket47, Packet47, LoginSuccess, LoginSuccessSpec,
+        test_login_success, bench_write_login_success, bench_read_login_success);
+
+    packet_test_cases!(RawPacket47, Packet47, LoginSetCompression, LoginSetCompressionSpec,
+        test_login_set_compression, bench_write_login_set_compression, bench_read_login_set_compression);
+
+    packet_test_cases!(RawPacket47, Packet47, LoginStart, LoginStartSpec,
+        test_login_start, bench_write_login_start, bench_read_login_start);
+
+    packet_test_cases!(RawPacket47, Packet47, LoginEncryptionResponse, LoginEncryptionResponseSpec,
+        test_login_encryption_response, bench_write_login_encryption_response, bench_read_login_encryption_response);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayServerKeepAlive, PlayServerKeepAliveSpec,
+        test_play_server_keep_alive, bench_write_play_server_keep_alive, bench_read_play_server_keep_alive);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayJoinGame, PlayJoinGameSpec,
+        test_play_join_game, bench_write_play_join_game, bench_read_play_join_game);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayServerChatMessage, PlayServerChatMessageSpec,
+        test_play_server_chat_message, bench_write_play_server_chat_message, bench_read_play_server_chat_message);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayTimeUpdate, PlayTimeUpdateSpec,
+        test_play_time_update, bench_write_play_time_update, bench_read_play_time_update);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayEntityEquipment, PlayEntityEquipmentSpec,
+        test_play_entity_equipment, bench_write_play_entity_equipment, bench_read_play_entity_equipment);
+
+    packet_test_cases!(RawPacket47, Packet47, PlaySpawnPosition, PlaySpawnPositionSpec,
+        test_play_spawn_position, bench_write_play_spawn_position, bench_read_play_spawn_position);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayUpdateHealth, PlayUpdateHealthSpec,
+        test_play_update_health, bench_write_play_update_health, bench_read_play_update_health);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayRespawn, PlayRespawnSpec,
+        test_play_respawn, bench_write_play_respawn, bench_read_play_respawn);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayServerPlayerPositionAndLook, PlayServerPlayerPositionAndLookSpec,
+        test_play_server_player_position_and_look, bench_write_play_server_player_position_and_look, bench_read_play_server_player_position_and_look);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayServerHeldItemChange, PlayServerHeldItemChangeSpec,
+        test_play_server_held_item_change, bench_write_play_server_held_item_change, bench_read_play_server_held_item_change);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayUseBed, PlayUseBedSpec,
+        test_play_use_bed, bench_write_play_use_bed, bench_read_play_use_bed);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayEntityAnimation, PlayEntityAnimationSpec,
+        test_play_entity_animation, bench_write_play_entity_animation, bench_read_play_entity_animation);
+
+    packet_test_cases!(RawPacket47, Packet47, PlaySpawnPlayer, PlaySpawnPlayerSpec,
+        test_play_spawn_player, bench_write_play_spawn_player, bench_read_play_spawn_player);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayCollectItem, PlayCollectItemSpec,
+        test_play_collect_item, bench_write_play_collect_item, bench_read_play_collect_item);
+
+    packet_test_cases!(RawPacket47, Packet47, PlaySpawnEntity, PlaySpawnEntitySpec,
+        test_play_spawn_entity, bench_write_play_spawn_entity, bench_read_play_spawn_entity);
+
+    packet_test_cases!(RawPacket47, Packet47, PlaySpawnLivingEntity, PlaySpawnLivingEntitySpec,
+        test_play_spawn_living_entity, bench_write_play_spawn_living_entity, bench_read_play_spawn_living_entity);
+
+    packet_test_cases!(RawPacket47, Packet47, PlaySpawnPainting, PlaySpawnPaintingSpec,
+        test_play_spawn_painting, bench_write_play_spawn_painting, bench_read_play_spawn_painting);
+
+    packet_test_cases!(RawPacket47, Packet47, PlaySpawnExperienceOrb, PlaySpawnExperienceOrbSpec,
+        test_play_spawn_experience_orb, bench_write_play_spawn_experience_orb, bench_read_play_spawn_experience_orb);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayEntityVelocity, PlayEntityVelocitySpec,
+        test_play_entity_velocity, bench_write_play_entity_velocity, bench_read_play_entity_velocity);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayDestroyEntities, PlayDestroyEntitiesSpec,
+        test_play_destroy_entities, bench_write_play_destroy_entities, bench_read_play_destroy_entities);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayEntityMovement, PlayEntityMovementSpec,
+        test_play_entity_movement, bench_write_play_entity_movement, bench_read_play_entity_movement);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayEntityPosition, PlayEntityPositionSpec,
+        test_play_entity_position, bench_write_play_entity_position, bench_read_play_entity_position);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayEntityRotation, PlayEntityRotationSpec,
+        test_play_entity_rotation, bench_write_play_entity_rotation, bench_read_play_entity_rotation);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayEntityPositionAndRotation, PlayEntityPositionAndRotationSpec,
+        test_play_entity_position_and_rotation, bench_write_play_entity_position_and_rotation, bench_read_play_entity_position_and_rotation);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayEntityTeleport, PlayEntityTeleportSpec,
+        test_play_entity_teleport, bench_write_play_entity_teleport, bench_read_play_entity_teleport);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayEntityHeadLook, PlayEntityHeadLookSpec,
+        test_play_entity_head_look, bench_write_play_entity_head_look, bench_read_play_entity_head_look);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayEntityStatus, PlayEntityStatusSpec,
+        test_play_entity_status, bench_write_play_entity_status, bench_read_play_entity_status);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayAttachEntity, PlayAttachEntitySpec,
+        test_play_attach_entity, bench_write_play_attach_entity, bench_read_play_attach_entity);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayEntityMetadata, PlayEntityMetadataSpec,
+        test_play_entity_metadata, bench_write_play_entity_metadata, bench_read_play_entity_metadata);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayEntityEffect, PlayEntityEffectSpec,
+        test_play_entity_effect, bench_write_play_entity_effect, bench_read_play_entity_effect);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayRemoveEntityEffect, PlayRemoveEntityEffectSpec,
+        test_play_remove_entity_effect, bench_write_play_remove_entity_effect, bench_read_play_remove_entity_effect);
+
+    packet_test_cases!(RawPacket47, Packet47, PlaySetExperience, PlaySetExperienceSpec,
+        test_play_set_experience, bench_write_play_set_experience, bench_read_play_set_experience);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayEntityProperties, PlayEntityPropertiesSpec,
+        test_play_entity_properties, bench_write_play_entity_properties, bench_read_play_entity_properties);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayChunkData, PlayChunkDataSpec,
+        test_play_chunk_data, bench_write_play_chunk_data, bench_read_play_chunk_data);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayMultiBlockChange, PlayMultiBlockChangeSpec,
+        test_play_multi_block_change, bench_write_play_multi_block_change, bench_read_play_multi_block_change);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayBlockChange, PlayBlockChangeSpec,
+        test_play_block_change, bench_write_play_block_change, bench_read_play_block_change);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayBlockAction, PlayBlockActionSpec,
+        test_play_block_action, bench_write_play_block_action, bench_read_play_block_action);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayBlockBreakAnimation, PlayBlockBreakAnimationSpec,
+        test_play_block_break_animation, bench_write_play_block_break_animation, bench_read_play_block_break_animation);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayMapChunkBulk, PlayMapChunkBulkSpec,
+        test_play_map_chunk_bulk, bench_write_play_map_chunk_bulk, bench_read_play_map_chunk_bulk);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayExplosion, PlayExplosionSpec,
+        test_play_explosion, bench_write_play_explosion, bench_read_play_explosion);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayEffect, PlayEffectSpec,
+        test_play_effect, bench_write_play_effect, bench_read_play_effect);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayNamedSoundEffect, PlayNamedSoundEffectSpec,
+        test_play_named_sound_effect, bench_write_play_named_sound_effect, bench_read_play_named_sound_effect);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayParticle, PlayParticleSpec,
+        test_play_particle, bench_write_play_particle, bench_read_play_particle);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayChangeGameState, PlayChangeGameStateSpec,
+        test_play_change_game_state, bench_write_play_change_game_state, bench_read_play_change_game_state);
+
+    packet_test_cases!(RawPacket47, Packet47, PlaySpawnWeatherEntity, PlaySpawnWeatherEntitySpec,
+        test_play_spawn_weather_entity, bench_write_play_spawn_weather_entity, bench_read_play_spawn_weather_entity);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayOpenWindow, PlayOpenWindowSpec,
+        test_play_open_window, bench_write_play_open_window, bench_read_play_open_window);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayServerCloseWindow, PlayServerCloseWindowSpec,
+        test_play_server_close_window, bench_write_play_server_close_window, bench_read_play_server_close_window);
+
+    packet_test_cases!(RawPacket47, Packet47, PlaySetSlot, PlaySetSlotSpec,
+        test_play_set_slot, bench_write_play_set_slot, bench_read_play_set_slot);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayWindowItems, PlayWindowItemsSpec,
+        test_play_window_items, bench_write_play_window_items, bench_read_play_window_items);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayWindowProperty, PlayWindowPropertySpec,
+        test_play_window_property, bench_write_play_window_property, bench_read_play_window_property);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayServerWindowConfirmation, PlayServerWindowConfirmationSpec,
+        test_play_server_window_confirmation, bench_write_play_server_window_confirmation, bench_read_play_server_window_confirmation);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayServerUpdateSign, PlayServerUpdateSignSpec,
+        test_play_server_update_sign, bench_write_play_server_update_sign, bench_read_play_server_update_sign);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayMapData, PlayMapDataSpec,
+        test_play_map_data, bench_write_play_map_data, bench_read_play_map_data);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayBlockEntityData, PlayBlockEntityDataSpec,
+        test_play_block_entity_data, bench_write_play_block_entity_data, bench_read_play_block_entity_data);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayOpenSignEditor, PlayOpenSignEditorSpec,
+        test_play_open_sign_editor, bench_write_play_open_sign_editor, bench_read_play_open_sign_editor);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayStatistics, PlayStatisticsSpec,
+        test_play_statistics, bench_write_play_statistics, bench_read_play_statistics);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayPlayerInfo, PlayPlayerInfoSpec,
+        test_play_player_info, bench_write_play_player_info, bench_read_play_player_info);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayServerPlayerAbilities, PlayServerPlayerAbilitiesSpec,
+        test_play_server_player_abilities, bench_write_play_server_player_abilities, bench_read_play_server_player_abilities);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayTabComplete, PlayTabCompleteSpec,
+        test_play_tab_complete, bench_write_play_tab_complete, bench_read_play_tab_complete);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayScoreboardObjective, PlayScoreboardObjectiveSpec,
+        test_play_scoreboard_objective, bench_write_play_scoreboard_objective, bench_read_play_scoreboard_objective);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayUpdateScore, PlayUpdateScoreSpec,
+        test_play_update_score, bench_write_play_update_score, bench_read_play_update_score);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayDisplayScoreboard, PlayDisplayScoreboardSpec,
+        test_play_display_scoreboard, bench_write_play_display_scoreboard, bench_read_play_display_scoreboard);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayTeams, PlayTeamsSpec,
+        test_play_teams, bench_write_play_teams, bench_read_play_teams);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayServerPluginMessage, PlayServerPluginMessageSpec,
+        test_play_server_plugin_message, bench_write_play_server_plugin_message, bench_read_play_server_plugin_message);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayDisconnect, PlayDisconnectSpec,
+        test_play_disconnect, bench_write_play_disconnect, bench_read_play_disconnect);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayServerDifficulty, PlayServerDifficultySpec,
+        test_play_server_difficulty, bench_write_play_server_difficulty, bench_read_play_server_difficulty);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayCombatEvent, PlayCombatEventSpec,
+        test_play_combat_event, bench_write_play_combat_event, bench_read_play_combat_event);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayCamera, PlayCameraSpec,
+        test_play_camera, bench_write_play_camera, bench_read_play_camera);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayWorldBorder, PlayWorldBorderSpec,
+        test_play_world_border, bench_write_play_world_border, bench_read_play_world_border);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayTitle, PlayTitleSpec,
+        test_play_title, bench_write_play_title, bench_read_play_title);
+
+    packet_test_cases!(RawPacket47, Packet47, PlaySetCompression, PlaySetCompressionSpec,
+        test_play_set_compression, bench_write_play_set_compression, bench_read_play_set_compression);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayPlayerListHeaderAndFooter, PlayPlayerListHeaderAndFooterSpec,
+        test_play_player_list_header_and_footer, bench_write_play_player_list_header_and_footer, bench_read_play_player_list_header_and_footer);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayResourcePackSend, PlayResourcePackSendSpec,
+        test_play_resource_pack_send, bench_write_play_resource_pack_send, bench_read_play_resource_pack_send);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayUpdateEntityNbt, PlayUpdateEntityNbtSpec,
+        test_play_update_entity_nbt, bench_write_play_update_entity_nbt, bench_read_play_update_entity_nbt);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayClientKeepAlive, PlayClientKeepAliveSpec,
+        test_play_client_keep_alive, bench_write_play_client_keep_alive, bench_read_play_client_keep_alive);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayClientChatMessage, PlayClientChatMessageSpec,
+        test_play_client_chat_message, bench_write_play_client_chat_message, bench_read_play_client_chat_message);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayInteractEntity, PlayInteractEntitySpec,
+        test_play_interact_entity, bench_write_play_interact_entity, bench_read_play_interact_entity);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayPlayerMovement, PlayPlayerMovementSpec,
+        test_play_player_movement, bench_write_play_player_movement, bench_read_play_player_movement);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayPlayerPosition, PlayPlayerPositionSpec,
+        test_play_player_position, bench_write_play_player_position, bench_read_play_player_position);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayPlayerRotation, PlayPlayerRotationSpec,
+        test_play_player_rotation, bench_write_play_player_rotation, bench_read_play_player_rotation);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayClientPlayerPositionAndRotation, PlayClientPlayerPositionAndRotationSpec,
+        test_play_client_player_position_and_rotation, bench_write_play_client_player_position_and_rotation, bench_read_play_client_player_position_and_rotation);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayPlayerDigging, PlayPlayerDiggingSpec,
+        test_play_player_digging, bench_write_play_player_digging, bench_read_play_player_digging);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayBlockPlacement, PlayBlockPlacementSpec,
+        test_play_block_placement, bench_write_play_block_placement, bench_read_play_block_placement);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayClientHeldItemChange, PlayClientHeldItemChangeSpec,
+        test_play_client_held_item_change, bench_write_play_client_held_item_change, bench_read_play_client_held_item_change);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayClientAnimation, PlayClientAnimationSpec,
+        test_play_client_animation, bench_write_play_client_animation, bench_read_play_client_animation);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayEntityAction, PlayEntityActionSpec,
+        test_play_entity_action, bench_write_play_entity_action, bench_read_play_entity_action);
+
+    packet_test_cases!(RawPacket47, Packet47, PlaySteerVehicle, PlaySteerVehicleSpec,
+        test_play_steer_vehicle, bench_write_play_steer_vehicle, bench_read_play_steer_vehicle);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayClientCloseWindow, PlayClientCloseWindowSpec,
+        test_play_client_close_window, bench_write_play_client_close_window, bench_read_play_client_close_window);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayClickWindow, PlayClickWindowSpec,
+        test_play_click_window, bench_write_play_click_window, bench_read_play_click_window);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayClientWindowConfirmation, PlayClientWindowConfirmationSpec,
+        test_play_client_window_confirmation, bench_write_play_client_window_confirmation, bench_read_play_client_window_confirmation);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayCreativeInventoryAction, PlayCreativeInventoryActionSpec,
+        test_play_creative_inventory_action, bench_write_play_creative_inventory_action, bench_read_play_creative_inventory_action);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayEnchantItem, PlayEnchantItemSpec,
+        test_play_enchant_item, bench_write_play_enchant_item, bench_read_play_enchant_item);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayClientUpdateSign, PlayClientUpdateSignSpec,
+        test_play_client_update_sign, bench_write_play_client_update_sign, bench_read_play_client_update_sign);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayClientPlayerAbilities, PlayClientPlayerAbilitiesSpec,
+        test_play_client_player_abilities, bench_write_play_client_player_abilities, bench_read_play_client_player_abilities);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayClientTabComplete, PlayClientTabCompleteSpec,
+        test_play_client_tab_complete, bench_write_play_client_tab_complete, bench_read_play_client_tab_complete);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayClientSettings, PlayClientSettingsSpec,
+        test_play_client_settings, bench_write_play_client_settings, bench_read_play_client_settings);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayClientStatus, PlayClientStatusSpec,
+        test_play_client_status, bench_write_play_client_status, bench_read_play_client_status);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayClientPluginMessage, PlayClientPluginMessageSpec,
+        test_play_client_plugin_message, bench_write_play_client_plugin_message, bench_read_play_client_plugin_message);
+
+    packet_test_cases!(RawPacket47, Packet47, PlaySpectate, PlaySpectateSpec,
+        test_play_spectate, bench_write_play_spectate, bench_read_play_spectate);
+
+    packet_test_cases!(RawPacket47, Packet47, PlayResourcePackStatus, PlayResourcePackStatusSpec,
+        test_play_resource_pack_status, bench_write_play_resource_pack_status, bench_read_play_resource_pack_status);
+
+    fn serialize_bytes<T: Serialize>(value: &T) -> Vec<u8> {
+        let mut serializer = BytesSerializer::default();
+        value.mc_serialize(&mut serializer).expect("serializes");
+        serializer.into_bytes()
+    }
+
+    #[test]
+    fn test_slot_layout() {
+        assert_eq!(serialize_bytes(&Slot(None)), alloc::vec![0xFF, 0xFF]);
+
+        let slot: Slot = ItemStack {
+            item_id: 1,
+            item_count: 64,
+            damage: 3,
+            nbt: None,
+        }.into();
+        let bytes = serialize_bytes(&slot);
+        assert_eq!(bytes, alloc::vec![0x00, 0x01, 0x40, 0x00, 0x03, 0x00]);
+        assert_eq!(Slot::mc_deserialize(bytes.as_slice()).expect("deserializes").value, slot);
+    }
+
+    #[test]
+    fn test_metadata_key_packing() {
+        let mut metadata = EntityMetadata::default();
+        metadata.set(0, EntityMetadataFieldData::Byte(0x08));
+        metadata.set(2, EntityMetadataFieldData::String("Notch".to_owned()));
+        metadata.set(6, EntityMetadataFieldData::Float(20.0));
+
+        let bytes = serialize_bytes(&metadata);
+        assert_eq!(bytes[..2], [0x00, 0x08]);
+        assert_eq!(bytes[2], 0x82);
+        assert_eq!(bytes[9], 0x66);
+        assert_eq!(*bytes.last().unwrap(), 0x7F);
+        assert_eq!(EntityMetadata::mc_deserialize(bytes.as_slice()).expect("deserializes").value, metadata);
+    }
+
+    #[test]
+    fn test_metadata_end_marker_collision() {
+        let mut metadata = EntityMetadata::default();
+        metadata.set(METADATA_MAX_INDEX, EntityMetadataFieldData::Float(1.0));
+
+        assert!(metadata.mc_serialize(&mut BytesSerializer::default()).is_err());
+    }
+
+    #[test]
+    fn test_legacy_position_layout() {
+        let packet = PlayBlockChangeSpec {
+            location: LegacyIntPosition { x: -1, y: 64, z: 1 },
+            block_id: VarInt(1 << 4),
+        };
+
+        let bytes = serialize_bytes(&packet);
+        assert_eq!(bytes[..8], [0xFF, 0xFF, 0xFF, 0xC1, 0x00, 0x00, 0x00, 0x01]);
+        assert_eq!(PlayBlockChangeSpec::mc_deserialize(bytes.as_slice()).expect("deserializes").value, packet);
+    }
+
+    #[test]
+    fn test_object_data_velocity() {
+        let without = ObjectData { data: 0, velocity: None };
+        assert_eq!(serialize_bytes(&without), alloc::vec![0x00, 0x00, 0x00, 0x00]);
+
+        let with = ObjectData { data: 1, velocity: Some(Vec3 { x: 1, y: -1, z: 0 }) };
+        let bytes = serialize_bytes(&with);
+        assert_eq!(bytes, alloc::vec![0x00, 0x00, 0x00, 0x01, 0x00, 0x01, 0xFF, 0xFF, 0x00, 0x00]);
+        assert_eq!(ObjectData::mc_deserialize(bytes.as_slice()).expect("deserializes").value, with);
+    }
+
+    #[test]
+    fn test_equipment_slot_is_short() {
+        assert_eq!(serialize_bytes(&EquipmentSlot::ArmorHelmet), alloc::vec![0x00, 0x04]);
+    }
+}